@@ -1,13 +1,17 @@
 #![no_std]
 
 use core::cell::Cell;
+use libtock_platform as platform;
 use libtock_platform::{
     share, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
-pub struct Adc<S: Syscalls>(S);
+mod sampling;
+pub use sampling::{samples, SamplingEvent, SamplingListener};
 
-impl<S: Syscalls> Adc<S> {
+pub struct Adc<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Adc<S, C> {
     /// Returns Ok() if the driver was present.This does not necessarily mean
     /// that the driver is working.
     pub fn exists() -> Result<(), ErrorCode> {
@@ -16,7 +20,12 @@ impl<S: Syscalls> Adc<S> {
 
     // Initiate a sample reading
     pub fn read_single_sample() -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, SINGLE_SAMPLE, 0, 0).to_result()
+        Self::sample_channel(0)
+    }
+
+    /// Initiates a sample reading on `channel`
+    pub fn sample_channel(channel: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, SINGLE_SAMPLE, channel, 0).to_result()
     }
 
     // Register a listener to be called when the ADC conversion is finished
@@ -24,7 +33,7 @@ impl<S: Syscalls> Adc<S> {
         listener: &'share ADCListener<F>,
         subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
     ) -> Result<(), ErrorCode> {
-        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
+        S::subscribe::<_, _, C, DRIVER_NUM, 0>(subscribe, listener)
     }
 
     /// Unregister the events listener
@@ -35,13 +44,19 @@ impl<S: Syscalls> Adc<S> {
     /// Initiates a synchronous ADC conversion
     /// Returns the converted ADC value or an error
     pub fn read_single_sample_sync() -> Result<u16, ErrorCode> {
+        Self::sample_channel_sync(0)
+    }
+
+    /// Initiates a synchronous ADC conversion on `channel`
+    /// Returns the converted ADC value or an error
+    pub fn sample_channel_sync(channel: u32) -> Result<u16, ErrorCode> {
         let sample: Cell<Option<u16>> = Cell::new(None);
         let listener = ADCListener(|adc_val| {
             sample.set(Some(adc_val));
         });
         share::scope(|subscribe| {
             Self::register_listener(&listener, subscribe)?;
-            Self::read_single_sample()?;
+            Self::sample_channel(channel)?;
             while sample.get() == None {
                 S::yield_wait();
             }
@@ -53,6 +68,19 @@ impl<S: Syscalls> Adc<S> {
         })
     }
 
+    /// Samples each of `channels` in turn, storing the samples in the same
+    /// order in `samples`. Returns `Size` if `samples` is shorter than
+    /// `channels`.
+    pub fn sample_channels_sync(channels: &[u32], samples: &mut [u16]) -> Result<(), ErrorCode> {
+        if samples.len() < channels.len() {
+            return Err(ErrorCode::Size);
+        }
+        for (&channel, sample) in channels.iter().zip(samples.iter_mut()) {
+            *sample = Self::sample_channel_sync(channel)?;
+        }
+        Ok(())
+    }
+
     /// Returns the number of ADC resolution bits
     pub fn get_resolution_bits() -> Result<u32, ErrorCode> {
         S::command(DRIVER_NUM, GET_RES_BITS, 0, 0).to_result()
@@ -62,6 +90,26 @@ impl<S: Syscalls> Adc<S> {
     pub fn get_reference_voltage_mv() -> Result<u32, ErrorCode> {
         S::command(DRIVER_NUM, GET_VOLTAGE_REF, 0, 0).to_result()
     }
+
+    /// Converts a raw sample to millivolts, using the driver's reference
+    /// voltage and resolution.
+    pub fn to_millivolts(sample: u16) -> Result<u32, ErrorCode> {
+        Ok(sample_to_millivolts(
+            sample,
+            Self::get_reference_voltage_mv()?,
+            Self::get_resolution_bits()?,
+        ))
+    }
+}
+
+/// Converts a raw sample to millivolts. A sample of `2^resolution_bits - 1`
+/// (the largest sample) is `reference_mv`.
+pub fn sample_to_millivolts(sample: u16, reference_mv: u32, resolution_bits: u32) -> u32 {
+    let max = (1u64 << resolution_bits.min(32)) - 1;
+    if max == 0 {
+        return 0;
+    }
+    (u64::from(sample) * u64::from(reference_mv) / max) as u32
 }
 
 pub struct ADCListener<F: Fn(u16)>(pub F);
@@ -72,6 +120,12 @@ impl<F: Fn(u16)> Upcall<OneId<DRIVER_NUM, 0>> for ADCListener<F> {
     }
 }
 
+/// System call configuration trait for `Adc`.
+pub trait Config: platform::allow_rw::Config + platform::subscribe::Config {}
+impl<T: platform::allow_rw::Config + platform::subscribe::Config> Config for T {}
+
+#[cfg(test)]
+mod sampling_tests;
 #[cfg(test)]
 mod tests;
 
@@ -85,9 +139,13 @@ const DRIVER_NUM: u32 = 0x5;
 
 const EXISTS: u32 = 0;
 const SINGLE_SAMPLE: u32 = 1;
-// const REPEAT_SINGLE_SAMPLE: u32 = 2;
-// const MULTIPLE_SAMPLE: u32 = 3;
-// const CONTINUOUS_BUFF_SAMPLE: u32 = 4;
-// const STOP_SAMPLE: u32 = 5;
+const REPEAT_SINGLE_SAMPLE: u32 = 2;
+const MULTIPLE_SAMPLE: u32 = 3;
+const CONTINUOUS_BUFF_SAMPLE: u32 = 4;
+const STOP_SAMPLE: u32 = 5;
 const GET_RES_BITS: u32 = 101;
 const GET_VOLTAGE_REF: u32 = 102;
+
+// Allow IDs
+const FIRST_BUFFER: u32 = 0;
+const SECOND_BUFFER: u32 = 1;
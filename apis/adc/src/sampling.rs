@@ -0,0 +1,189 @@
+use crate::{
+    Adc, Config, CONTINUOUS_BUFF_SAMPLE, DRIVER_NUM, FIRST_BUFFER, MULTIPLE_SAMPLE,
+    REPEAT_SINGLE_SAMPLE, SECOND_BUFFER, STOP_SAMPLE,
+};
+use core::cell::Cell;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{OneId, Subscribe, SupportsId};
+use libtock_platform::{ErrorCode, Syscalls, Upcall};
+
+// The sampling mode the driver reports as the upcall's first argument.
+const MODE_REPEATED: u32 = 1;
+const MODE_BUFFERED: u32 = 2;
+const MODE_DOUBLE_BUFFERED: u32 = 3;
+
+/// Continuous and buffered sampling.
+///
+/// Repeated sampling reports each sample in its own upcall. Buffered sampling
+/// fills the first buffer with samples and stops, and double-buffered
+/// sampling keeps sampling, alternating between the two buffers and reporting
+/// each one as it fills. Buffers hold samples as little-endian `u16`s; use
+/// [`samples`] to read them back.
+///
+/// Shared buffers cannot be read until the `share::scope` that shares them
+/// ends, so sampling should be stopped before the samples are read.
+impl<S: Syscalls, C: Config> Adc<S, C> {
+    /// Starts sampling `channel` at `frequency` Hz, reporting every sample.
+    pub fn start_repeated(channel: u32, frequency: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, REPEAT_SINGLE_SAMPLE, channel, frequency).to_result()
+    }
+
+    /// Starts sampling `channel` at `frequency` Hz until the first buffer is
+    /// full.
+    pub fn start_buffered(channel: u32, frequency: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, MULTIPLE_SAMPLE, channel, frequency).to_result()
+    }
+
+    /// Starts sampling `channel` at `frequency` Hz into the first and second
+    /// buffers in turn, until sampling is stopped.
+    pub fn start_double_buffered(channel: u32, frequency: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, CONTINUOUS_BUFF_SAMPLE, channel, frequency).to_result()
+    }
+
+    /// Stops repeated, buffered or double-buffered sampling.
+    pub fn stop_sampling() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, STOP_SAMPLE, 0, 0).to_result()
+    }
+
+    /// Shares the buffer that buffered sampling fills, and that
+    /// double-buffered sampling fills first.
+    pub fn allow_buffer<'share>(
+        buffer: &'share mut [u8],
+        allow_rw: share::Handle<AllowRw<'share, S, DRIVER_NUM, FIRST_BUFFER>>,
+    ) -> Result<(), ErrorCode> {
+        S::allow_rw::<C, DRIVER_NUM, FIRST_BUFFER>(allow_rw, buffer)
+    }
+
+    /// Shares the buffer that double-buffered sampling fills second.
+    pub fn allow_second_buffer<'share>(
+        buffer: &'share mut [u8],
+        allow_rw: share::Handle<AllowRw<'share, S, DRIVER_NUM, SECOND_BUFFER>>,
+    ) -> Result<(), ErrorCode> {
+        S::allow_rw::<C, DRIVER_NUM, SECOND_BUFFER>(allow_rw, buffer)
+    }
+
+    /// Registers a listener for sampling upcalls, usually a
+    /// `SamplingListener`. Replaces any listener registered with
+    /// `register_listener`, as both use the same upcall.
+    pub fn register_sampling_listener<'share, IDS: SupportsId<DRIVER_NUM, 0>, U: Upcall<IDS>>(
+        listener: &'share U,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, 0>(subscribe, listener)
+    }
+
+    /// Samples `channel` at `frequency` Hz, calling `on_sample` with each
+    /// sample until it returns `false`.
+    pub fn sample_repeated_sync<F: FnMut(u16) -> bool>(
+        channel: u32,
+        frequency: u32,
+        mut on_sample: F,
+    ) -> Result<(), ErrorCode> {
+        let sample: Cell<Option<u16>> = Cell::new(None);
+        let listener = SamplingListener(|event| {
+            if let SamplingEvent::Sample { sample: value, .. } = event {
+                sample.set(Some(value));
+            }
+        });
+        share::scope(|subscribe| {
+            Self::register_sampling_listener(&listener, subscribe)?;
+            Self::start_repeated(channel, frequency)?;
+            loop {
+                S::yield_wait();
+                if let Some(value) = sample.take() {
+                    if !on_sample(value) {
+                        return Self::stop_sampling();
+                    }
+                }
+            }
+        })
+    }
+
+    /// Fills `buffer` with samples of `channel` taken at `frequency` Hz, and
+    /// returns the number of samples.
+    pub fn read_buffered_sync(
+        channel: u32,
+        frequency: u32,
+        buffer: &mut [u8],
+    ) -> Result<usize, ErrorCode> {
+        let filled: Cell<Option<usize>> = Cell::new(None);
+        let listener = SamplingListener(|event| {
+            if let SamplingEvent::BufferFull { len, .. } = event {
+                filled.set(Some(len));
+            }
+        });
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, FIRST_BUFFER>,
+                Subscribe<_, DRIVER_NUM, 0>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            Self::allow_buffer(buffer, allow_rw)?;
+            Self::register_sampling_listener(&listener, subscribe)?;
+            Self::start_buffered(channel, frequency)?;
+            loop {
+                S::yield_wait();
+                if let Some(len) = filled.get() {
+                    return Ok(len);
+                }
+            }
+        })
+    }
+}
+
+/// An event reported during repeated, buffered or double-buffered sampling.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SamplingEvent {
+    /// A sample taken by repeated sampling.
+    Sample { channel: u32, sample: u16 },
+    /// A buffer was filled with `len` samples. `address` is the address of
+    /// the buffer, to tell the first and second buffers apart.
+    BufferFull {
+        channel: u32,
+        len: usize,
+        address: usize,
+    },
+}
+
+/// A wrapper around a closure to be registered and called for each
+/// `SamplingEvent`.
+///
+/// ```ignore
+/// let listener = SamplingListener(|event| {
+///     if let SamplingEvent::Sample { sample, .. } = event {
+///         // make use of the sample
+///     }
+/// });
+/// ```
+pub struct SamplingListener<F: Fn(SamplingEvent)>(pub F);
+
+impl<F: Fn(SamplingEvent)> Upcall<OneId<DRIVER_NUM, 0>> for SamplingListener<F> {
+    fn upcall(&self, mode: u32, arg1: u32, arg2: u32) {
+        match mode {
+            MODE_REPEATED => self.0(SamplingEvent::Sample {
+                channel: arg1,
+                sample: arg2 as u16,
+            }),
+            // For buffers, the second argument holds the channel in its low
+            // byte and the number of samples above it.
+            MODE_BUFFERED | MODE_DOUBLE_BUFFERED => self.0(SamplingEvent::BufferFull {
+                channel: arg1 & 0xff,
+                len: (arg1 >> 8) as usize,
+                address: arg2 as usize,
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the samples stored in a buffer filled by buffered or
+/// double-buffered sampling.
+pub fn samples(buffer: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    buffer
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
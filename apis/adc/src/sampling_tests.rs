@@ -0,0 +1,145 @@
+use core::cell::Cell;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::{share, ErrorCode, Subscribe, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+use crate::{samples, SamplingEvent, SamplingListener};
+
+type Adc = super::Adc<fake::Syscalls>;
+
+// Upcall arguments are 32 bits, so the fake reports buffer addresses
+// truncated to 32 bits.
+fn address(buffer: &[u8]) -> usize {
+    buffer.as_ptr() as u32 as usize
+}
+
+#[test]
+fn repeated() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    let last: Cell<Option<SamplingEvent>> = Cell::new(None);
+    let listener = SamplingListener(|event| last.set(Some(event)));
+    share::scope(|subscribe| {
+        assert_eq!(
+            Adc::register_sampling_listener(&listener, subscribe),
+            Ok(())
+        );
+        assert_eq!(Adc::start_repeated(2, 0), Err(ErrorCode::Invalid));
+        assert_eq!(Adc::start_repeated(2, 100), Ok(()));
+        assert_eq!(driver.sampling(), Some((2, 100)));
+        assert_eq!(Adc::start_repeated(2, 100), Err(ErrorCode::Busy));
+        assert_eq!(Adc::read_single_sample(), Err(ErrorCode::Busy));
+
+        driver.push_samples(&[10, 20]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            last.get(),
+            Some(SamplingEvent::Sample {
+                channel: 2,
+                sample: 10
+            })
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            last.get(),
+            Some(SamplingEvent::Sample {
+                channel: 2,
+                sample: 20
+            })
+        );
+
+        assert_eq!(Adc::stop_sampling(), Ok(()));
+        assert_eq!(driver.sampling(), None);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
+
+#[test]
+fn sample_repeated_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    driver.push_samples(&[1, 2, 3]);
+    let sum = Cell::new(0);
+    let result = Adc::sample_repeated_sync(1, 1000, |sample| {
+        sum.set(sum.get() + sample);
+        sample < 3
+    });
+    assert_eq!(result, Ok(()));
+    assert_eq!(sum.get(), 6);
+    assert_eq!(driver.sampling(), None);
+}
+
+#[test]
+fn read_buffered_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; 6];
+    assert_eq!(
+        Adc::read_buffered_sync(0, 100, &mut []),
+        Err(ErrorCode::Size)
+    );
+    driver.push_samples(&[0x0102, 0x0304, 0x0506, 0x0708]);
+    assert_eq!(Adc::read_buffered_sync(0, 100, &mut buffer), Ok(3));
+    assert_eq!(driver.sampling(), None);
+    assert!(samples(&buffer).eq([0x0102, 0x0304, 0x0506]));
+    assert_eq!(buffer[..2], [0x02, 0x01]);
+}
+
+#[test]
+fn double_buffered() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    let mut first = [0; 4];
+    let mut second = [0; 4];
+    let (first_address, second_address) = (address(&first), address(&second));
+    let last: Cell<Option<SamplingEvent>> = Cell::new(None);
+    let listener = SamplingListener(|event| last.set(Some(event)));
+    share::scope::<(AllowRw<_, 5, 0>, AllowRw<_, 5, 1>, Subscribe<_, 5, 0>), _, _>(|handle| {
+        let (allow_first, allow_second, subscribe) = handle.split();
+        assert_eq!(Adc::allow_buffer(&mut first, allow_first), Ok(()));
+        assert_eq!(Adc::allow_second_buffer(&mut second, allow_second), Ok(()));
+        assert_eq!(
+            Adc::register_sampling_listener(&listener, subscribe),
+            Ok(())
+        );
+        assert_eq!(Adc::start_double_buffered(3, 500), Ok(()));
+
+        driver.push_samples(&[1, 2, 3]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            last.get(),
+            Some(SamplingEvent::BufferFull {
+                channel: 3,
+                len: 2,
+                address: first_address
+            })
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        driver.push_samples(&[4]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            last.get(),
+            Some(SamplingEvent::BufferFull {
+                channel: 3,
+                len: 2,
+                address: second_address
+            })
+        );
+
+        // Sampling wraps around to the first buffer.
+        driver.push_samples(&[5]);
+        assert_eq!(driver.sampling(), Some((3, 500)));
+        assert_eq!(Adc::stop_sampling(), Ok(()));
+    });
+    assert!(samples(&first).eq([5, 2]));
+    assert!(samples(&second).eq([3, 4]));
+}
@@ -69,3 +69,39 @@ fn read_single_sample_sync() {
     driver.set_value_sync(1000);
     assert_eq!(Adc::read_single_sample_sync(), Ok(1000));
 }
+
+#[test]
+fn sample_channels_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    driver.set_channel_value(0, 100);
+    driver.set_channel_value(3, 300);
+    let mut samples = [0; 3];
+    assert_eq!(Adc::sample_channel_sync(3), Ok(300));
+    assert_eq!(Adc::sample_channels_sync(&[3, 0], &mut samples), Ok(()));
+    assert_eq!(samples, [300, 100, 0]);
+    assert_eq!(
+        Adc::sample_channels_sync(&[0, 3], &mut samples[..1]),
+        Err(ErrorCode::Size)
+    );
+}
+
+#[test]
+fn millivolts() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::new();
+    kernel.add_driver(&driver);
+
+    driver.set_resolution_bits(10);
+    driver.set_reference_voltage_mv(3300);
+    assert_eq!(Adc::get_resolution_bits(), Ok(10));
+    assert_eq!(Adc::get_reference_voltage_mv(), Ok(3300));
+    assert_eq!(Adc::to_millivolts(0), Ok(0));
+    assert_eq!(Adc::to_millivolts(1023), Ok(3300));
+    assert_eq!(Adc::to_millivolts(341), Ok(1100));
+
+    assert_eq!(crate::sample_to_millivolts(u16::MAX, 1800, 16), 1800);
+    assert_eq!(crate::sample_to_millivolts(5, 1800, 0), 0);
+}
@@ -0,0 +1,215 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The AES-128 driver.
+///
+/// An operation starts with `set_mode` and `setup`, which loads the key and
+/// IV into the kernel. In the CTR, CBC and ECB modes, the data can then be
+/// processed in any number of `crypt` calls, each of which continues where
+/// the previous one stopped. CCM processes a whole message in a single
+/// `ccm_crypt` call.
+///
+/// # Example
+/// ```ignore
+/// use libtock::aes::{Aes, AesMode, Direction};
+///
+/// let mut ciphertext = [0; 32];
+/// Aes::set_mode(AesMode::Ctr, Direction::Encrypt)?;
+/// Aes::setup(&key, &initial_counter)?;
+/// Aes::crypt(&plaintext[..16], &mut ciphertext[..16])?;
+/// Aes::crypt(&plaintext[16..], &mut ciphertext[16..])?;
+/// Aes::finish()?;
+/// ```
+pub struct Aes<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Aes<S, C> {
+    /// Run a check against the AES capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Selects the mode and direction of the next operation.
+    pub fn set_mode(mode: AesMode, direction: Direction) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            command::SET_MODE,
+            mode as u32,
+            (direction == Direction::Encrypt) as u32,
+        )
+        .to_result()
+    }
+
+    /// Loads `key` and `iv`, and starts a new operation. `iv` is the initial
+    /// counter block in CTR mode, the IV in CBC mode and the
+    /// `CCM_NONCE_LEN`-byte nonce in CCM mode. ECB mode ignores it.
+    pub fn setup(key: &[u8; KEY_LEN], iv: &[u8]) -> Result<(), ErrorCode> {
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::IV }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_iv) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::IV }>(allow_iv, iv)?;
+            S::command(DRIVER_NUM, command::SETUP, 0, 0).to_result()
+        })
+    }
+
+    /// Encrypts or decrypts `source` into the start of `destination`, in the
+    /// CTR, CBC or ECB mode. In the CBC and ECB modes, the length of `source`
+    /// must be a multiple of `BLOCK_LEN`.
+    pub fn crypt(source: &[u8], destination: &mut [u8]) -> Result<(), ErrorCode> {
+        if destination.len() < source.len() {
+            return Err(ErrorCode::Size);
+        }
+        Self::run(command::CRYPT, 0, &[], source, destination).map(|_| ())
+    }
+
+    /// Encrypts or decrypts a whole message in CCM mode, authenticating it
+    /// together with `aad`. Returns the number of bytes written into
+    /// `destination`.
+    ///
+    /// When encrypting, `source` is the plaintext and `destination` receives
+    /// the ciphertext followed by a `mic_len`-byte message integrity code.
+    /// When decrypting, `source` is the ciphertext followed by the MIC, and
+    /// `destination` receives the plaintext. Returns `Err(ErrorCode::Fail)`
+    /// if the MIC does not match.
+    pub fn ccm_crypt(
+        aad: &[u8],
+        source: &[u8],
+        destination: &mut [u8],
+        mic_len: usize,
+    ) -> Result<usize, ErrorCode> {
+        Self::run(command::CCM_CRYPT, mic_len as u32, aad, source, destination)
+    }
+
+    /// Ends the current operation.
+    pub fn finish() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AesMode {
+    Ctr = 0,
+    Cbc = 1,
+    Ecb = 2,
+    Ccm = 3,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// The length of an AES-128 key.
+pub const KEY_LEN: usize = 16;
+
+/// The length of an AES block, and of the CTR and CBC IVs.
+pub const BLOCK_LEN: usize = 16;
+
+/// The length of the CCM nonce.
+pub const CCM_NONCE_LEN: usize = 13;
+
+/// System call configuration trait for `Aes`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> Aes<S, C> {
+    // Runs a command with the AAD, source and destination buffers shared, and
+    // waits for its upcall. Returns the number of bytes written into
+    // `destination`.
+    fn run(
+        command_id: u32,
+        argument0: u32,
+        aad: &[u8],
+        source: &[u8],
+        destination: &mut [u8],
+    ) -> Result<usize, ErrorCode> {
+        let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::AAD }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::SOURCE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::DESTINATION }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_aad, allow_source, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::AAD }>(allow_aad, aad)?;
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::SOURCE }>(allow_source, source)?;
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DESTINATION }>(allow_rw, destination)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command_id, argument0, 0).to_result::<(), _>()?;
+            loop {
+                S::yield_wait();
+                if let Some((status, len)) = done.get() {
+                    return match status {
+                        0 => Ok(len as usize),
+                        _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                    };
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_MODE: u32 = 1;
+    pub const SETUP: u32 = 2;
+    pub const CRYPT: u32 = 3;
+    pub const FINISH: u32 = 4;
+    pub const CCM_CRYPT: u32 = 5;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const IV: u32 = 1;
+    pub const SOURCE: u32 = 2;
+    pub const AAD: u32 = 3;
+}
+
+mod allow_rw {
+    pub const DESTINATION: u32 = 0;
+}
@@ -0,0 +1,184 @@
+use crate::{AesMode, Direction, BLOCK_LEN, KEY_LEN};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Aes = super::Aes<fake::Syscalls>;
+
+// The key and first two plaintext blocks of the NIST SP 800-38A examples.
+const KEY: [u8; KEY_LEN] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const PLAINTEXT: [u8; 2 * BLOCK_LEN] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Aes::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Aes::exists(), Ok(()));
+}
+
+#[test]
+fn ecb() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let mut ciphertext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Ecb, Direction::Encrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &[]), Ok(()));
+    assert_eq!(driver.key(), Some(KEY));
+    assert_eq!(Aes::crypt(&PLAINTEXT, &mut ciphertext), Ok(()));
+    assert_eq!(Aes::finish(), Ok(()));
+    assert_eq!(
+        ciphertext,
+        [
+            0x3a, 0xd7, 0x7b, 0xb4, 0x0d, 0x7a, 0x36, 0x60, 0xa8, 0x9e, 0xca, 0xf3, 0x24, 0x66,
+            0xef, 0x97, 0xf5, 0xd3, 0xd5, 0x85, 0x03, 0xb9, 0x69, 0x9d, 0xe7, 0x85, 0x89, 0x5a,
+            0x96, 0xfd, 0xba, 0xaf,
+        ]
+    );
+
+    let mut plaintext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Ecb, Direction::Decrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &[]), Ok(()));
+    assert_eq!(Aes::crypt(&ciphertext, &mut plaintext), Ok(()));
+    assert_eq!(plaintext, PLAINTEXT);
+
+    // ECB only processes whole blocks.
+    assert_eq!(
+        Aes::crypt(&PLAINTEXT[..5], &mut plaintext),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        Aes::crypt(&PLAINTEXT, &mut plaintext[..BLOCK_LEN]),
+        Err(ErrorCode::Size)
+    );
+}
+
+#[test]
+fn cbc_streaming() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let iv: [u8; BLOCK_LEN] = core::array::from_fn(|i| i as u8);
+    let expected = [
+        0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46, 0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19,
+        0x7d, 0x50, 0x86, 0xcb, 0x9b, 0x50, 0x72, 0x19, 0xee, 0x95, 0xdb, 0x11, 0x3a, 0x91, 0x76,
+        0x78, 0xb2,
+    ];
+
+    // The chaining continues across calls to `crypt`.
+    let mut ciphertext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Cbc, Direction::Encrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &iv), Ok(()));
+    let (first, second) = ciphertext.split_at_mut(BLOCK_LEN);
+    assert_eq!(Aes::crypt(&PLAINTEXT[..BLOCK_LEN], first), Ok(()));
+    assert_eq!(Aes::crypt(&PLAINTEXT[BLOCK_LEN..], second), Ok(()));
+    assert_eq!(ciphertext, expected);
+
+    let mut plaintext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Cbc, Direction::Decrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &iv), Ok(()));
+    assert_eq!(Aes::crypt(&expected, &mut plaintext), Ok(()));
+    assert_eq!(plaintext, PLAINTEXT);
+}
+
+#[test]
+fn ctr_streaming() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let counter: [u8; BLOCK_LEN] = core::array::from_fn(|i| 0xf0 + i as u8);
+    let expected = [
+        0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6,
+        0xce, 0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff,
+        0xfd, 0xff,
+    ];
+
+    // CTR mode does not need whole blocks, and the keystream continues
+    // across calls to `crypt`.
+    let mut ciphertext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Ctr, Direction::Encrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &counter), Ok(()));
+    let (first, second) = ciphertext.split_at_mut(5);
+    assert_eq!(Aes::crypt(&PLAINTEXT[..5], first), Ok(()));
+    assert_eq!(Aes::crypt(&PLAINTEXT[5..], second), Ok(()));
+    assert_eq!(ciphertext, expected);
+
+    let mut plaintext = [0; 2 * BLOCK_LEN];
+    assert_eq!(Aes::set_mode(AesMode::Ctr, Direction::Decrypt), Ok(()));
+    assert_eq!(Aes::setup(&KEY, &counter), Ok(()));
+    assert_eq!(Aes::crypt(&expected, &mut plaintext), Ok(()));
+    assert_eq!(plaintext, PLAINTEXT);
+}
+
+#[test]
+fn ccm() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    // Packet vector #1 of RFC 3610.
+    let key: [u8; KEY_LEN] = core::array::from_fn(|i| 0xc0 + i as u8);
+    let nonce = [
+        0x00, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5,
+    ];
+    let aad: [u8; 8] = core::array::from_fn(|i| i as u8);
+    let payload: [u8; 23] = core::array::from_fn(|i| 8 + i as u8);
+    let expected = [
+        0x58, 0x8c, 0x97, 0x9a, 0x61, 0xc6, 0x63, 0xd2, 0xf0, 0x66, 0xd0, 0xc2, 0xc0, 0xf9, 0x89,
+        0x80, 0x6d, 0x5f, 0x6b, 0x61, 0xda, 0xc3, 0x84, 0x17, 0xe8, 0xd1, 0x2c, 0xfd, 0xf9, 0x26,
+        0xe0,
+    ];
+
+    let mut sealed = [0; 40];
+    assert_eq!(Aes::set_mode(AesMode::Ccm, Direction::Encrypt), Ok(()));
+    assert_eq!(Aes::setup(&key, &nonce), Ok(()));
+    assert_eq!(Aes::ccm_crypt(&aad, &payload, &mut sealed, 8), Ok(31));
+    assert_eq!(sealed[..31], expected);
+    assert_eq!(
+        Aes::ccm_crypt(&aad, &payload, &mut sealed[..30], 8),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(
+        Aes::ccm_crypt(&aad, &payload, &mut sealed, 5),
+        Err(ErrorCode::Invalid)
+    );
+
+    let mut opened = [0; 23];
+    assert_eq!(Aes::set_mode(AesMode::Ccm, Direction::Decrypt), Ok(()));
+    assert_eq!(Aes::setup(&key, &nonce), Ok(()));
+    assert_eq!(Aes::ccm_crypt(&aad, &expected, &mut opened, 8), Ok(23));
+    assert_eq!(opened, payload);
+
+    // Any change to the ciphertext, MIC or associated data is detected.
+    let mut tampered = expected;
+    tampered[3] ^= 1;
+    assert_eq!(
+        Aes::ccm_crypt(&aad, &tampered, &mut opened, 8),
+        Err(ErrorCode::Fail)
+    );
+    let mut tampered = expected;
+    tampered[30] ^= 1;
+    assert_eq!(
+        Aes::ccm_crypt(&aad, &tampered, &mut opened, 8),
+        Err(ErrorCode::Fail)
+    );
+    assert_eq!(
+        Aes::ccm_crypt(&aad[1..], &expected, &mut opened, 8),
+        Err(ErrorCode::Fail)
+    );
+}
@@ -0,0 +1,139 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::fmt;
+use libtock_platform::{
+    share, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+/// The air quality driver, for sensors that measure the CO2 and total volatile
+/// organic compound (TVOC) concentrations, such as the CCS811.
+///
+/// Both measurements are reported through the same upcall, so only one
+/// measurement can be in progress at a time.
+pub struct AirQuality<S: Syscalls>(S);
+
+impl<S: Syscalls> AirQuality<S> {
+    /// Returns Ok() if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Initiate a CO2 measurement. The listener receives the concentration in
+    /// parts per million.
+    pub fn read_co2() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, READ_CO2, 0, 0).to_result()
+    }
+
+    /// Initiate a TVOC measurement. The listener receives the concentration in
+    /// parts per billion.
+    pub fn read_tvoc() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, READ_TVOC, 0, 0).to_result()
+    }
+
+    /// Register an events listener, usually a `Co2Listener` or a
+    /// `TvocListener` matching the measurement being started.
+    pub fn register_listener<'share, U: Upcall<OneId<DRIVER_NUM, 0>>>(
+        listener: &'share U,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
+    }
+
+    /// Unregister the events listener
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, 0)
+    }
+
+    /// Initiate a synchronous CO2 measurement.
+    /// Returns Ok(co2) if the operation was successful.
+    pub fn read_co2_sync() -> Result<PartsPerMillion, ErrorCode> {
+        Self::read_sync(READ_CO2).map(PartsPerMillion)
+    }
+
+    /// Initiate a synchronous TVOC measurement.
+    /// Returns Ok(tvoc) if the operation was successful.
+    pub fn read_tvoc_sync() -> Result<PartsPerBillion, ErrorCode> {
+        Self::read_sync(READ_TVOC).map(PartsPerBillion)
+    }
+}
+
+/// A concentration in parts per million, as used for CO2.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PartsPerMillion(pub u32);
+
+impl fmt::Display for PartsPerMillion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ppm", self.0)
+    }
+}
+
+/// A concentration in parts per billion, as used for TVOC.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PartsPerBillion(pub u32);
+
+impl fmt::Display for PartsPerBillion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ppb", self.0)
+    }
+}
+
+/// A listener for CO2 measurements started with `read_co2`.
+pub struct Co2Listener<F: Fn(PartsPerMillion)>(pub F);
+impl<F: Fn(PartsPerMillion)> Upcall<OneId<DRIVER_NUM, 0>> for Co2Listener<F> {
+    fn upcall(&self, co2: u32, _arg1: u32, _arg2: u32) {
+        self.0(PartsPerMillion(co2))
+    }
+}
+
+/// A listener for TVOC measurements started with `read_tvoc`.
+pub struct TvocListener<F: Fn(PartsPerBillion)>(pub F);
+impl<F: Fn(PartsPerBillion)> Upcall<OneId<DRIVER_NUM, 0>> for TvocListener<F> {
+    fn upcall(&self, tvoc: u32, _arg1: u32, _arg2: u32) {
+        self.0(PartsPerBillion(tvoc))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls> AirQuality<S> {
+    fn read_sync(command_id: u32) -> Result<u32, ErrorCode> {
+        let value_cell: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope(|subscribe| {
+            if let Ok(()) =
+                S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &value_cell)
+            {
+                let started: Result<(), ErrorCode> =
+                    S::command(DRIVER_NUM, command_id, 0, 0).to_result();
+                if let Ok(()) = started {
+                    while value_cell.get() == None {
+                        S::yield_wait();
+                    }
+                }
+            }
+        });
+
+        match value_cell.get() {
+            None => Err(ErrorCode::Busy),
+            Some((value,)) => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60007;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const READ_CO2: u32 = 2;
+const READ_TVOC: u32 = 3;
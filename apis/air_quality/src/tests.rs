@@ -0,0 +1,96 @@
+use crate::{PartsPerBillion, PartsPerMillion};
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type AirQuality = super::AirQuality<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(AirQuality::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AirQuality::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(AirQuality::exists(), Ok(()));
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AirQuality::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(AirQuality::read_co2(), Ok(()));
+    assert!(driver.is_busy());
+
+    assert_eq!(AirQuality::read_co2(), Err(ErrorCode::Busy));
+    assert_eq!(AirQuality::read_tvoc(), Err(ErrorCode::Busy));
+    assert_eq!(AirQuality::read_co2_sync(), Err(ErrorCode::Busy));
+    assert_eq!(AirQuality::read_tvoc_sync(), Err(ErrorCode::Busy));
+}
+
+#[test]
+fn register_unregister_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AirQuality::new();
+    kernel.add_driver(&driver);
+
+    let tvoc_cell: Cell<Option<PartsPerBillion>> = Cell::new(None);
+    let listener = crate::TvocListener(|tvoc| {
+        tvoc_cell.set(Some(tvoc));
+    });
+    share::scope(|subscribe| {
+        assert_eq!(AirQuality::read_co2(), Ok(()));
+        driver.set_value(400);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(AirQuality::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(AirQuality::read_tvoc(), Ok(()));
+        assert_eq!(driver.pending_read(), Some(crate::READ_TVOC));
+        driver.set_value(12);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(tvoc_cell.get(), Some(PartsPerBillion(12)));
+
+        AirQuality::unregister_listener();
+        assert_eq!(AirQuality::read_co2(), Ok(()));
+        driver.set_value(400);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
+
+#[test]
+fn co2_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AirQuality::new();
+    kernel.add_driver(&driver);
+
+    let co2_cell: Cell<Option<PartsPerMillion>> = Cell::new(None);
+    let listener = crate::Co2Listener(|co2| {
+        co2_cell.set(Some(co2));
+    });
+    share::scope(|subscribe| {
+        assert_eq!(AirQuality::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(AirQuality::read_co2(), Ok(()));
+        driver.set_value(415);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(co2_cell.get(), Some(PartsPerMillion(415)));
+    });
+}
+
+#[test]
+fn read_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AirQuality::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(415);
+    assert_eq!(AirQuality::read_co2_sync(), Ok(PartsPerMillion(415)));
+    driver.set_value_sync(30);
+    assert_eq!(AirQuality::read_tvoc_sync(), Ok(PartsPerBillion(30)));
+}
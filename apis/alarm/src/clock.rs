@@ -0,0 +1,224 @@
+use crate::{Alarm, Convert, Hz, Ticks};
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::ops::{Add, Sub};
+use core::time::Duration;
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+impl Hz {
+    /// Converts `duration` to ticks, rounding up. Saturates at `u64::MAX`.
+    pub fn duration_to_ticks(self, duration: Duration) -> u64 {
+        let ticks = (duration.as_nanos() * self.0 as u128 + NANOS_PER_SEC - 1) / NANOS_PER_SEC;
+        ticks.min(u64::MAX as u128) as u64
+    }
+
+    /// Converts `ticks` to a `Duration`, rounding down to a whole number of
+    /// nanoseconds.
+    ///
+    /// Panics if the frequency is zero.
+    pub fn ticks_to_duration(self, ticks: u64) -> Duration {
+        let nanos = ticks as u128 * NANOS_PER_SEC / self.0 as u128;
+        Duration::new(
+            (nanos / NANOS_PER_SEC) as u64,
+            (nanos % NANOS_PER_SEC) as u32,
+        )
+    }
+}
+
+impl Convert for Duration {
+    fn to_ticks(self, freq: Hz) -> Ticks {
+        // Saturates like Milliseconds.
+        let ticks = freq.duration_to_ticks(self);
+        Ticks(ticks.min(u32::MAX as u64) as u32)
+    }
+}
+
+/// A monotonic clock that extends the alarm's 32-bit tick counter to 64 bits.
+///
+/// The clock detects wraparound of the tick counter each time it is read, so
+/// it must be read at least once per wraparound period (`2^32` ticks, which is
+/// about 36 hours at 32768 Hz and 71 minutes at 1 MHz) to stay correct.
+///
+/// # Example
+/// ```ignore
+/// use libtock::alarm::Clock;
+///
+/// let clock = Clock::new()?;
+/// let start = clock.now()?;
+/// do_work();
+/// let elapsed: core::time::Duration = clock.elapsed(start)?;
+/// ```
+pub struct Clock<S: Syscalls, C: platform::subscribe::Config = DefaultConfig> {
+    frequency: Hz,
+    // The upper 32 bits of the tick count, and the tick counter at the last
+    // reading.
+    high: Cell<u32>,
+    last: Cell<u32>,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Clock<S, C> {
+    pub fn new() -> Result<Self, ErrorCode> {
+        Ok(Clock {
+            frequency: Alarm::<S, C>::get_frequency()?,
+            high: Cell::new(0),
+            last: Cell::new(Alarm::<S, C>::get_ticks()?.0),
+            _syscalls: PhantomData,
+        })
+    }
+
+    pub fn frequency(&self) -> Hz {
+        self.frequency
+    }
+
+    pub fn now(&self) -> Result<Instant, ErrorCode> {
+        let ticks = Alarm::<S, C>::get_ticks()?.0;
+        if ticks < self.last.get() {
+            self.high.set(self.high.get().wrapping_add(1));
+        }
+        self.last.set(ticks);
+        Ok(Instant {
+            ticks: ((self.high.get() as u64) << 32) | ticks as u64,
+            frequency: self.frequency,
+        })
+    }
+
+    /// Returns the time elapsed since `earlier`.
+    pub fn elapsed(&self, earlier: Instant) -> Result<Duration, ErrorCode> {
+        Ok(self.now()?.duration_since(earlier))
+    }
+}
+
+/// A point in time, measured by a `Clock`.
+///
+/// Instants are only comparable with instants from the same clock.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Instant {
+    ticks: u64,
+    frequency: Hz,
+}
+
+impl Instant {
+    /// The number of ticks since the clock's tick counter was last zero before
+    /// the clock was created.
+    pub fn ticks(self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the time from `earlier` to `self`, or zero if `earlier` is
+    /// later than `self`.
+    pub fn duration_since(self, earlier: Instant) -> Duration {
+        self.frequency
+            .ticks_to_duration(self.ticks.saturating_sub(earlier.ticks))
+    }
+
+    /// Returns the instant `duration` after `self`, rounded up to a whole
+    /// tick, or `None` on overflow.
+    pub fn checked_add(self, duration: Duration) -> Option<Instant> {
+        let ticks = self
+            .ticks
+            .checked_add(self.frequency.duration_to_ticks(duration))?;
+        Some(Instant { ticks, ..self })
+    }
+
+    /// Returns the instant `duration` before `self`, rounded up to a whole
+    /// tick, or `None` on underflow.
+    pub fn checked_sub(self, duration: Duration) -> Option<Instant> {
+        let ticks = self
+            .ticks
+            .checked_sub(self.frequency.duration_to_ticks(duration))?;
+        Some(Instant { ticks, ..self })
+    }
+}
+
+impl PartialOrd for Instant {
+    fn partial_cmp(&self, other: &Instant) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Instant {
+    fn cmp(&self, other: &Instant) -> Ordering {
+        self.ticks.cmp(&other.ticks)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    /// Panics on overflow.
+    fn add(self, duration: Duration) -> Instant {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to instant")
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// Panics on underflow.
+    fn sub(self, duration: Duration) -> Instant {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl Sub<Instant> for Instant {
+    type Output = Duration;
+
+    /// Saturates at zero, like `duration_since`.
+    fn sub(self, earlier: Instant) -> Duration {
+        self.duration_since(earlier)
+    }
+}
+
+/// Blocking delays using the alarm, for `embedded_hal` drivers.
+///
+/// Delays are rounded up to a whole number of ticks. `DelayNs` cannot report
+/// errors, so if the alarm fails the delay returns early.
+pub struct Delay<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(PhantomData<(S, C)>);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Delay<S, C> {
+    pub fn new() -> Self {
+        Delay(PhantomData)
+    }
+
+    fn delay(duration: Duration) {
+        let mut ticks = match Alarm::<S, C>::get_frequency() {
+            Ok(frequency) => frequency.duration_to_ticks(duration),
+            Err(_) => return,
+        };
+        // Delays longer than the alarm's range are split up.
+        while ticks > 0 {
+            let step = ticks.min(u32::MAX as u64);
+            if Alarm::<S, C>::sleep_for(Ticks(step as u32)).is_err() {
+                return;
+            }
+            ticks -= step;
+        }
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Default for Delay<S, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> embedded_hal::delay::DelayNs for Delay<S, C> {
+    fn delay_ns(&mut self, ns: u32) {
+        Self::delay(Duration::from_nanos(ns as u64))
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        Self::delay(Duration::from_micros(us as u64))
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        Self::delay(Duration::from_millis(ms as u64))
+    }
+}
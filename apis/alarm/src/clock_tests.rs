@@ -0,0 +1,151 @@
+use core::time::Duration;
+use embedded_hal::delay::DelayNs;
+use libtock_unittest::fake;
+
+use crate::{Convert, Hz, Ticks};
+
+type Alarm = crate::Alarm<fake::Syscalls>;
+type Clock = crate::Clock<fake::Syscalls>;
+type Delay = crate::Delay<fake::Syscalls>;
+
+#[test]
+fn duration_to_ticks() {
+    assert_eq!(Hz(1000).duration_to_ticks(Duration::ZERO), 0);
+    assert_eq!(Hz(1000).duration_to_ticks(Duration::from_millis(1)), 1);
+    // Partial ticks round up.
+    assert_eq!(Hz(1000).duration_to_ticks(Duration::from_micros(1500)), 2);
+    assert_eq!(Hz(1000).duration_to_ticks(Duration::from_nanos(1)), 1);
+    assert_eq!(Hz(32768).duration_to_ticks(Duration::from_millis(1)), 33);
+    assert_eq!(Hz(32768).duration_to_ticks(Duration::from_secs(1)), 32768);
+    assert_eq!(Hz(1_000_000).duration_to_ticks(Duration::MAX), u64::MAX);
+
+    assert_eq!(Duration::from_millis(1).to_ticks(Hz(32768)), Ticks(33));
+    assert_eq!(Duration::MAX.to_ticks(Hz(32768)), Ticks(u32::MAX));
+}
+
+#[test]
+fn ticks_to_duration() {
+    assert_eq!(Hz(1000).ticks_to_duration(0), Duration::ZERO);
+    assert_eq!(
+        Hz(1000).ticks_to_duration(1500),
+        Duration::from_millis(1500)
+    );
+    // Partial nanoseconds round down.
+    assert_eq!(
+        Hz(32768).ticks_to_duration(33),
+        Duration::from_nanos(1_007_080)
+    );
+    assert_eq!(
+        Hz(1).ticks_to_duration(u64::MAX),
+        Duration::from_secs(u64::MAX)
+    );
+
+    // Converting to a duration and back gives the original tick count.
+    for ticks in 0..1000 {
+        let duration = Hz(32768).ticks_to_duration(ticks);
+        assert_eq!(Hz(32768).duration_to_ticks(duration), ticks);
+    }
+}
+
+#[test]
+fn ticks_arithmetic() {
+    assert_eq!(Ticks(5) + Ticks(7), Ticks(12));
+    assert_eq!(Ticks(u32::MAX) + Ticks(2), Ticks(1));
+    assert_eq!(Ticks(1) - Ticks(2), Ticks(u32::MAX));
+}
+
+#[test]
+fn get_ticks() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Alarm::get_ticks(), Ok(Ticks(0)));
+    driver.advance(1234);
+    assert_eq!(Alarm::get_ticks(), Ok(Ticks(1234)));
+}
+
+#[test]
+fn clock_wraparound() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&driver);
+
+    driver.set_time(u32::MAX - 9);
+    let clock = Clock::new().unwrap();
+    let before = clock.now().unwrap();
+    assert_eq!(before.ticks(), u32::MAX as u64 - 9);
+
+    driver.advance(20);
+    let after = clock.now().unwrap();
+    assert_eq!(after.ticks(), (1 << 32) + 10);
+    assert!(after > before);
+    assert_eq!(after - before, Duration::from_millis(20));
+
+    // The clock keeps counting across further wraparounds.
+    for _ in 0..4 {
+        driver.advance(u32::MAX / 2 + 1);
+        clock.now().unwrap();
+    }
+    assert_eq!(clock.now().unwrap().ticks(), (3 << 32) + 10);
+}
+
+#[test]
+fn elapsed() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new_controlled(32768);
+    kernel.add_driver(&driver);
+
+    let clock = Clock::new().unwrap();
+    assert_eq!(clock.frequency(), Hz(32768));
+    let start = clock.now().unwrap();
+    driver.advance(49152);
+    assert_eq!(clock.elapsed(start), Ok(Duration::from_millis(1500)));
+}
+
+#[test]
+fn instant_arithmetic() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&driver);
+
+    let clock = Clock::new().unwrap();
+    driver.advance(100);
+    let now = clock.now().unwrap();
+
+    let later = now + Duration::from_micros(2500);
+    assert_eq!(later.ticks(), 103);
+    assert_eq!(later.duration_since(now), Duration::from_millis(3));
+    assert_eq!(now.duration_since(later), Duration::ZERO);
+    assert_eq!((now - Duration::from_millis(40)).ticks(), 60);
+    assert_eq!(now.checked_sub(Duration::from_millis(101)), None);
+    assert_eq!(now.checked_add(Duration::MAX), None);
+}
+
+#[test]
+fn delay() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    let mut delay = Delay::new();
+    delay.delay_ms(5);
+    assert_eq!(driver.now(), 5);
+    // Delays are rounded up to whole ticks.
+    delay.delay_us(1);
+    assert_eq!(driver.now(), 6);
+    delay.delay_ns(0);
+    assert_eq!(driver.now(), 6);
+}
+
+#[test]
+fn long_delay() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1_000_000);
+    kernel.add_driver(&driver);
+
+    // The delay is longer than the 32-bit tick counter can express, so the
+    // fake's counter wraps around while sleeping.
+    Delay::new().delay_ms(u32::MAX);
+    assert_eq!(driver.now(), 0u32.wrapping_sub(1000));
+}
@@ -3,7 +3,8 @@
 use core::cell::Cell;
 use libtock_platform as platform;
 use libtock_platform::share;
-use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_platform::subscribe::{Subscribe, SupportsId};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
 
 /// The alarm driver
 ///
@@ -25,16 +26,34 @@ pub trait Convert {
     fn to_ticks(self, freq: Hz) -> Ticks;
 }
 
-#[derive(Copy, Clone, Debug)]
+/// A tick count, or a value of the alarm's tick counter. Arithmetic wraps
+/// around like the tick counter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Ticks(pub u32);
 
+impl core::ops::Add for Ticks {
+    type Output = Ticks;
+
+    fn add(self, other: Ticks) -> Ticks {
+        Ticks(self.0.wrapping_add(other.0))
+    }
+}
+
+impl core::ops::Sub for Ticks {
+    type Output = Ticks;
+
+    fn sub(self, other: Ticks) -> Ticks {
+        Ticks(self.0.wrapping_sub(other.0))
+    }
+}
+
 impl Convert for Ticks {
     fn to_ticks(self, _freq: Hz) -> Ticks {
         self
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Milliseconds(pub u32);
 
 impl Convert for Milliseconds {
@@ -70,6 +89,14 @@ impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
             .map(Hz)
     }
 
+    /// Returns the current value of the tick counter, which wraps around at
+    /// `u32::MAX`. Use a `Clock` for time that does not wrap around.
+    pub fn get_ticks() -> Result<Ticks, ErrorCode> {
+        S::command(DRIVER_NUM, command::TIME, 0, 0)
+            .to_result()
+            .map(Ticks)
+    }
+
     pub fn sleep_for<T: Convert>(time: T) -> Result<(), ErrorCode> {
         let freq = Self::get_frequency()?;
         let ticks = time.to_ticks(freq);
@@ -90,19 +117,64 @@ impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
             }
         })
     }
+
+    /// Registers a listener for the alarm upcall, which receives the current
+    /// time and the expiration of the alarm that fired. For building timers
+    /// that do not block; most apps should use `sleep_for` or an `AlarmMux`.
+    pub fn register_listener<
+        'share,
+        IDS: SupportsId<DRIVER_NUM, { subscribe::CALLBACK }>,
+        U: Upcall<IDS>,
+    >(
+        listener: &'share U,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::CALLBACK }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, listener)
+    }
+
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::CALLBACK)
+    }
+
+    /// Sets the alarm to fire `dt` ticks after the time `reference`, replacing
+    /// the alarm that is set, if any. If that time has already passed, the
+    /// alarm fires immediately. Returns the expiration time.
+    pub fn set_absolute(reference: Ticks, dt: Ticks) -> Result<Ticks, ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ABSOLUTE, reference.0, dt.0)
+            .to_result()
+            .map(Ticks)
+    }
+
+    /// Stops the alarm. Returns `Already` if no alarm is set.
+    pub fn stop() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
 }
 
+mod clock;
+mod mux;
+mod timeout;
+pub use clock::{Clock, Delay, Instant};
+pub use mux::{AlarmMux, TimerId};
+
+#[cfg(test)]
+mod clock_tests;
+#[cfg(test)]
+mod mux_tests;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod timeout_tests;
 
 // -----------------------------------------------------------------------------
 // Driver number and command IDs
 // -----------------------------------------------------------------------------
 
-const DRIVER_NUM: u32 = 0;
+/// The alarm driver's number, for naming its subscribe handles, as in
+/// `Subscribe<'share, S, DRIVER_NUM, 0>`.
+pub const DRIVER_NUM: u32 = 0;
 
 // Command IDs
-#[allow(unused)]
 mod command {
     pub const DRIVER_CHECK: u32 = 0;
     pub const FREQUENCY: u32 = 1;
@@ -113,7 +185,6 @@ mod command {
     pub const SET_ABSOLUTE: u32 = 6;
 }
 
-#[allow(unused)]
 mod subscribe {
     pub const CALLBACK: u32 = 0;
 }
@@ -0,0 +1,264 @@
+use crate::{subscribe, Alarm, Convert, Ticks, DRIVER_NUM};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform as platform;
+use libtock_platform::subscribe::{OneId, Subscribe};
+use libtock_platform::{share, DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+/// Software timers multiplexed onto the alarm driver.
+///
+/// An `AlarmMux` runs up to `N` one-shot or periodic timers at once, using a
+/// single alarm subscription. The alarm is set for the timer that expires
+/// first, and each timer's callback is called from the alarm upcall, so
+/// callbacks only run while the app yields.
+///
+/// Deadlines are tracked as a reference time plus a delay, so timers expire
+/// correctly when the 32-bit tick counter wraps around, as long as no timer is
+/// more than `u32::MAX` ticks long.
+///
+/// # Example
+/// ```ignore
+/// use libtock::alarm::{AlarmMux, Milliseconds};
+/// use libtock::leds::Leds;
+/// use libtock::runtime::TockSyscalls;
+/// use libtock_platform::{share, Syscalls};
+///
+/// let blink = || {
+///     let _ = Leds::toggle(0);
+/// };
+/// let mux: AlarmMux<2> = AlarmMux::new();
+/// share::scope(|subscribe| {
+///     mux.register(subscribe)?;
+///     mux.start_periodic(Milliseconds(500), &blink)?;
+///     loop {
+///         TockSyscalls::yield_wait();
+///     }
+/// })
+/// ```
+pub struct AlarmMux<'a, S: Syscalls, const N: usize, C: platform::subscribe::Config = DefaultConfig>
+{
+    timers: [Timer<'a>; N],
+    _syscalls: PhantomData<(S, C)>,
+}
+
+/// Identifies a timer started by an `AlarmMux`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimerId {
+    index: usize,
+    generation: u32,
+}
+
+impl<'a, S: Syscalls, const N: usize, C: platform::subscribe::Config> AlarmMux<'a, S, N, C> {
+    pub fn new() -> Self {
+        AlarmMux {
+            timers: [Timer::IDLE; N],
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Subscribes to the alarm upcall. Timers do not fire until the mux is
+    /// registered.
+    pub fn register<'share>(
+        &'share self,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::CALLBACK }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, self)
+    }
+
+    /// Returns the current value of the alarm's tick counter.
+    pub fn now(&self) -> Result<Ticks, ErrorCode> {
+        Alarm::<S, C>::get_ticks()
+    }
+
+    /// Starts a timer that calls `callback` once, after `delay`.
+    pub fn start_oneshot<T: Convert>(
+        &self,
+        delay: T,
+        callback: &'a dyn Fn(),
+    ) -> Result<TimerId, ErrorCode> {
+        let delay = delay.to_ticks(Alarm::<S, C>::get_frequency()?);
+        self.start(self.now()?, delay, 0, callback)
+    }
+
+    /// Starts a timer that calls `callback` every `period`. The first call is
+    /// one period from now. Returns `Invalid` if `period` is zero ticks.
+    pub fn start_periodic<T: Convert>(
+        &self,
+        period: T,
+        callback: &'a dyn Fn(),
+    ) -> Result<TimerId, ErrorCode> {
+        let period = period.to_ticks(Alarm::<S, C>::get_frequency()?);
+        if period.0 == 0 {
+            return Err(ErrorCode::Invalid);
+        }
+        self.start(self.now()?, period, period.0, callback)
+    }
+
+    /// Starts a timer that calls `callback` once, `delay` ticks after the
+    /// absolute time `reference`. As with the alarm driver's absolute alarms,
+    /// the callback is called on the next upcall if that time has already
+    /// passed.
+    ///
+    /// A `reference` less than half the counter's range ahead of now is taken
+    /// to be in the future, and the timer expires `delay` ticks after it.
+    /// Returns `Invalid` if that is more than `u32::MAX` ticks from now.
+    pub fn start_at(
+        &self,
+        reference: Ticks,
+        delay: Ticks,
+        callback: &'a dyn Fn(),
+    ) -> Result<TimerId, ErrorCode> {
+        let now = self.now()?;
+        let ahead = reference.0.wrapping_sub(now.0);
+        if ahead == 0 || ahead > u32::MAX / 2 {
+            return self.start(reference, delay, 0, callback);
+        }
+        // Timers count down from a reference in the past, so count from now.
+        let delay = ahead.checked_add(delay.0).ok_or(ErrorCode::Invalid)?;
+        self.start(now, Ticks(delay), 0, callback)
+    }
+
+    /// Cancels a timer. Returns `Already` if the timer has already expired or
+    /// been cancelled, and `Invalid` if `id` is from a larger mux. If no other
+    /// timer is running, the alarm is stopped.
+    pub fn cancel(&self, id: TimerId) -> Result<(), ErrorCode> {
+        let timer = self.timers.get(id.index).ok_or(ErrorCode::Invalid)?;
+        if timer.callback.get().is_none() || timer.generation.get() != id.generation {
+            return Err(ErrorCode::Already);
+        }
+        timer.callback.set(None);
+        self.rearm()
+    }
+
+    /// Returns true if the timer has neither expired nor been cancelled.
+    /// Periodic timers stay active until they are cancelled.
+    pub fn is_active(&self, id: TimerId) -> bool {
+        match self.timers.get(id.index) {
+            Some(timer) => {
+                timer.callback.get().is_some() && timer.generation.get() == id.generation
+            }
+            None => false,
+        }
+    }
+
+    fn start(
+        &self,
+        reference: Ticks,
+        delay: Ticks,
+        period: u32,
+        callback: &'a dyn Fn(),
+    ) -> Result<TimerId, ErrorCode> {
+        let index = self
+            .timers
+            .iter()
+            .position(|timer| timer.callback.get().is_none())
+            .ok_or(ErrorCode::NoMem)?;
+        let timer = &self.timers[index];
+        let generation = timer.generation.get().wrapping_add(1);
+        timer.generation.set(generation);
+        timer.reference.set(reference.0);
+        timer.delay.set(delay.0);
+        timer.period.set(period);
+        timer.callback.set(Some(callback));
+        self.rearm()?;
+        Ok(TimerId { index, generation })
+    }
+
+    // Sets the alarm for the timer that expires first, or stops it if no
+    // timer is running.
+    fn rearm(&self) -> Result<(), ErrorCode> {
+        let now = self.now()?.0;
+        let next = self
+            .timers
+            .iter()
+            .filter(|timer| timer.callback.get().is_some())
+            .min_by_key(|timer| timer.remaining(now));
+        match next {
+            Some(timer) => {
+                Alarm::<S, C>::set_absolute(Ticks(timer.reference.get()), Ticks(timer.delay.get()))
+                    .map(|_expiration| ())
+            }
+            None => match Alarm::<S, C>::stop() {
+                Ok(()) | Err(ErrorCode::Already) => Ok(()),
+                Err(error) => Err(error),
+            },
+        }
+    }
+}
+
+impl<'a, S: Syscalls, const N: usize, C: platform::subscribe::Config> Default
+    for AlarmMux<'a, S, N, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S: Syscalls, const N: usize, C: platform::subscribe::Config>
+    Upcall<OneId<DRIVER_NUM, { subscribe::CALLBACK }>> for AlarmMux<'a, S, N, C>
+{
+    fn upcall(&self, _now: u32, _expiration: u32, _: u32) {
+        // The upcall may be stale, as timers can be cancelled or started
+        // after the alarm fires, so expired timers are found by time.
+        let now = match self.now() {
+            Ok(now) => now.0,
+            Err(_) => return,
+        };
+        for timer in &self.timers {
+            let callback = match timer.callback.get() {
+                Some(callback) if timer.remaining(now) == 0 => callback,
+                _ => continue,
+            };
+            match timer.period.get() {
+                0 => timer.callback.set(None),
+                period => {
+                    // Periods that were missed entirely are skipped, keeping
+                    // the timer in phase.
+                    let expiration = timer.reference.get().wrapping_add(timer.delay.get());
+                    let missed = now.wrapping_sub(expiration) / period;
+                    timer
+                        .reference
+                        .set(expiration.wrapping_add(missed * period));
+                    timer.delay.set(period);
+                }
+            }
+            callback();
+        }
+        let _ = self.rearm();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+struct Timer<'a> {
+    // None if the timer is not running.
+    callback: Cell<Option<&'a dyn Fn()>>,
+    // The timer expires `delay` ticks after `reference`.
+    reference: Cell<u32>,
+    delay: Cell<u32>,
+    // Zero for one-shot timers.
+    period: Cell<u32>,
+    // Incremented each time the timer is started, so that stale `TimerId`s
+    // do not cancel a newer timer.
+    generation: Cell<u32>,
+}
+
+impl<'a> Timer<'a> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const IDLE: Timer<'a> = Timer {
+        callback: Cell::new(None),
+        reference: Cell::new(0),
+        delay: Cell::new(0),
+        period: Cell::new(0),
+        generation: Cell::new(0),
+    };
+
+    // Returns the number of ticks until the timer expires, or zero if it has
+    // expired.
+    fn remaining(&self, now: u32) -> u32 {
+        let elapsed = now.wrapping_sub(self.reference.get());
+        self.delay.get().saturating_sub(elapsed)
+    }
+}
@@ -0,0 +1,261 @@
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+use crate::{Milliseconds, Ticks};
+
+type AlarmMux<'a, const N: usize> = crate::AlarmMux<'a, fake::Syscalls, N>;
+
+// Advances the fake alarm's time and delivers any resulting upcalls.
+fn advance(alarm: &fake::Alarm, ticks: u32) {
+    alarm.advance(ticks);
+    while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+}
+
+#[test]
+fn oneshot() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let fired = Cell::new(0);
+    let callback = || fired.set(fired.get() + 1);
+    let mux = AlarmMux::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        let id = mux.start_oneshot(Milliseconds(100), &callback).unwrap();
+        assert!(mux.is_active(id));
+        assert_eq!(alarm.expiration(), Some(100));
+
+        advance(&alarm, 99);
+        assert_eq!(fired.get(), 0);
+        advance(&alarm, 1);
+        assert_eq!(fired.get(), 1);
+        assert!(!mux.is_active(id));
+        assert_eq!(alarm.expiration(), None);
+
+        advance(&alarm, 1000);
+        assert_eq!(fired.get(), 1);
+    });
+}
+
+#[test]
+fn multiple_timers() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    // Records the order in which the timers fire, one decimal digit each.
+    let order = Cell::new(0);
+    let first = || order.set(order.get() * 10 + 1);
+    let second = || order.set(order.get() * 10 + 2);
+    let third = || order.set(order.get() * 10 + 3);
+    let mux = AlarmMux::<3>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        mux.start_oneshot(Milliseconds(300), &third).unwrap();
+        mux.start_oneshot(Milliseconds(100), &first).unwrap();
+        mux.start_oneshot(Milliseconds(200), &second).unwrap();
+        // The alarm is set for the earliest timer.
+        assert_eq!(alarm.expiration(), Some(100));
+
+        advance(&alarm, 100);
+        assert_eq!(order.get(), 1);
+        assert_eq!(alarm.expiration(), Some(200));
+        advance(&alarm, 100);
+        assert_eq!(order.get(), 12);
+        advance(&alarm, 100);
+        assert_eq!(order.get(), 123);
+        assert_eq!(alarm.expiration(), None);
+    });
+}
+
+#[test]
+fn periodic() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let fired = Cell::new(0);
+    let callback = || fired.set(fired.get() + 1);
+    let mux = AlarmMux::<1>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        let id = mux.start_periodic(Milliseconds(100), &callback).unwrap();
+        for expected in 1..=3 {
+            advance(&alarm, 100);
+            assert_eq!(fired.get(), expected);
+        }
+        assert!(mux.is_active(id));
+        assert_eq!(alarm.expiration(), Some(400));
+
+        // Missed periods are skipped, keeping the timer in phase.
+        advance(&alarm, 350);
+        assert_eq!(fired.get(), 4);
+        assert_eq!(alarm.expiration(), Some(700));
+
+        assert_eq!(mux.cancel(id), Ok(()));
+        assert_eq!(alarm.expiration(), None);
+        advance(&alarm, 1000);
+        assert_eq!(fired.get(), 4);
+    });
+}
+
+#[test]
+fn cancel() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let cancelled_fired = Cell::new(false);
+    let kept_fired = Cell::new(false);
+    let cancelled_callback = || cancelled_fired.set(true);
+    let kept_callback = || kept_fired.set(true);
+    let mux = AlarmMux::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        let cancelled = mux
+            .start_oneshot(Milliseconds(100), &cancelled_callback)
+            .unwrap();
+        mux.start_oneshot(Milliseconds(200), &kept_callback)
+            .unwrap();
+        assert_eq!(mux.cancel(cancelled), Ok(()));
+        assert_eq!(mux.cancel(cancelled), Err(ErrorCode::Already));
+        assert_eq!(alarm.expiration(), Some(200));
+
+        advance(&alarm, 200);
+        assert!(!cancelled_fired.get());
+        assert!(kept_fired.get());
+
+        // A stale id does not cancel a newer timer in the same slot.
+        let newer = mux
+            .start_oneshot(Milliseconds(100), &cancelled_callback)
+            .unwrap();
+        assert_eq!(mux.cancel(cancelled), Err(ErrorCode::Already));
+        assert!(mux.is_active(newer));
+    });
+}
+
+// An id from a larger mux is rejected rather than indexing out of bounds.
+#[test]
+fn foreign_id() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let callback = || {};
+    let large = AlarmMux::<3>::new();
+    let small = AlarmMux::<1>::new();
+    let id = (0..3)
+        .map(|_| large.start_oneshot(Milliseconds(100), &callback).unwrap())
+        .last()
+        .unwrap();
+    assert!(large.is_active(id));
+    assert!(!small.is_active(id));
+    assert_eq!(small.cancel(id), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn wraparound() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let order = Cell::new(0);
+    let first = || order.set(order.get() * 10 + 1);
+    let second = || order.set(order.get() * 10 + 2);
+    let mux = AlarmMux::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        alarm.set_time(u32::MAX - 49);
+        // Expires after the counter wraps around, but before `first`.
+        mux.start_oneshot(Ticks(60), &second).unwrap();
+        mux.start_oneshot(Ticks(40), &first).unwrap();
+        assert_eq!(alarm.expiration(), Some(u32::MAX - 9));
+
+        advance(&alarm, 40);
+        assert_eq!(order.get(), 1);
+        assert_eq!(alarm.expiration(), Some(10));
+        advance(&alarm, 19);
+        assert_eq!(order.get(), 1);
+        advance(&alarm, 1);
+        assert_eq!(order.get(), 12);
+    });
+}
+
+#[test]
+fn start_at() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let fired = Cell::new(0);
+    let callback = || fired.set(fired.get() + 1);
+    let mux = AlarmMux::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        advance(&alarm, 500);
+        assert_eq!(mux.now().map(|now| now.0), Ok(500));
+
+        mux.start_at(Ticks(400), Ticks(300), &callback).unwrap();
+        assert_eq!(alarm.expiration(), Some(700));
+        advance(&alarm, 200);
+        assert_eq!(fired.get(), 1);
+
+        // A deadline that has already passed fires on the next upcall.
+        mux.start_at(Ticks(0), Ticks(100), &callback).unwrap();
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+        assert_eq!(fired.get(), 2);
+    });
+}
+
+#[test]
+fn start_at_future_reference() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let fired = Cell::new(false);
+    let callback = || fired.set(true);
+    let other = || {};
+    let mux = AlarmMux::<3>::new();
+    share::scope(|subscribe| {
+        assert_eq!(mux.register(subscribe), Ok(()));
+        advance(&alarm, 500);
+
+        mux.start_at(Ticks(800), Ticks(100), &callback).unwrap();
+        assert_eq!(alarm.expiration(), Some(900));
+        // Another timer's upcall does not fire the future timer early.
+        mux.start_oneshot(Ticks(100), &other).unwrap();
+        advance(&alarm, 100);
+        assert!(!fired.get());
+        advance(&alarm, 299);
+        assert!(!fired.get());
+        advance(&alarm, 1);
+        assert!(fired.get());
+
+        assert_eq!(
+            mux.start_at(Ticks(1400), Ticks(u32::MAX), &callback),
+            Err(ErrorCode::Invalid)
+        );
+    });
+}
+
+#[test]
+fn errors() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let callback = || {};
+    let mux = AlarmMux::<1>::new();
+    assert_eq!(
+        mux.start_periodic(Ticks(0), &callback),
+        Err(ErrorCode::Invalid)
+    );
+    mux.start_oneshot(Ticks(10), &callback).unwrap();
+    assert_eq!(
+        mux.start_oneshot(Ticks(10), &callback),
+        Err(ErrorCode::NoMem)
+    );
+}
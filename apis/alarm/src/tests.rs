@@ -1,3 +1,5 @@
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
 use libtock_unittest::fake;
 
 use crate::{Hz, Milliseconds, Ticks};
@@ -22,3 +24,34 @@ fn sleep() {
     assert_eq!(Alarm::sleep_for(Ticks(1000)), Ok(()));
     assert_eq!(Alarm::sleep_for(Milliseconds(1000)), Ok(()));
 }
+
+#[test]
+fn set_absolute() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&driver);
+
+    let fired: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(Alarm::register_listener(&fired, subscribe), Ok(()));
+        driver.set_time(50);
+        assert_eq!(
+            Alarm::set_absolute(Ticks(40), Ticks(100)).map(|t| t.0),
+            Ok(140)
+        );
+        assert_eq!(driver.expiration(), Some(140));
+
+        driver.advance(90);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fired.get(), Some((140, 140)));
+
+        assert_eq!(Alarm::stop(), Err(ErrorCode::Already));
+        assert_eq!(
+            Alarm::set_absolute(Ticks(140), Ticks(10)).map(|t| t.0),
+            Ok(150)
+        );
+        assert_eq!(Alarm::stop(), Ok(()));
+        assert_eq!(driver.expiration(), None);
+        Alarm::unregister_listener();
+    });
+}
@@ -0,0 +1,98 @@
+use crate::{command, subscribe, Alarm, Convert, DRIVER_NUM};
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::{share, subscribe::Subscribe, ErrorCode, Syscalls};
+
+impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
+    /// Runs an upcall-driven operation, giving up if it has not completed
+    /// after `timeout`.
+    ///
+    /// `start` receives a subscribe handle for the driver's upcall, which it
+    /// should register before starting the operation. The alarm and driver
+    /// subscriptions share one `share::scope`, so both are removed when this
+    /// function returns. `poll` is called after each upcall and returns the
+    /// result once the operation has completed.
+    ///
+    /// If the timeout expires first, `abort` is called so the operation can
+    /// be cancelled if the driver supports it, and `Cancel` is returned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::alarm::{Alarm, Milliseconds};
+    /// use libtock::temperature::{Temperature, TemperatureListener};
+    ///
+    /// let temperature = Cell::new(None);
+    /// let listener = TemperatureListener(|value| temperature.set(Some(value)));
+    /// let value = Alarm::run_with_timeout(
+    ///     Milliseconds(100),
+    ///     |subscribe| {
+    ///         Temperature::register_listener(&listener, subscribe)?;
+    ///         Temperature::read_temperature()
+    ///     },
+    ///     || temperature.get(),
+    ///     || {},
+    /// );
+    /// ```
+    pub fn run_with_timeout<
+        'share,
+        const DRIVER: u32,
+        const SUBSCRIBE: u32,
+        T,
+        R,
+        Start,
+        Poll,
+        Abort,
+    >(
+        timeout: T,
+        start: Start,
+        mut poll: Poll,
+        abort: Abort,
+    ) -> Result<R, ErrorCode>
+    where
+        T: Convert,
+        Start: for<'handle> FnOnce(
+            share::Handle<'handle, Subscribe<'share, S, DRIVER, SUBSCRIBE>>,
+        ) -> Result<(), ErrorCode>,
+        Poll: FnMut() -> Option<R>,
+        Abort: FnOnce(),
+    {
+        let ticks = timeout.to_ticks(Self::get_frequency()?);
+        let expired: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                Subscribe<_, DRIVER_NUM, { subscribe::CALLBACK }>,
+                Subscribe<'share, S, DRIVER, SUBSCRIBE>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (alarm_subscribe, driver_subscribe) = handle.split();
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(
+                alarm_subscribe,
+                &expired,
+            )?;
+            start(driver_subscribe)?;
+            if let Err(error) = S::command(DRIVER_NUM, command::SET_RELATIVE, ticks.0, 0)
+                .to_result::<u32, ErrorCode>()
+            {
+                abort();
+                return Err(error);
+            }
+
+            loop {
+                // Completion takes priority over a timeout that expired at
+                // the same time.
+                if let Some(result) = poll() {
+                    let _ =
+                        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result::<(), ErrorCode>();
+                    return Ok(result);
+                }
+                if expired.get().is_some() {
+                    abort();
+                    return Err(ErrorCode::Cancel);
+                }
+                S::yield_wait();
+            }
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use core::cell::Cell;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_unittest::fake;
+
+use crate::Milliseconds;
+
+type Alarm = crate::Alarm<fake::Syscalls>;
+
+// The operations are run against the fake temperature driver, which responds
+// to a read only if a value has been set with `set_value_sync`.
+const TEMPERATURE: u32 = 0x60000;
+const READ_TEMPERATURE: u32 = 1;
+
+fn read_temperature(value: &Cell<Option<(u32,)>>, aborted: &Cell<bool>) -> Result<u32, ErrorCode> {
+    Alarm::run_with_timeout(
+        Milliseconds(100),
+        |subscribe| {
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, TEMPERATURE, 0>(subscribe, value)?;
+            fake::Syscalls::command(TEMPERATURE, READ_TEMPERATURE, 0, 0).to_result()
+        },
+        || value.get().map(|(value,)| value),
+        || aborted.set(true),
+    )
+}
+
+#[test]
+fn completes() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    let temperature = fake::Temperature::new();
+    kernel.add_driver(&alarm);
+    kernel.add_driver(&temperature);
+
+    temperature.set_value_sync(2500);
+    let value = Cell::new(None);
+    let aborted = Cell::new(false);
+    assert_eq!(read_temperature(&value, &aborted), Ok(2500));
+    assert!(!aborted.get());
+}
+
+#[test]
+fn times_out() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    let temperature = fake::Temperature::new();
+    kernel.add_driver(&alarm);
+    kernel.add_driver(&temperature);
+
+    let value = Cell::new(None);
+    let aborted = Cell::new(false);
+    assert_eq!(read_temperature(&value, &aborted), Err(ErrorCode::Cancel));
+    assert!(aborted.get());
+    // The fake alarm fires immediately, so the full timeout has elapsed.
+    assert_eq!(alarm.now(), 100);
+}
+
+#[test]
+fn start_fails() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new(1000);
+    let temperature = fake::Temperature::new();
+    kernel.add_driver(&alarm);
+    kernel.add_driver(&temperature);
+
+    // A read is already in progress.
+    fake::Syscalls::command(TEMPERATURE, READ_TEMPERATURE, 0, 0)
+        .to_result::<(), ErrorCode>()
+        .unwrap();
+    let value = Cell::new(None);
+    let aborted = Cell::new(false);
+    assert_eq!(read_temperature(&value, &aborted), Err(ErrorCode::Busy));
+    // The alarm was never set.
+    assert_eq!(alarm.now(), 0);
+}
+
+#[test]
+fn no_alarm() {
+    let kernel = fake::Kernel::new();
+    let temperature = fake::Temperature::new();
+    kernel.add_driver(&temperature);
+
+    let value = Cell::new(None);
+    let aborted = Cell::new(false);
+    assert_eq!(read_temperature(&value, &aborted), Err(ErrorCode::NoDevice));
+    assert!(!temperature.is_busy());
+}
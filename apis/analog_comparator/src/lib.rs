@@ -0,0 +1,111 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::{
+    share, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+/// The analog comparator driver. Each channel compares the voltages of its
+/// positive and negative inputs.
+///
+/// # Example
+/// ```ignore
+/// use libtock::analog_comparator::AnalogComparator;
+///
+/// if AnalogComparator::compare(0)? {
+///     // The positive input is above the negative input.
+/// }
+/// // Wait until the positive input rises above the negative input.
+/// AnalogComparator::wait_for_interrupt(0)?;
+/// ```
+pub struct AnalogComparator<S: Syscalls>(S);
+
+impl<S: Syscalls> AnalogComparator<S> {
+    /// Returns Ok() if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the number of comparator channels.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, COUNT, 0, 0).to_result()
+    }
+
+    /// Returns true if the positive input of `channel` is above its negative
+    /// input.
+    pub fn compare(channel: u32) -> Result<bool, ErrorCode> {
+        let result: u32 = S::command(DRIVER_NUM, COMPARE, channel, 0).to_result()?;
+        Ok(result != 0)
+    }
+
+    /// Enables interrupts on `channel`. The listener is called each time the
+    /// positive input of the channel rises above the negative input.
+    pub fn start_comparing(channel: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, START_COMPARING, channel, 0).to_result()
+    }
+
+    /// Disables interrupts on `channel`.
+    pub fn stop_comparing(channel: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, STOP_COMPARING, channel, 0).to_result()
+    }
+
+    /// Register an events listener
+    pub fn register_listener<'share, F: Fn(u32)>(
+        listener: &'share ComparatorListener<F>,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
+    }
+
+    /// Unregister the events listener
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, 0)
+    }
+
+    /// Waits until the positive input of `channel` rises above its negative
+    /// input.
+    pub fn wait_for_interrupt(channel: u32) -> Result<(), ErrorCode> {
+        let interrupted = Cell::new(false);
+        let listener = ComparatorListener(|interrupt_channel| {
+            if interrupt_channel == channel {
+                interrupted.set(true);
+            }
+        });
+        share::scope(|subscribe| {
+            Self::register_listener(&listener, subscribe)?;
+            Self::start_comparing(channel)?;
+            while !interrupted.get() {
+                S::yield_wait();
+            }
+            Self::stop_comparing(channel)
+        })
+    }
+}
+
+/// A listener for comparator interrupts, which receives the channel that
+/// triggered the interrupt.
+pub struct ComparatorListener<F: Fn(u32)>(pub F);
+
+impl<F: Fn(u32)> Upcall<OneId<DRIVER_NUM, 0>> for ComparatorListener<F> {
+    fn upcall(&self, channel: u32, _arg1: u32, _arg2: u32) {
+        self.0(channel)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x7;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const COMPARE: u32 = 1;
+const START_COMPARING: u32 = 2;
+const STOP_COMPARING: u32 = 3;
+const COUNT: u32 = 4;
@@ -0,0 +1,85 @@
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type AnalogComparator = super::AnalogComparator<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(AnalogComparator::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(AnalogComparator::exists(), Ok(()));
+    assert_eq!(AnalogComparator::count(), Ok(2));
+}
+
+#[test]
+fn compare() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(AnalogComparator::compare(0), Ok(false));
+    driver.set_output(0, true);
+    assert_eq!(AnalogComparator::compare(0), Ok(true));
+    assert_eq!(AnalogComparator::compare(1), Ok(false));
+    assert_eq!(AnalogComparator::compare(2), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn register_unregister_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::<2>::new();
+    kernel.add_driver(&driver);
+
+    let channel: Cell<Option<u32>> = Cell::new(None);
+    let listener = crate::ComparatorListener(|interrupt_channel| {
+        channel.set(Some(interrupt_channel));
+    });
+    share::scope(|subscribe| {
+        assert_eq!(AnalogComparator::start_comparing(1), Ok(()));
+        assert!(driver.is_comparing(1));
+        driver.set_output(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        driver.set_output(1, false);
+
+        assert_eq!(
+            AnalogComparator::register_listener(&listener, subscribe),
+            Ok(())
+        );
+        driver.set_output(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(channel.get(), Some(1));
+        driver.set_output(1, false);
+
+        assert_eq!(AnalogComparator::stop_comparing(1), Ok(()));
+        driver.set_output(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        AnalogComparator::unregister_listener();
+        assert_eq!(AnalogComparator::start_comparing(0), Ok(()));
+        driver.set_output(0, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
+
+#[test]
+fn wait_for_interrupt() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::<2>::new();
+    kernel.add_driver(&driver);
+
+    // Invalid channels are rejected before waiting.
+    assert_eq!(
+        AnalogComparator::wait_for_interrupt(2),
+        Err(ErrorCode::Invalid)
+    );
+    assert!(!driver.is_comparing(0));
+}
@@ -0,0 +1,214 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod payload;
+mod scan_report;
+
+pub use payload::{flags, AdvertisingPayload, MAX_ADVERTISING_DATA_LEN};
+pub use scan_report::{AdStructures, ScanReport};
+
+/// The BLE advertising driver.
+///
+/// It allows the process to broadcast BLE advertisements and to passively scan
+/// for advertisements sent by other devices.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ble::{flags, AdvertisingPayload, AdvertisingType, Ble};
+///
+/// let mut payload = AdvertisingPayload::new();
+/// payload
+///     .flags(flags::LE_GENERAL_DISCOVERABLE | flags::BR_EDR_NOT_SUPPORTED)?
+///     .local_name("TockOS")?;
+///
+/// share::scope(|allow_ro| {
+///     Ble::start_advertising(allow_ro, &payload, AdvertisingType::NonConnectable, 300)?;
+///     // Advertise until the scope ends.
+///     Ble::stop()
+/// })?;
+/// ```
+pub struct Ble<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Ble<S, C> {
+    /// Run a check against the BLE advertising capsule to ensure it is
+    /// present.
+    ///
+    /// The capsule has no dedicated check command (command 0 starts
+    /// advertising), so this sends a command number it does not implement:
+    /// the kernel returns `NoDevice` if the driver is missing, and the capsule
+    /// returns `NoSupport` without doing anything otherwise. Returns `Ok(())`
+    /// if the driver was present. This does not necessarily mean that the
+    /// driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        match S::command(DRIVER_NUM, command::PROBE, 0, 0).to_result::<(), ErrorCode>() {
+            Err(ErrorCode::NoDevice) => Err(ErrorCode::NoDevice),
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts advertising `payload` every `interval_ms` milliseconds.
+    ///
+    /// The payload stays shared with the kernel until the `allow_ro` handle's
+    /// scope ends, so advertising should be stopped before leaving the scope.
+    /// Returns `ErrorCode::Invalid` if the interval is outside of the
+    /// 20 ms to 10.24 s range allowed by the Bluetooth specification.
+    pub fn start_advertising<'share>(
+        allow_ro: Handle<AllowRo<'share, S, DRIVER_NUM, { allow_ro::ADVERTISING_DATA }>>,
+        payload: &'share AdvertisingPayload,
+        advertising_type: AdvertisingType,
+        interval_ms: u32,
+    ) -> Result<(), ErrorCode> {
+        if !(MIN_INTERVAL_MS..=MAX_INTERVAL_MS).contains(&interval_ms) {
+            return Err(ErrorCode::Invalid);
+        }
+        S::allow_ro::<C, DRIVER_NUM, { allow_ro::ADVERTISING_DATA }>(allow_ro, payload.as_bytes())?;
+        S::command(
+            DRIVER_NUM,
+            command::START_ADVERTISING,
+            advertising_type as u32,
+            interval_ms,
+        )
+        .to_result()
+    }
+
+    /// Stops advertising or passive scanning.
+    pub fn stop() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
+
+    /// Sets the transmit power in dBm. The Bluetooth specification allows
+    /// values from -20 dBm to 10 dBm; others return `ErrorCode::Invalid`.
+    pub fn set_tx_power(dbm: i8) -> Result<(), ErrorCode> {
+        if !(MIN_TX_POWER_DBM..=MAX_TX_POWER_DBM).contains(&dbm) {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(DRIVER_NUM, command::SET_TX_POWER, dbm as u8 as u32, 0).to_result()
+    }
+
+    /// Passively scans until one advertisement is received, then stops
+    /// scanning and returns the parsed report. The report borrows the
+    /// advertisement's bytes from `buffer`.
+    ///
+    /// Returns `ErrorCode::Fail` if the advertisement could not be parsed.
+    pub fn scan_sync(buffer: &mut [u8]) -> Result<ScanReport<'_>, ErrorCode> {
+        let len = Self::receive_advertisement(buffer)?;
+        ScanReport::parse(&buffer[..len]).ok_or(ErrorCode::Fail)
+    }
+
+    /// Passively scans, calling `listener` with each parsed report, until the
+    /// listener returns `false` or an error occurs. Advertisements that cannot
+    /// be parsed are skipped.
+    pub fn passive_scan<F: FnMut(&ScanReport) -> bool>(
+        buffer: &mut [u8],
+        mut listener: F,
+    ) -> Result<(), ErrorCode> {
+        loop {
+            let len = Self::receive_advertisement(buffer)?;
+            if let Some(report) = ScanReport::parse(&buffer[..len]) {
+                if !listener(&report) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// The advertising PDU type, which determines whether the device accepts
+/// connections and scan requests.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AdvertisingType {
+    /// Connectable and scannable undirected advertising (`ADV_IND`).
+    Connectable = 0x00,
+    /// Non-connectable, non-scannable advertising (`ADV_NONCONN_IND`).
+    NonConnectable = 0x02,
+    /// Scannable, non-connectable advertising (`ADV_SCAN_IND`).
+    Scannable = 0x06,
+}
+
+/// System call configuration trait for `Ble`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> Ble<S, C> {
+    // Scans until one advertisement has been copied into `buffer`, and returns
+    // its length.
+    fn receive_advertisement(buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let received: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::SCAN_BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::SCAN }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::SCAN_BUFFER }>(allow_rw, buffer)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::SCAN }>(subscribe, &received)?;
+            S::command(DRIVER_NUM, command::PASSIVE_SCAN, 0, 0).to_result()?;
+            while received.get().is_none() {
+                S::yield_wait();
+            }
+            Self::stop()
+        })?;
+
+        match received.get() {
+            Some((0, len)) => Ok(core::cmp::min(len as usize, buffer.len())),
+            Some((status, _)) => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+            None => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30000;
+
+const MIN_INTERVAL_MS: u32 = 20;
+const MAX_INTERVAL_MS: u32 = 10240;
+const MIN_TX_POWER_DBM: i8 = -20;
+const MAX_TX_POWER_DBM: i8 = 10;
+
+// Command IDs
+mod command {
+    pub const START_ADVERTISING: u32 = 0;
+    pub const STOP: u32 = 1;
+    pub const SET_TX_POWER: u32 = 2;
+    pub const PASSIVE_SCAN: u32 = 5;
+    // Not implemented by the capsule; used by `exists`.
+    pub const PROBE: u32 = 3;
+}
+
+mod subscribe {
+    pub const SCAN: u32 = 0;
+}
+
+mod allow_ro {
+    pub const ADVERTISING_DATA: u32 = 0;
+}
+
+mod allow_rw {
+    pub const SCAN_BUFFER: u32 = 0;
+}
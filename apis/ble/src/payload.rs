@@ -0,0 +1,147 @@
+use libtock_platform::ErrorCode;
+
+/// The maximum length of legacy advertising data, in bytes.
+pub const MAX_ADVERTISING_DATA_LEN: usize = 31;
+
+/// Values for the flags AD structure, which may be combined with `|`.
+pub mod flags {
+    pub const LE_LIMITED_DISCOVERABLE: u8 = 0x01;
+    pub const LE_GENERAL_DISCOVERABLE: u8 = 0x02;
+    pub const BR_EDR_NOT_SUPPORTED: u8 = 0x04;
+}
+
+/// Builds the advertising data broadcast by `Ble::start_advertising`.
+///
+/// Advertising data is a sequence of AD structures, each consisting of a
+/// length byte, a type byte, and the data. Each builder method appends one AD
+/// structure and returns `ErrorCode::Size` if it does not fit in the remaining
+/// space, leaving the payload unchanged.
+///
+/// ```ignore
+/// let mut payload = AdvertisingPayload::new();
+/// payload
+///     .flags(flags::LE_GENERAL_DISCOVERABLE)?
+///     .service_uuids16(&[0x181A])?
+///     .manufacturer_data(0xFFFF, &[1, 2, 3])?;
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdvertisingPayload {
+    data: [u8; MAX_ADVERTISING_DATA_LEN],
+    len: usize,
+}
+
+impl AdvertisingPayload {
+    pub const fn new() -> Self {
+        AdvertisingPayload {
+            data: [0; MAX_ADVERTISING_DATA_LEN],
+            len: 0,
+        }
+    }
+
+    /// Appends a flags AD structure. See the `flags` module for values.
+    pub fn flags(&mut self, flags: u8) -> Result<&mut Self, ErrorCode> {
+        self.push(ad_type::FLAGS, &[&[flags]])
+    }
+
+    /// Appends the complete local name of the device.
+    pub fn local_name(&mut self, name: &str) -> Result<&mut Self, ErrorCode> {
+        self.push(ad_type::COMPLETE_LOCAL_NAME, &[name.as_bytes()])
+    }
+
+    /// Appends manufacturer specific data, prefixed by the manufacturer's
+    /// Bluetooth SIG company identifier.
+    pub fn manufacturer_data(
+        &mut self,
+        company_id: u16,
+        data: &[u8],
+    ) -> Result<&mut Self, ErrorCode> {
+        self.push(
+            ad_type::MANUFACTURER_DATA,
+            &[&company_id.to_le_bytes(), data],
+        )
+    }
+
+    /// Appends a complete list of 16-bit service UUIDs.
+    pub fn service_uuids16(&mut self, uuids: &[u16]) -> Result<&mut Self, ErrorCode> {
+        self.check_space(uuids.len() * 2)?;
+        let start = self.len;
+        for uuid in uuids {
+            self.push_data(&uuid.to_le_bytes());
+        }
+        self.finish(start, ad_type::COMPLETE_UUIDS16);
+        Ok(self)
+    }
+
+    /// Appends a complete list of 128-bit service UUIDs.
+    pub fn service_uuids128(&mut self, uuids: &[u128]) -> Result<&mut Self, ErrorCode> {
+        self.check_space(uuids.len() * 16)?;
+        let start = self.len;
+        for uuid in uuids {
+            self.push_data(&uuid.to_le_bytes());
+        }
+        self.finish(start, ad_type::COMPLETE_UUIDS128);
+        Ok(self)
+    }
+
+    /// Removes all AD structures.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the encoded advertising data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    // Appends an AD structure whose data is the concatenation of `parts`.
+    fn push(&mut self, ad_type: u8, parts: &[&[u8]]) -> Result<&mut Self, ErrorCode> {
+        self.check_space(parts.iter().map(|part| part.len()).sum())?;
+        let start = self.len;
+        for part in parts {
+            self.push_data(part);
+        }
+        self.finish(start, ad_type);
+        Ok(self)
+    }
+
+    // Checks that an AD structure with `data_len` bytes of data fits, and
+    // reserves space for its length and type bytes.
+    fn check_space(&mut self, data_len: usize) -> Result<(), ErrorCode> {
+        if self.len + AD_HEADER_LEN + data_len > MAX_ADVERTISING_DATA_LEN {
+            return Err(ErrorCode::Size);
+        }
+        self.len += AD_HEADER_LEN;
+        Ok(())
+    }
+
+    fn push_data(&mut self, data: &[u8]) {
+        self.data[self.len..self.len + data.len()].copy_from_slice(data);
+        self.len += data.len();
+    }
+
+    // Fills in the length and type bytes of the AD structure whose data starts
+    // at `start`.
+    fn finish(&mut self, start: usize, ad_type: u8) {
+        self.data[start - AD_HEADER_LEN] = (self.len - start + 1) as u8;
+        self.data[start - 1] = ad_type;
+    }
+}
+
+impl Default for AdvertisingPayload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The length and type bytes that precede the data of each AD structure.
+const AD_HEADER_LEN: usize = 2;
+
+/// AD type values from the Bluetooth SIG's assigned numbers.
+pub(crate) mod ad_type {
+    pub const FLAGS: u8 = 0x01;
+    pub const COMPLETE_UUIDS16: u8 = 0x03;
+    pub const COMPLETE_UUIDS128: u8 = 0x07;
+    pub const SHORTENED_LOCAL_NAME: u8 = 0x08;
+    pub const COMPLETE_LOCAL_NAME: u8 = 0x09;
+    pub const MANUFACTURER_DATA: u8 = 0xFF;
+}
@@ -0,0 +1,80 @@
+use crate::payload::ad_type;
+
+/// An advertisement received while scanning.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ScanReport<'a> {
+    /// The advertising PDU type (e.g. 0x00 for `ADV_IND`).
+    pub pdu_type: u8,
+    /// The advertiser's device address, in transmission (little-endian) order.
+    pub address: [u8; 6],
+    /// The advertising data, a sequence of AD structures.
+    pub data: &'a [u8],
+}
+
+impl<'a> ScanReport<'a> {
+    /// Parses an advertising channel PDU, consisting of a two-byte header, the
+    /// advertiser's address, and the advertising data. Returns `None` if the
+    /// PDU is truncated.
+    pub fn parse(pdu: &'a [u8]) -> Option<ScanReport<'a>> {
+        let (header, rest) = (pdu.get(..PDU_HEADER_LEN)?, pdu.get(PDU_HEADER_LEN..)?);
+        let payload = rest.get(..header[1] as usize)?;
+        let address = payload.get(..ADDRESS_LEN)?.try_into().ok()?;
+        Some(ScanReport {
+            pdu_type: header[0] & PDU_TYPE_MASK,
+            address,
+            data: &payload[ADDRESS_LEN..],
+        })
+    }
+
+    /// Returns an iterator over the `(ad_type, data)` AD structures in the
+    /// advertising data.
+    pub fn ad_structures(&self) -> AdStructures<'a> {
+        AdStructures { data: self.data }
+    }
+
+    /// Returns the complete or shortened local name of the advertiser.
+    pub fn local_name(&self) -> Option<&'a [u8]> {
+        self.ad_structures()
+            .find(|&(ty, _)| {
+                ty == ad_type::COMPLETE_LOCAL_NAME || ty == ad_type::SHORTENED_LOCAL_NAME
+            })
+            .map(|(_, name)| name)
+    }
+
+    /// Returns the company identifier and data of the first manufacturer
+    /// specific data AD structure.
+    pub fn manufacturer_data(&self) -> Option<(u16, &'a [u8])> {
+        self.ad_structures()
+            .find(|&(ty, data)| ty == ad_type::MANUFACTURER_DATA && data.len() >= 2)
+            .map(|(_, data)| (u16::from_le_bytes([data[0], data[1]]), &data[2..]))
+    }
+}
+
+/// An iterator over the AD structures in advertising data. Iteration stops at
+/// the first malformed or zero-length structure.
+#[derive(Clone, Debug)]
+pub struct AdStructures<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for AdStructures<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<(u8, &'a [u8])> {
+        let len = *self.data.first()? as usize;
+        match self.data.get(1..=len) {
+            Some(structure) if len > 0 => {
+                self.data = &self.data[len + 1..];
+                Some((structure[0], &structure[1..]))
+            }
+            _ => {
+                self.data = &[];
+                None
+            }
+        }
+    }
+}
+
+const PDU_HEADER_LEN: usize = 2;
+const PDU_TYPE_MASK: u8 = 0x0F;
+const ADDRESS_LEN: usize = 6;
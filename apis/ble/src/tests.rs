@@ -0,0 +1,184 @@
+use libtock_platform::{share, ErrorCode};
+use libtock_unittest::fake;
+
+use crate::{flags, AdvertisingPayload, AdvertisingType, ScanReport};
+
+type Ble = super::Ble<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ble::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ble::exists(), Ok(()));
+}
+
+#[test]
+fn exists_keeps_advertising() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    let mut payload = AdvertisingPayload::new();
+    payload.local_name("Tock").unwrap();
+    share::scope(|allow_ro| {
+        assert_eq!(
+            Ble::start_advertising(allow_ro, &payload, AdvertisingType::NonConnectable, 300),
+            Ok(())
+        );
+        assert_eq!(Ble::exists(), Ok(()));
+        assert!(driver.is_advertising());
+        assert_eq!(Ble::stop(), Ok(()));
+    });
+}
+
+#[test]
+fn payload() {
+    let mut payload = AdvertisingPayload::new();
+    payload
+        .flags(flags::LE_GENERAL_DISCOVERABLE | flags::BR_EDR_NOT_SUPPORTED)
+        .unwrap()
+        .local_name("Tock")
+        .unwrap()
+        .manufacturer_data(0x1234, &[0xAB])
+        .unwrap()
+        .service_uuids16(&[0x180F, 0x181A])
+        .unwrap();
+    assert_eq!(
+        payload.as_bytes(),
+        [
+            2, 0x01, 0x06, // Flags
+            5, 0x09, b'T', b'o', b'c', b'k', // Local name
+            4, 0xFF, 0x34, 0x12, 0xAB, // Manufacturer data
+            5, 0x03, 0x0F, 0x18, 0x1A, 0x18, // 16-bit UUIDs
+        ]
+    );
+
+    // 20 bytes are used, so a 128-bit UUID (18 bytes) does not fit.
+    assert_eq!(
+        payload.service_uuids128(&[1]).map(|_| ()),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(payload.as_bytes().len(), 20);
+
+    payload.clear();
+    payload.service_uuids128(&[0x0102]).unwrap();
+    assert_eq!(payload.as_bytes()[..4], [17, 0x07, 0x02, 0x01]);
+}
+
+#[test]
+fn scan_report() {
+    let pdu = [
+        0x42, 17, // Header: ADV_NONCONN_IND, payload length
+        1, 2, 3, 4, 5, 6, // Address
+        3, 0x08, b'h', b'i', // Shortened local name
+        5, 0xFF, 0x59, 0x00, 7, 8,    // Manufacturer data
+        0xAA, // Trailing bytes beyond the payload length are ignored.
+    ];
+    let report = ScanReport::parse(&pdu).unwrap();
+    assert_eq!(report.pdu_type, 0x02);
+    assert_eq!(report.address, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(report.local_name(), Some(&b"hi"[..]));
+    assert_eq!(report.manufacturer_data(), Some((0x0059, &[7, 8][..])));
+    assert_eq!(report.ad_structures().count(), 2);
+
+    // Truncated PDUs and AD structures.
+    assert_eq!(ScanReport::parse(&pdu[..10]), None);
+    assert_eq!(ScanReport::parse(&[0x00, 5, 1, 2, 3, 4, 5]), None);
+    let report = ScanReport::parse(&[0x00, 8, 1, 2, 3, 4, 5, 6, 4, 0x09]).unwrap();
+    assert_eq!(report.ad_structures().next(), None);
+}
+
+#[test]
+fn advertise() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    let mut payload = AdvertisingPayload::new();
+    payload.local_name("Tock").unwrap();
+    share::scope(|allow_ro| {
+        assert_eq!(
+            Ble::start_advertising(allow_ro, &payload, AdvertisingType::NonConnectable, 19),
+            Err(ErrorCode::Invalid)
+        );
+        assert_eq!(
+            Ble::start_advertising(allow_ro, &payload, AdvertisingType::NonConnectable, 300),
+            Ok(())
+        );
+        assert!(driver.is_advertising());
+        assert_eq!(Ble::stop(), Ok(()));
+    });
+    assert_eq!(
+        driver.take_advertisements(),
+        [fake::Advertisement {
+            advertising_type: 0x02,
+            interval_ms: 300,
+            data: payload.as_bytes().to_vec(),
+        }]
+    );
+    assert!(!driver.is_advertising());
+}
+
+#[test]
+fn tx_power() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ble::set_tx_power(-12), Ok(()));
+    assert_eq!(driver.tx_power(), Some(-12));
+    assert_eq!(Ble::set_tx_power(11), Err(ErrorCode::Invalid));
+    assert_eq!(Ble::set_tx_power(-21), Err(ErrorCode::Invalid));
+    assert_eq!(driver.tx_power(), Some(-12));
+}
+
+#[test]
+fn scan() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    driver.add_scan_report(&[0x00, 10, 1, 2, 3, 4, 5, 6, 3, 0x09, b'a', b'b']);
+    let mut buffer = [0; 39];
+    let report = Ble::scan_sync(&mut buffer).unwrap();
+    assert_eq!(report.pdu_type, 0x00);
+    assert_eq!(report.local_name(), Some(&b"ab"[..]));
+    assert!(!driver.is_scanning());
+
+    driver.add_scan_report(&[0x00, 2]);
+    assert_eq!(Ble::scan_sync(&mut buffer), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn passive_scan() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ble::new();
+    kernel.add_driver(&driver);
+
+    driver.add_scan_report(&[0x00, 6, 1, 1, 1, 1, 1, 1]);
+    // Unparseable reports are skipped.
+    driver.add_scan_report(&[0x00]);
+    driver.add_scan_report(&[0x02, 6, 2, 2, 2, 2, 2, 2]);
+    driver.add_scan_report(&[0x02, 6, 3, 3, 3, 3, 3, 3]);
+
+    let mut buffer = [0; 39];
+    let mut addresses = [0; 2];
+    let mut count = 0;
+    assert_eq!(
+        Ble::passive_scan(&mut buffer, |report| {
+            addresses[count] = report.address[0];
+            count += 1;
+            count < 2
+        }),
+        Ok(())
+    );
+    assert_eq!(addresses, [1, 2]);
+}
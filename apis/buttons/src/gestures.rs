@@ -0,0 +1,355 @@
+use crate::{Buttons, DRIVER_NUM};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_alarm::{Alarm, Convert, Milliseconds, Ticks, DRIVER_NUM as ALARM_DRIVER_NUM};
+use libtock_platform as platform;
+use libtock_platform::share::Handle;
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+/// The most events that can be waiting to be taken. Events that arrive while
+/// the queue is full are dropped.
+const QUEUE_LEN: usize = 8;
+
+/// A higher-level button event, reported by `Gestures`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Gesture {
+    /// The button was pressed, after debouncing.
+    Pressed,
+    /// The button was released, after debouncing.
+    Released,
+    /// The button was pressed and released once, and not pressed again within
+    /// the double-click interval.
+    Click,
+    /// The button was pressed a second time within the double-click interval.
+    /// Reported right after the second `Pressed`.
+    DoubleClick,
+    /// The button was held down for the long-press duration. No `Click` is
+    /// reported when it is released.
+    LongPress,
+}
+
+/// Timing for gesture detection.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GestureConfig {
+    /// Changes within this long of the previous accepted change are treated as
+    /// contact bounce. The button is read again once the interval ends.
+    pub debounce: Milliseconds,
+    /// How long a button must be held to report `LongPress`.
+    pub long_press: Milliseconds,
+    /// How long after a click a second press counts as a `DoubleClick`. If
+    /// zero, double-clicks are not detected and `Click` is reported as soon
+    /// as the button is released.
+    pub double_click: Milliseconds,
+}
+
+impl Default for GestureConfig {
+    fn default() -> GestureConfig {
+        GestureConfig {
+            debounce: Milliseconds(20),
+            long_press: Milliseconds(800),
+            double_click: Milliseconds(300),
+        }
+    }
+}
+
+/// Detects presses, clicks, double-clicks and long presses on the first `N`
+/// buttons.
+///
+/// `Gestures` subscribes to both the buttons driver and the alarm driver, so
+/// it cannot be used alongside a `ButtonListener` or an `AlarmMux`. Events are
+/// detected in upcalls, so they are only reported while the app yields.
+///
+/// # Example
+/// ```ignore
+/// use libtock::buttons::{Gesture, GestureConfig, Gestures};
+/// use libtock_platform::share;
+///
+/// let gestures: Gestures<2> = Gestures::new(GestureConfig::default());
+/// share::scope(|handle| {
+///     let (buttons, alarm) = handle.split();
+///     gestures.register(buttons, alarm)?;
+///     loop {
+///         match gestures.wait() {
+///             (0, Gesture::Click) => { /* ... */ }
+///             (0, Gesture::LongPress) => { /* ... */ }
+///             _ => {}
+///         }
+///     }
+/// })
+/// ```
+pub struct Gestures<S: Syscalls, const N: usize, C: platform::subscribe::Config = DefaultConfig> {
+    config: GestureConfig,
+    // The config's durations in ticks, set by `register`.
+    debounce: Cell<u32>,
+    long_press: Cell<u32>,
+    double_click: Cell<u32>,
+    trackers: [Tracker; N],
+    events: [Cell<Option<(u32, Gesture)>>; QUEUE_LEN],
+    queued: Cell<usize>,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, const N: usize, C: platform::subscribe::Config> Gestures<S, N, C> {
+    pub fn new(config: GestureConfig) -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const NO_EVENT: Cell<Option<(u32, Gesture)>> = Cell::new(None);
+        Gestures {
+            config,
+            debounce: Cell::new(0),
+            long_press: Cell::new(0),
+            double_click: Cell::new(0),
+            trackers: [Tracker::IDLE; N],
+            events: [NO_EVENT; QUEUE_LEN],
+            queued: Cell::new(0),
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Subscribes to the button and alarm upcalls, and enables interrupts for
+    /// the buttons this tracks. Buttons already held down are reported as
+    /// pressed by `is_pressed`, but produce no events until released.
+    pub fn register<'share>(
+        &'share self,
+        buttons: Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+        alarm: Handle<Subscribe<'share, S, ALARM_DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        let freq = Alarm::<S, C>::get_frequency()?;
+        self.debounce.set(self.config.debounce.to_ticks(freq).0);
+        self.long_press.set(self.config.long_press.to_ticks(freq).0);
+        self.double_click
+            .set(self.config.double_click.to_ticks(freq).0);
+        let count = Buttons::<S>::count()?;
+        S::subscribe::<OneId<DRIVER_NUM, 0>, _, C, DRIVER_NUM, 0>(buttons, self)?;
+        Alarm::<S, C>::register_listener::<OneId<ALARM_DRIVER_NUM, 0>, _>(self, alarm)?;
+        let now = Alarm::<S, C>::get_ticks()?.0;
+        for (button, tracker) in (0..count).zip(self.trackers.iter()) {
+            Buttons::<S>::enable_interrupts(button)?;
+            tracker.pressed.set(Buttons::<S>::is_pressed(button));
+            tracker.changed_at.set(now);
+        }
+        Ok(())
+    }
+
+    /// Returns the oldest event that has not been taken yet, as a button
+    /// number and gesture.
+    pub fn take_event(&self) -> Option<(u32, Gesture)> {
+        let queued = self.queued.get();
+        if queued == 0 {
+            return None;
+        }
+        let event = self.events[0].take();
+        for i in 1..queued {
+            self.events[i - 1].set(self.events[i].take());
+        }
+        self.queued.set(queued - 1);
+        event
+    }
+
+    /// Yields until an event arrives, and returns it.
+    pub fn wait(&self) -> (u32, Gesture) {
+        loop {
+            if let Some(event) = self.take_event() {
+                return event;
+            }
+            S::yield_wait();
+        }
+    }
+
+    /// Yields until any button is pressed, and returns its number. Events
+    /// other than `Pressed` are discarded.
+    pub fn wait_for_press(&self) -> u32 {
+        loop {
+            if let (button, Gesture::Pressed) = self.wait() {
+                return button;
+            }
+        }
+    }
+
+    /// Returns whether a button is pressed, after debouncing. Returns `false`
+    /// for buttons this does not track.
+    pub fn is_pressed(&self, button: u32) -> bool {
+        self.tracker(button)
+            .map_or(false, |tracker| tracker.pressed.get())
+    }
+
+    fn tracker(&self, button: u32) -> Option<&Tracker> {
+        self.trackers.get(button as usize)
+    }
+
+    fn push(&self, button: u32, gesture: Gesture) {
+        let queued = self.queued.get();
+        if queued < QUEUE_LEN {
+            self.events[queued].set(Some((button, gesture)));
+            self.queued.set(queued + 1);
+        }
+    }
+
+    // Accepts a debounced change of a button's state.
+    fn change(&self, button: u32, tracker: &Tracker, pressed: bool, now: u32) {
+        tracker.pressed.set(pressed);
+        tracker.changed_at.set(now);
+        tracker.settle.set(None);
+        if pressed {
+            self.push(button, Gesture::Pressed);
+            if tracker.phase.get() == Phase::AwaitSecond {
+                self.push(button, Gesture::DoubleClick);
+                tracker.phase.set(Phase::SecondHeld);
+                tracker.timer.set(None);
+            } else {
+                tracker.phase.set(Phase::Held);
+                tracker
+                    .timer
+                    .set(Some(Deadline::new(now, self.long_press.get())));
+            }
+        } else {
+            self.push(button, Gesture::Released);
+            if tracker.phase.get() == Phase::Held && self.double_click.get() > 0 {
+                tracker.phase.set(Phase::AwaitSecond);
+                tracker
+                    .timer
+                    .set(Some(Deadline::new(now, self.double_click.get())));
+            } else {
+                if tracker.phase.get() == Phase::Held {
+                    self.push(button, Gesture::Click);
+                }
+                tracker.phase.set(Phase::Idle);
+                tracker.timer.set(None);
+            }
+        }
+    }
+
+    // Sets the alarm for the earliest pending deadline, or stops it if there
+    // are none. Upcalls have nowhere to report errors.
+    fn rearm(&self, now: u32) {
+        let next = self
+            .trackers
+            .iter()
+            .flat_map(|tracker| [tracker.timer.get(), tracker.settle.get()])
+            .flatten()
+            .map(|deadline| deadline.remaining(now))
+            .min();
+        let _ = match next {
+            Some(dt) => Alarm::<S, C>::set_absolute(Ticks(now), Ticks(dt)).map(|_| ()),
+            None => Alarm::<S, C>::stop(),
+        };
+    }
+}
+
+impl<S: Syscalls, const N: usize, C: platform::subscribe::Config> Upcall<OneId<DRIVER_NUM, 0>>
+    for Gestures<S, N, C>
+{
+    fn upcall(&self, button: u32, state: u32, _arg2: u32) {
+        let tracker = match self.tracker(button) {
+            Some(tracker) => tracker,
+            None => return,
+        };
+        let pressed = state != 0;
+        if pressed == tracker.pressed.get() {
+            return;
+        }
+        let now = match Alarm::<S, C>::get_ticks() {
+            Ok(now) => now.0,
+            Err(_) => return,
+        };
+        let changed_at = tracker.changed_at.get();
+        if now.wrapping_sub(changed_at) < self.debounce.get() {
+            // Check the button again once it has settled.
+            tracker
+                .settle
+                .set(Some(Deadline::new(changed_at, self.debounce.get())));
+        } else {
+            self.change(button, tracker, pressed, now);
+        }
+        self.rearm(now);
+    }
+}
+
+impl<S: Syscalls, const N: usize, C: platform::subscribe::Config> Upcall<OneId<ALARM_DRIVER_NUM, 0>>
+    for Gestures<S, N, C>
+{
+    fn upcall(&self, now: u32, _expiration: u32, _arg2: u32) {
+        for (button, tracker) in (0..).zip(self.trackers.iter()) {
+            if let Some(timer) = tracker.timer.get() {
+                if timer.remaining(now) == 0 {
+                    tracker.timer.set(None);
+                    match tracker.phase.get() {
+                        Phase::Held => {
+                            self.push(button, Gesture::LongPress);
+                            tracker.phase.set(Phase::LongHeld);
+                        }
+                        Phase::AwaitSecond => {
+                            self.push(button, Gesture::Click);
+                            tracker.phase.set(Phase::Idle);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if let Some(settle) = tracker.settle.get() {
+                if settle.remaining(now) == 0 {
+                    tracker.settle.set(None);
+                    let pressed = Buttons::<S>::is_pressed(button);
+                    if pressed != tracker.pressed.get() {
+                        self.change(button, tracker, pressed, now);
+                    }
+                }
+            }
+        }
+        self.rearm(now);
+    }
+}
+
+// Tracks the debounced state of one button and the gesture in progress.
+struct Tracker {
+    pressed: Cell<bool>,
+    // When the debounced state last changed.
+    changed_at: Cell<u32>,
+    phase: Cell<Phase>,
+    // The long-press or double-click deadline, depending on the phase.
+    timer: Cell<Option<Deadline>>,
+    // When to read the button again after ignoring a bounce.
+    settle: Cell<Option<Deadline>>,
+}
+
+impl Tracker {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const IDLE: Tracker = Tracker {
+        pressed: Cell::new(false),
+        changed_at: Cell::new(0),
+        phase: Cell::new(Phase::Idle),
+        timer: Cell::new(None),
+        settle: Cell::new(None),
+    };
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Phase {
+    Idle,
+    // Pressed, waiting for release or a long press.
+    Held,
+    // Held past the long-press duration.
+    LongHeld,
+    // Released after a click, waiting for a second press.
+    AwaitSecond,
+    // Pressed the second time of a double-click.
+    SecondHeld,
+}
+
+// A deadline stored as a reference time and delay, so it expires correctly
+// when the tick counter wraps around.
+#[derive(Copy, Clone)]
+struct Deadline {
+    reference: u32,
+    delay: u32,
+}
+
+impl Deadline {
+    fn new(reference: u32, delay: u32) -> Deadline {
+        Deadline { reference, delay }
+    }
+
+    fn remaining(self, now: u32) -> u32 {
+        self.delay.saturating_sub(now.wrapping_sub(self.reference))
+    }
+}
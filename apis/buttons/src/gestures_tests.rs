@@ -0,0 +1,253 @@
+extern crate std;
+
+use libtock_alarm::Milliseconds;
+use libtock_platform::share;
+use libtock_platform::{ErrorCode, Subscribe, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+use crate::{Gesture, GestureConfig};
+
+type Gestures = crate::Gestures<fake::Syscalls, 2>;
+type Handles<'share> = (
+    Subscribe<'share, fake::Syscalls, 3, 0>,
+    Subscribe<'share, fake::Syscalls, { libtock_alarm::DRIVER_NUM }, 0>,
+);
+
+// Delivers any queued upcalls.
+fn deliver() {
+    while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+}
+
+fn set_pressed(buttons: &fake::Buttons<2>, button: u32, pressed: bool) {
+    assert_eq!(buttons.set_pressed(button, pressed), Ok(()));
+    deliver();
+}
+
+fn advance(alarm: &fake::Alarm, ticks: u32) {
+    alarm.advance(ticks);
+    deliver();
+}
+
+fn take_events(gestures: &Gestures) -> std::vec::Vec<(u32, Gesture)> {
+    core::iter::from_fn(|| gestures.take_event()).collect()
+}
+
+// Sets up the fake drivers, with a 1 kHz alarm so ticks are milliseconds.
+fn drivers() -> (
+    fake::Kernel,
+    std::rc::Rc<fake::Alarm>,
+    std::rc::Rc<fake::Buttons<2>>,
+) {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    let buttons = fake::Buttons::<2>::new();
+    kernel.add_driver(&alarm);
+    kernel.add_driver(&buttons);
+    (kernel, alarm, buttons)
+}
+
+#[test]
+fn no_driver() {
+    let kernel = fake::Kernel::new();
+    let alarm = fake::Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+    let gestures = Gestures::new(GestureConfig::default());
+    share::scope::<Handles, _, _>(|handle| {
+        let (buttons, alarm) = handle.split();
+        assert_eq!(gestures.register(buttons, alarm), Err(ErrorCode::NoDevice));
+    });
+}
+
+#[test]
+fn click() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig::default());
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        advance(&alarm, 100);
+        set_pressed(&buttons, 1, true);
+        assert!(gestures.is_pressed(1));
+        // The long-press timer is running.
+        assert_eq!(alarm.expiration(), Some(900));
+        advance(&alarm, 100);
+        set_pressed(&buttons, 1, false);
+        assert_eq!(
+            take_events(&gestures),
+            [(1, Gesture::Pressed), (1, Gesture::Released)]
+        );
+
+        // The click is reported once the double-click interval ends.
+        advance(&alarm, 299);
+        assert_eq!(gestures.take_event(), None);
+        advance(&alarm, 1);
+        assert_eq!(take_events(&gestures), [(1, Gesture::Click)]);
+        assert_eq!(alarm.expiration(), None);
+    });
+}
+
+#[test]
+fn click_without_double_click() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig {
+        double_click: Milliseconds(0),
+        ..GestureConfig::default()
+    });
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, true);
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, false);
+        assert_eq!(
+            take_events(&gestures),
+            [
+                (0, Gesture::Pressed),
+                (0, Gesture::Released),
+                (0, Gesture::Click)
+            ]
+        );
+        assert_eq!(alarm.expiration(), None);
+    });
+}
+
+#[test]
+fn double_click() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig::default());
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, true);
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, false);
+        advance(&alarm, 200);
+        set_pressed(&buttons, 0, true);
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, false);
+        assert_eq!(
+            take_events(&gestures),
+            [
+                (0, Gesture::Pressed),
+                (0, Gesture::Released),
+                (0, Gesture::Pressed),
+                (0, Gesture::DoubleClick),
+                (0, Gesture::Released)
+            ]
+        );
+
+        // Neither press is also reported as a click or long press.
+        advance(&alarm, 2000);
+        assert_eq!(gestures.take_event(), None);
+    });
+}
+
+#[test]
+fn long_press() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig {
+        long_press: Milliseconds(500),
+        ..GestureConfig::default()
+    });
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, true);
+        advance(&alarm, 499);
+        assert_eq!(take_events(&gestures), [(0, Gesture::Pressed)]);
+        advance(&alarm, 1);
+        assert_eq!(take_events(&gestures), [(0, Gesture::LongPress)]);
+        advance(&alarm, 1000);
+        set_pressed(&buttons, 0, false);
+        advance(&alarm, 1000);
+        assert_eq!(take_events(&gestures), [(0, Gesture::Released)]);
+    });
+}
+
+#[test]
+fn debounce() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig {
+        double_click: Milliseconds(0),
+        ..GestureConfig::default()
+    });
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        // The bounces right after the press are ignored.
+        advance(&alarm, 100);
+        set_pressed(&buttons, 0, true);
+        advance(&alarm, 2);
+        set_pressed(&buttons, 0, false);
+        advance(&alarm, 2);
+        set_pressed(&buttons, 0, true);
+        assert!(gestures.is_pressed(0));
+        advance(&alarm, 100);
+        assert_eq!(take_events(&gestures), [(0, Gesture::Pressed)]);
+
+        // A press that comes within the debounce interval of the release is
+        // picked up when the button is read again.
+        set_pressed(&buttons, 0, false);
+        advance(&alarm, 5);
+        set_pressed(&buttons, 0, true);
+        assert!(!gestures.is_pressed(0));
+        assert_eq!(alarm.expiration(), Some(224));
+        advance(&alarm, 15);
+        assert!(gestures.is_pressed(0));
+        assert_eq!(
+            take_events(&gestures),
+            [
+                (0, Gesture::Released),
+                (0, Gesture::Click),
+                (0, Gesture::Pressed)
+            ]
+        );
+        assert_eq!(alarm.expiration(), Some(1024));
+    });
+}
+
+#[test]
+fn wait() {
+    let (_kernel, alarm, buttons) = drivers();
+    let gestures = Gestures::new(GestureConfig::default());
+    share::scope::<Handles, _, _>(|handle| {
+        let (subscribe_buttons, subscribe_alarm) = handle.split();
+        assert_eq!(
+            gestures.register(subscribe_buttons, subscribe_alarm),
+            Ok(())
+        );
+
+        advance(&alarm, 100);
+        // The fake kernel's yield_wait panics if no upcall is queued, so the
+        // upcalls are queued before waiting.
+        assert_eq!(buttons.set_pressed(1, true), Ok(()));
+        assert_eq!(gestures.wait(), (1, Gesture::Pressed));
+        assert_eq!(buttons.set_pressed(1, false), Ok(()));
+        assert_eq!(buttons.set_pressed(0, true), Ok(()));
+        assert_eq!(gestures.wait_for_press(), 0);
+        assert_eq!(gestures.take_event(), None);
+    });
+}
@@ -131,6 +131,12 @@ impl<F: Fn(u32, ButtonState)> Upcall<OneId<DRIVER_NUM, 0>> for ButtonListener<F>
 #[cfg(test)]
 mod tests;
 
+mod gestures;
+pub use gestures::{Gesture, GestureConfig, Gestures};
+
+#[cfg(test)]
+mod gestures_tests;
+
 // -----------------------------------------------------------------------------
 // Driver number and command IDs
 // -----------------------------------------------------------------------------
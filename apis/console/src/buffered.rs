@@ -0,0 +1,312 @@
+use crate::{Config, Console, ConsoleWriter};
+use core::fmt;
+use core::marker::PhantomData;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// A console writer that batches small writes.
+///
+/// Bytes are collected in an `N`-byte ring buffer, and written to the console
+/// when a newline is written, when the buffer is full, on `flush`, and when
+/// the writer is dropped. `N` must be at least 1; a zero-byte buffer fails to
+/// compile.
+///
+/// # Example
+/// ```ignore
+/// use libtock::console::BufferedWriter;
+///
+/// let mut writer = BufferedWriter::<64>::new();
+/// for value in values {
+///     write!(writer, "{} ", value)?;
+/// }
+/// writer.flush()?;
+/// ```
+pub struct BufferedWriter<S: Syscalls, C: Config = DefaultConfig, const N: usize = 64> {
+    buffer: RingBuffer<N>,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config, const N: usize> BufferedWriter<S, C, N> {
+    // Evaluated by `new`, so that a zero-byte buffer fails to compile.
+    const CAPACITY: usize = {
+        assert!(N > 0, "BufferedWriter needs a buffer of at least one byte");
+        N
+    };
+
+    pub fn new() -> Self {
+        let _ = Self::CAPACITY;
+        BufferedWriter {
+            buffer: RingBuffer::new(),
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Adds `bytes` to the buffer, writing to the console as needed.
+    pub fn write(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        for &byte in bytes {
+            if self.buffer.is_full() {
+                self.flush()?;
+            }
+            self.buffer.push(byte);
+            if byte == b'\n' {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered bytes to the console. If an error is returned, the
+    /// bytes that were not written remain buffered.
+    pub fn flush(&mut self) -> Result<(), ErrorCode> {
+        while !self.buffer.is_empty() {
+            let chunk = self.buffer.front();
+            Console::<S, C>::write(chunk)?;
+            let written = chunk.len();
+            self.buffer.consume(written);
+        }
+        Ok(())
+    }
+
+    /// The number of bytes waiting to be written.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<S: Syscalls, C: Config, const N: usize> Default for BufferedWriter<S, C, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Syscalls, C: Config, const N: usize> Drop for BufferedWriter<S, C, N> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<S: Syscalls, C: Config, const N: usize> fmt::Write for BufferedWriter<S, C, N> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        self.write(s.as_bytes()).map_err(|_e| fmt::Error)
+    }
+}
+
+impl<S: Syscalls, C: Config, const N: usize> ufmt_write::uWrite for BufferedWriter<S, C, N> {
+    type Error = ErrorCode;
+
+    fn write_str(&mut self, s: &str) -> Result<(), ErrorCode> {
+        self.write(s.as_bytes())
+    }
+}
+
+/// A console reader with line editing.
+///
+/// The console driver completes a read only once every requested byte has
+/// arrived, so the reader requests one byte at a time.
+///
+/// Reads wait for their bytes inside `read_byte`, `read_line` and `read`, so
+/// they cannot be cancelled with `Console::abort_read`.
+pub struct ConsoleReader<S: Syscalls, C: Config = DefaultConfig> {
+    echo: bool,
+    // Set after a carriage return, so that a following line feed does not end
+    // an empty line.
+    skip_line_feed: bool,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> ConsoleReader<S, C> {
+    /// Creates a reader that echoes lines read with `read_line`.
+    pub fn new() -> Self {
+        ConsoleReader {
+            echo: true,
+            skip_line_feed: false,
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Sets whether `read_line` echoes what is typed back to the console.
+    pub fn set_echo(&mut self, echo: bool) {
+        self.echo = echo;
+    }
+
+    /// Waits for a single byte.
+    pub fn read_byte(&mut self) -> Result<u8, ErrorCode> {
+        let mut byte = [0];
+        loop {
+            let (count, result) = Console::<S, C>::read(&mut byte);
+            result?;
+            if count == 1 {
+                return Ok(byte[0]);
+            }
+        }
+    }
+
+    /// Reads a line into `line`, returning its length. The line ends at a
+    /// carriage return, a line feed, or both, which are not included.
+    ///
+    /// Backspace and delete remove the last byte of the line. Bytes that do
+    /// not fit in `line` are discarded.
+    pub fn read_line(&mut self, line: &mut [u8]) -> Result<usize, ErrorCode> {
+        let mut len = 0;
+        loop {
+            let byte = self.read_byte()?;
+            let skip_line_feed = core::mem::replace(&mut self.skip_line_feed, false);
+            match byte {
+                b'\n' if skip_line_feed => {}
+                b'\r' | b'\n' => {
+                    self.skip_line_feed = byte == b'\r';
+                    self.echo(b"\r\n")?;
+                    return Ok(len);
+                }
+                BACKSPACE | DELETE => {
+                    if len > 0 {
+                        len -= 1;
+                        self.echo(b"\x08 \x08")?;
+                    }
+                }
+                _ => {
+                    if let Some(slot) = line.get_mut(len) {
+                        *slot = byte;
+                        len += 1;
+                        self.echo(&[byte])?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn echo(&self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        if !self.echo {
+            return Ok(());
+        }
+        Console::<S, C>::write(bytes)
+    }
+}
+
+impl<S: Syscalls, C: Config> Default for ConsoleReader<S, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The error type of the `embedded_io` implementations.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConsoleError(pub ErrorCode);
+
+impl From<ErrorCode> for ConsoleError {
+    fn from(error: ErrorCode) -> ConsoleError {
+        ConsoleError(error)
+    }
+}
+
+impl embedded_io::Error for ConsoleError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self.0 {
+            ErrorCode::Cancel => embedded_io::ErrorKind::Interrupted,
+            ErrorCode::Invalid => embedded_io::ErrorKind::InvalidInput,
+            ErrorCode::NoMem => embedded_io::ErrorKind::OutOfMemory,
+            ErrorCode::NoSupport => embedded_io::ErrorKind::Unsupported,
+            _ => embedded_io::ErrorKind::Other,
+        }
+    }
+}
+
+impl<S: Syscalls> embedded_io::ErrorType for ConsoleWriter<S> {
+    type Error = ConsoleError;
+}
+
+impl<S: Syscalls> embedded_io::Write for ConsoleWriter<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ConsoleError> {
+        Console::<S>::write(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), ConsoleError> {
+        Ok(())
+    }
+}
+
+impl<S: Syscalls, C: Config, const N: usize> embedded_io::ErrorType for BufferedWriter<S, C, N> {
+    type Error = ConsoleError;
+}
+
+impl<S: Syscalls, C: Config, const N: usize> embedded_io::Write for BufferedWriter<S, C, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ConsoleError> {
+        BufferedWriter::write(self, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), ConsoleError> {
+        Ok(BufferedWriter::flush(self)?)
+    }
+}
+
+impl<S: Syscalls, C: Config> embedded_io::ErrorType for ConsoleReader<S, C> {
+    type Error = ConsoleError;
+}
+
+/// Reads return a single byte, as soon as it is available.
+impl<S: Syscalls, C: Config> embedded_io::Read for ConsoleReader<S, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, ConsoleError> {
+        match buf.first_mut() {
+            None => Ok(0),
+            Some(first) => {
+                *first = self.read_byte()?;
+                Ok(1)
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const BACKSPACE: u8 = 0x08;
+const DELETE: u8 = 0x7f;
+
+struct RingBuffer<const N: usize> {
+    bytes: [u8; N],
+    // The index of the oldest byte, and the number of bytes stored.
+    start: usize,
+    len: usize,
+}
+
+impl<const N: usize> RingBuffer<N> {
+    fn new() -> Self {
+        RingBuffer {
+            bytes: [0; N],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // Panics if the buffer is full.
+    fn push(&mut self, byte: u8) {
+        assert!(!self.is_full());
+        self.bytes[(self.start + self.len) % N] = byte;
+        self.len += 1;
+    }
+
+    // Returns the oldest bytes that are contiguous in memory.
+    fn front(&self) -> &[u8] {
+        let end = core::cmp::min(self.start + self.len, N);
+        &self.bytes[self.start..end]
+    }
+
+    fn consume(&mut self, count: usize) {
+        self.start = (self.start + count) % N;
+        self.len -= count;
+    }
+}
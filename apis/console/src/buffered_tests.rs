@@ -0,0 +1,193 @@
+use core::cell::Cell;
+use core::fmt::Write;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+use crate::{allow_rw, subscribe, DRIVER_NUM};
+
+type Console = crate::Console<fake::Syscalls>;
+type BufferedWriter<const N: usize> =
+    crate::BufferedWriter<fake::Syscalls, libtock_platform::DefaultConfig, N>;
+type ConsoleReader = crate::ConsoleReader<fake::Syscalls>;
+
+#[test]
+fn buffered_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut writer = BufferedWriter::<16>::new();
+    assert_eq!(writer.write(b"foo"), Ok(()));
+    assert_eq!(writer.write(b"bar"), Ok(()));
+    assert_eq!(writer.buffered_len(), 6);
+    assert_eq!(driver.take_bytes(), b"");
+    assert_eq!(writer.flush(), Ok(()));
+    assert_eq!(driver.take_bytes(), b"foobar");
+
+    // A newline flushes the line.
+    write!(writer, "{}\nba", 42).unwrap();
+    assert_eq!(driver.take_bytes(), b"42\n");
+    assert_eq!(writer.buffered_len(), 2);
+
+    // Dropping the writer flushes the rest.
+    drop(writer);
+    assert_eq!(driver.take_bytes(), b"ba");
+}
+
+#[test]
+fn buffered_uwrite() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut writer = BufferedWriter::<16>::new();
+    ufmt::uwrite!(writer, "{}\nba", 42u32).unwrap();
+    assert_eq!(driver.take_bytes(), b"42\n");
+    assert_eq!(writer.buffered_len(), 2);
+    ufmt::uwriteln!(writer, "{}", 7u32).unwrap();
+    assert_eq!(driver.take_bytes(), b"ba7\n");
+}
+
+#[test]
+fn buffered_write_full() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut writer = BufferedWriter::<4>::new();
+    assert_eq!(writer.write(b"abcdefghij"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"abcdefgh");
+    assert_eq!(writer.buffered_len(), 2);
+    // The buffer wraps around.
+    assert_eq!(writer.write(b"kl\nmn"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"ijkl\n");
+    assert_eq!(writer.flush(), Ok(()));
+    assert_eq!(driver.take_bytes(), b"mn");
+}
+
+#[test]
+fn embedded_io_write() {
+    use embedded_io::Write;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut writer = BufferedWriter::<16>::new();
+    writer.write_all(b"buffered").unwrap();
+    assert_eq!(driver.take_bytes(), b"");
+    writer.flush().unwrap();
+    assert_eq!(driver.take_bytes(), b"buffered");
+
+    let mut writer = Console::writer();
+    writer.write_all(b"direct").unwrap();
+    assert_eq!(driver.take_bytes(), b"direct");
+}
+
+#[test]
+fn read_line() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"hello\r\nworld\n\rlast\r");
+    kernel.add_driver(&driver);
+
+    let mut reader = ConsoleReader::new();
+    let mut line = [0; 16];
+    assert_eq!(reader.read_line(&mut line), Ok(5));
+    assert_eq!(&line[..5], b"hello");
+    assert_eq!(reader.read_line(&mut line), Ok(5));
+    assert_eq!(&line[..5], b"world");
+    // A carriage return after a line feed ends an empty line.
+    assert_eq!(reader.read_line(&mut line), Ok(0));
+    assert_eq!(reader.read_line(&mut line), Ok(4));
+    assert_eq!(&line[..4], b"last");
+    assert_eq!(driver.take_bytes(), b"hello\r\nworld\r\n\r\nlast\r\n");
+}
+
+#[test]
+fn read_line_editing() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"\x08helx\x08lo\x7f\x7flo\n");
+    kernel.add_driver(&driver);
+
+    let mut reader = ConsoleReader::new();
+    let mut line = [0; 16];
+    assert_eq!(reader.read_line(&mut line), Ok(5));
+    assert_eq!(&line[..5], b"hello");
+    // Backspace at the start of the line is ignored.
+    assert_eq!(
+        driver.take_bytes(),
+        b"helx\x08 \x08lo\x08 \x08\x08 \x08lo\r\n"
+    );
+}
+
+#[test]
+fn read_line_overflow() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"abcdef\x08g\nnext\n");
+    kernel.add_driver(&driver);
+
+    let mut reader = ConsoleReader::new();
+    reader.set_echo(false);
+    let mut line = [0; 4];
+    assert_eq!(reader.read_line(&mut line), Ok(4));
+    assert_eq!(&line, b"abcg");
+    assert_eq!(reader.read_line(&mut line), Ok(4));
+    assert_eq!(&line, b"next");
+    assert_eq!(driver.take_bytes(), b"");
+}
+
+#[test]
+fn embedded_io_read() {
+    use embedded_io::Read;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"xyz");
+    kernel.add_driver(&driver);
+
+    let mut reader = ConsoleReader::new();
+    let mut buf = [0; 8];
+    assert_eq!(reader.read(&mut buf), Ok(1));
+    assert_eq!(buf[0], b'x');
+    assert_eq!(reader.read(&mut []), Ok(0));
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"yz");
+}
+
+#[test]
+fn abort_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 4];
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+            Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        assert_eq!(Console::allow_read_buffer(&mut buf, allow_rw), Ok(()));
+        assert_eq!(Console::register_read_listener(&done, subscribe), Ok(()));
+
+        assert_eq!(Console::start_read(4), Ok(()));
+        assert!(driver.is_reading());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(Console::abort_read(), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::Cancel as u32, 0)));
+        assert!(!driver.is_reading());
+
+        // A read completes when input arrives.
+        assert_eq!(Console::start_read(2), Ok(()));
+        driver.add_input(b"ok");
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 2)));
+    });
+    assert_eq!(&buf[..2], b"ok");
+}
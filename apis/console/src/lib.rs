@@ -7,8 +7,11 @@ use libtock_platform as platform;
 use libtock_platform::allow_ro::AllowRo;
 use libtock_platform::allow_rw::AllowRw;
 use libtock_platform::share;
-use libtock_platform::subscribe::Subscribe;
-use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_platform::subscribe::{Subscribe, SupportsId};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+mod buffered;
+pub use buffered::{BufferedWriter, ConsoleError, ConsoleReader};
 
 /// The console driver.
 ///
@@ -103,6 +106,42 @@ impl<S: Syscalls, C: Config> Console<S, C> {
         (bytes_received, r)
     }
 
+    /// Shares the buffer that reads started by `start_read` write into.
+    pub fn allow_read_buffer<'share>(
+        buffer: &'share mut [u8],
+        allow_rw: share::Handle<AllowRw<'share, S, DRIVER_NUM, { allow_rw::READ }>>,
+    ) -> Result<(), ErrorCode> {
+        S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)
+    }
+
+    /// Registers a listener for read completions. The upcall's arguments are
+    /// the status (0 or an `ErrorCode`) and the number of bytes read.
+    pub fn register_read_listener<
+        'share,
+        IDS: SupportsId<DRIVER_NUM, { subscribe::READ }>,
+        U: Upcall<IDS>,
+    >(
+        listener: &'share U,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::READ }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READ }>(subscribe, listener)
+    }
+
+    /// Starts reading `len` bytes into the read buffer, without waiting for
+    /// them to arrive.
+    pub fn start_read(len: usize) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::READ, len as u32, 0).to_result()
+    }
+
+    /// Aborts the current read. The read listener receives `Cancel` along
+    /// with the number of bytes read before the abort.
+    ///
+    /// Only reads started with `start_read` can be aborted: `read` and
+    /// `ConsoleReader` wait for their reads to complete before returning.
+    pub fn abort_read() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ABORT, 0, 0).to_result()
+    }
+
     pub fn writer() -> ConsoleWriter<S> {
         ConsoleWriter {
             syscalls: Default::default(),
@@ -110,6 +149,8 @@ impl<S: Syscalls, C: Config> Console<S, C> {
     }
 }
 
+/// A writer that writes to the console. It implements both `core::fmt::Write`
+/// and `ufmt::uWrite`.
 pub struct ConsoleWriter<S: Syscalls> {
     syscalls: PhantomData<S>,
 }
@@ -120,6 +161,14 @@ impl<S: Syscalls> fmt::Write for ConsoleWriter<S> {
     }
 }
 
+impl<S: Syscalls> ufmt_write::uWrite for ConsoleWriter<S> {
+    type Error = ErrorCode;
+
+    fn write_str(&mut self, s: &str) -> Result<(), ErrorCode> {
+        Console::<S>::write(s.as_bytes())
+    }
+}
+
 /// System call configuration trait for `Console`.
 pub trait Config:
     platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
@@ -130,6 +179,8 @@ impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subs
 {
 }
 
+#[cfg(test)]
+mod buffered_tests;
 #[cfg(test)]
 mod tests;
 
@@ -140,7 +191,6 @@ mod tests;
 const DRIVER_NUM: u32 = 1;
 
 // Command IDs
-#[allow(unused)]
 mod command {
     pub const DRIVER_CHECK: u32 = 0;
     pub const WRITE: u32 = 1;
@@ -148,7 +198,6 @@ mod command {
     pub const ABORT: u32 = 3;
 }
 
-#[allow(unused)]
 mod subscribe {
     pub const WRITE: u32 = 1;
     pub const READ: u32 = 2;
@@ -42,6 +42,16 @@ fn write_str() {
     assert_eq!(driver.take_bytes(), b"foo");
 }
 
+#[test]
+fn uwrite_str() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    ufmt::uwrite!(Console::writer(), "{}-{}", 12u8, -3i32).unwrap();
+    assert_eq!(driver.take_bytes(), b"12--3");
+}
+
 #[test]
 fn read_bytes_short() {
     let kernel = fake::Kernel::new();
@@ -0,0 +1,256 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The CRC driver, which computes checksums in hardware.
+///
+/// The kernel computes the CRC of a single buffer. Messages that are not in a
+/// single buffer can be checksummed in chunks with a `CrcStream`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::crc::{Crc, CrcAlgorithm};
+///
+/// let crc = Crc::compute(CrcAlgorithm::Crc32, b"123456789")?;
+/// assert_eq!(crc, 0xcbf43926);
+///
+/// let mut stream = Crc::stream(CrcAlgorithm::Crc32);
+/// stream.update(b"1234")?;
+/// stream.update(b"56789")?;
+/// assert_eq!(stream.value(), 0xcbf43926);
+/// ```
+pub struct Crc<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Crc<S, C> {
+    /// Run a check against the CRC capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Computes the CRC of `data` with `algorithm`. For `Crc16Ccitt`, the
+    /// CRC is in the lower 16 bits.
+    pub fn compute(algorithm: CrcAlgorithm, data: &[u8]) -> Result<u32, ErrorCode> {
+        let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::BUFFER }>(allow_ro, data)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(
+                DRIVER_NUM,
+                command::COMPUTE,
+                algorithm as u32,
+                data.len() as u32,
+            )
+            .to_result()?;
+            loop {
+                S::yield_wait();
+                if let Some((status, crc)) = done.get() {
+                    return match status {
+                        0 => Ok(crc),
+                        _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Starts computing the CRC of a message that is added in chunks.
+    pub fn stream(algorithm: CrcAlgorithm) -> CrcStream<S, C> {
+        CrcStream {
+            algorithm,
+            value: algorithm.initial() ^ algorithm.xor_out(),
+            _syscalls: core::marker::PhantomData,
+        }
+    }
+}
+
+/// The CRC algorithms supported by the kernel.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CrcAlgorithm {
+    /// CRC-32 with polynomial 0x04c11db7, as used by Ethernet and zlib.
+    Crc32 = 0,
+    /// CRC-32C with the Castagnoli polynomial 0x1edc6f41, as used by iSCSI.
+    Crc32C = 1,
+    /// CRC-16-CCITT with polynomial 0x1021 and initial value 0xffff, without
+    /// reflection or a final XOR.
+    Crc16Ccitt = 2,
+}
+
+/// The CRC of a message added in chunks, returned by `Crc::stream`.
+///
+/// Each chunk is checksummed by the kernel on its own, and its CRC is
+/// combined in software with the CRC of the preceding chunks.
+pub struct CrcStream<S: Syscalls, C: Config = DefaultConfig> {
+    algorithm: CrcAlgorithm,
+    value: u32,
+    _syscalls: core::marker::PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> CrcStream<S, C> {
+    pub fn algorithm(&self) -> CrcAlgorithm {
+        self.algorithm
+    }
+
+    /// Appends `data` to the message. If an error is returned, the message
+    /// is left unchanged.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ErrorCode> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let crc = Crc::<S, C>::compute(self.algorithm, data)?;
+        self.value = self.algorithm.combine(self.value, crc, data.len());
+        Ok(())
+    }
+
+    /// The CRC of the message added so far.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// System call configuration trait for `Crc`.
+pub trait Config: platform::allow_ro::Config + platform::subscribe::Config {}
+impl<T: platform::allow_ro::Config + platform::subscribe::Config> Config for T {}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl CrcAlgorithm {
+    fn width(self) -> u32 {
+        match self {
+            CrcAlgorithm::Crc16Ccitt => 16,
+            _ => 32,
+        }
+    }
+
+    fn polynomial(self) -> u32 {
+        match self {
+            CrcAlgorithm::Crc32 => 0x04c1_1db7,
+            CrcAlgorithm::Crc32C => 0x1edc_6f41,
+            CrcAlgorithm::Crc16Ccitt => 0x1021,
+        }
+    }
+
+    fn initial(self) -> u32 {
+        match self {
+            CrcAlgorithm::Crc16Ccitt => 0xffff,
+            _ => 0xffff_ffff,
+        }
+    }
+
+    fn xor_out(self) -> u32 {
+        match self {
+            CrcAlgorithm::Crc16Ccitt => 0,
+            _ => 0xffff_ffff,
+        }
+    }
+
+    // Whether the data bits are processed least significant bit first, in
+    // which case the CRC register is bit-reversed.
+    fn reflected(self) -> bool {
+        self != CrcAlgorithm::Crc16Ccitt
+    }
+
+    // Returns the CRC of A followed by B, given the CRCs of A and B and the
+    // length of B. Processing B from the register left by A gives the same
+    // result as processing B from the initial value, except that the
+    // difference between the two starting registers is shifted through the
+    // register once per bit of B. As CRCs are linear, that shifted difference
+    // is XORed into the CRC of B.
+    fn combine(self, crc_a: u32, crc_b: u32, len_b: usize) -> u32 {
+        let difference = self.to_polynomial(crc_a ^ self.xor_out() ^ self.initial());
+        let shifted = self.multiply(difference, self.x_to_bytes(len_b));
+        self.to_polynomial(shifted) ^ crc_b
+    }
+
+    // Converts between the CRC register and a polynomial with the coefficient
+    // of x^i in bit i. The conversion is its own inverse.
+    fn to_polynomial(self, register: u32) -> u32 {
+        match self.reflected() {
+            true => register.reverse_bits() >> (32 - self.width()),
+            false => register,
+        }
+    }
+
+    // Multiplies the polynomials `a` and `b` modulo the CRC polynomial, with
+    // the coefficient of x^i in bit i.
+    fn multiply(self, a: u32, b: u32) -> u32 {
+        let mut product = 0;
+        for bit in (0..self.width()).rev() {
+            product = self.times_x(product);
+            if (b >> bit) & 1 == 1 {
+                product ^= a;
+            }
+        }
+        product
+    }
+
+    fn times_x(self, value: u32) -> u32 {
+        let top = 1 << (self.width() - 1);
+        let shifted = (value << 1) & self.mask();
+        if value & top == 0 {
+            shifted
+        } else {
+            shifted ^ self.polynomial()
+        }
+    }
+
+    fn mask(self) -> u32 {
+        u32::MAX >> (32 - self.width())
+    }
+
+    // Returns x^(8 * len) modulo the CRC polynomial, which is the effect of
+    // shifting `len` bytes through the CRC register.
+    fn x_to_bytes(self, len: usize) -> u32 {
+        let mut result = 1;
+        let mut square = (0..8).fold(1, |value, _| self.times_x(value));
+        let mut len = len;
+        while len != 0 {
+            if len & 1 == 1 {
+                result = self.multiply(result, square);
+            }
+            square = self.multiply(square, square);
+            len >>= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40002;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const COMPUTE: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const BUFFER: u32 = 0;
+}
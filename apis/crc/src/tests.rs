@@ -0,0 +1,87 @@
+use crate::CrcAlgorithm;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Crc = super::Crc<fake::Syscalls>;
+
+const ALGORITHMS: [CrcAlgorithm; 3] = [
+    CrcAlgorithm::Crc32,
+    CrcAlgorithm::Crc32C,
+    CrcAlgorithm::Crc16Ccitt,
+];
+
+// The check values of the algorithms, which are the CRCs of "123456789".
+const CHECK: [u32; 3] = [0xcbf4_3926, 0xe306_9283, 0x29b1];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Crc::exists(), Err(ErrorCode::NoDevice));
+    assert_eq!(
+        Crc::compute(CrcAlgorithm::Crc32, b"123456789"),
+        Err(ErrorCode::NoDevice)
+    );
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Crc::exists(), Ok(()));
+}
+
+#[test]
+fn compute() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    for (algorithm, check) in ALGORITHMS.into_iter().zip(CHECK) {
+        assert_eq!(Crc::compute(algorithm, b"123456789"), Ok(check));
+    }
+    assert_eq!(Crc::compute(CrcAlgorithm::Crc32, b""), Ok(0));
+    assert_eq!(Crc::compute(CrcAlgorithm::Crc16Ccitt, b""), Ok(0xffff));
+}
+
+#[test]
+fn stream() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    // Every way of splitting the message in three chunks gives the same CRC.
+    let message = b"123456789";
+    for (algorithm, check) in ALGORITHMS.into_iter().zip(CHECK) {
+        let empty = Crc::compute(algorithm, b"").unwrap();
+        assert_eq!(Crc::stream(algorithm).value(), empty);
+        for first in 0..=message.len() {
+            for second in first..=message.len() {
+                let mut stream = Crc::stream(algorithm);
+                assert_eq!(stream.update(&message[..first]), Ok(()));
+                assert_eq!(stream.update(&message[first..second]), Ok(()));
+                assert_eq!(stream.update(&message[second..]), Ok(()));
+                assert_eq!(stream.value(), check);
+            }
+        }
+    }
+}
+
+#[test]
+fn stream_long_chunks() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    let message: [u8; 1000] = core::array::from_fn(|i| (i * 7) as u8);
+    for algorithm in ALGORITHMS {
+        let expected = Crc::compute(algorithm, &message).unwrap();
+        let mut stream = Crc::stream(algorithm);
+        for chunk in message.chunks(300) {
+            assert_eq!(stream.update(chunk), Ok(()));
+        }
+        assert_eq!(stream.algorithm(), algorithm);
+        assert_eq!(stream.value(), expected);
+    }
+}
@@ -0,0 +1,171 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{OneId, Subscribe};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+/// The CTAP HID driver, which exchanges CTAP HID packets with a USB host, as
+/// used by FIDO security keys.
+///
+/// Packets can be exchanged synchronously with `send` and `receive`, or a
+/// `CtapListener` can be notified when the kernel has sent or received a
+/// packet.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ctap::{Ctap, PACKET_LEN};
+///
+/// let mut packet = [0; PACKET_LEN];
+/// Ctap::receive(&mut packet)?;
+/// Ctap::send(&packet)?;
+/// ```
+pub struct Ctap<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Ctap<S, C> {
+    /// Run a check against the CTAP capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Sends `packet` to the host, and waits until it has been sent.
+    pub fn send(packet: &[u8; PACKET_LEN]) -> Result<(), ErrorCode> {
+        let mut buffer = *packet;
+        let event: Cell<Option<CtapEvent>> = Cell::new(None);
+        let listener = CtapListener(|e| event.set(Some(e)));
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::SEND }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::SEND }>(allow_rw, &mut buffer)?;
+            Self::register_listener(&listener, subscribe)?;
+            S::command(DRIVER_NUM, command::SEND, 0, 0).to_result()?;
+            while event.get() != Some(CtapEvent::Sent) {
+                S::yield_wait();
+            }
+            Ok(())
+        })
+    }
+
+    /// Waits for a packet from the host, and writes it into `packet`.
+    pub fn receive(packet: &mut [u8; PACKET_LEN]) -> Result<(), ErrorCode> {
+        let event: Cell<Option<CtapEvent>> = Cell::new(None);
+        let listener = CtapListener(|e| event.set(Some(e)));
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::RECEIVE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            Self::allow_receive_buffer(allow_rw, packet)?;
+            Self::register_listener(&listener, subscribe)?;
+            Self::start_receive()?;
+            while event.get() != Some(CtapEvent::Received) {
+                S::yield_wait();
+            }
+            Ok(())
+        })
+    }
+
+    /// Shares the buffer the next received packet is written into.
+    pub fn allow_receive_buffer<'share>(
+        allow_rw: share::Handle<AllowRw<'share, S, DRIVER_NUM, { allow_rw::RECEIVE }>>,
+        buffer: &'share mut [u8; PACKET_LEN],
+    ) -> Result<(), ErrorCode> {
+        S::allow_rw::<C, DRIVER_NUM, { allow_rw::RECEIVE }>(allow_rw, buffer)
+    }
+
+    /// Register an events listener
+    pub fn register_listener<'share, F: Fn(CtapEvent)>(
+        listener: &'share CtapListener<F>,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::DONE }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, listener)
+    }
+
+    /// Unregister the events listener
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::DONE)
+    }
+
+    /// Starts waiting for a packet. When one arrives, it is written into the
+    /// receive buffer and the listener receives `CtapEvent::Received`.
+    pub fn start_receive() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::RECEIVE, 0, 0).to_result()
+    }
+
+    /// Stops waiting for a packet.
+    pub fn cancel() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::CANCEL, 0, 0).to_result()
+    }
+}
+
+/// The length of a CTAP HID packet.
+pub const PACKET_LEN: usize = 64;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CtapEvent {
+    /// A packet was written into the receive buffer.
+    Received,
+    /// The packet in the send buffer was sent.
+    Sent,
+}
+
+pub struct CtapListener<F: Fn(CtapEvent)>(pub F);
+
+impl<F: Fn(CtapEvent)> Upcall<OneId<DRIVER_NUM, { subscribe::DONE }>> for CtapListener<F> {
+    fn upcall(&self, kind: u32, _arg1: u32, _arg2: u32) {
+        match kind {
+            RECEIVED => self.0(CtapEvent::Received),
+            SENT => self.0(CtapEvent::Sent),
+            _ => {}
+        }
+    }
+}
+
+/// System call configuration trait for `Ctap`.
+pub trait Config: platform::allow_rw::Config + platform::subscribe::Config {}
+impl<T: platform::allow_rw::Config + platform::subscribe::Config> Config for T {}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20009;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SEND: u32 = 1;
+    pub const RECEIVE: u32 = 2;
+    pub const CANCEL: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const RECEIVE: u32 = 0;
+    pub const SEND: u32 = 1;
+}
+
+// The first argument of the DONE upcall.
+const RECEIVED: u32 = 0;
+const SENT: u32 = 1;
@@ -0,0 +1,88 @@
+use crate::{allow_rw, subscribe, CtapEvent, CtapListener, DRIVER_NUM, PACKET_LEN};
+use core::cell::Cell;
+use libtock_platform::{share, AllowRw, ErrorCode, Subscribe, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type Ctap = super::Ctap<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ctap::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ctap::exists(), Ok(()));
+}
+
+#[test]
+fn send() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let mut packet = [0; PACKET_LEN];
+    packet[..4].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(Ctap::send(&packet), Ok(()));
+    assert_eq!(Ctap::send(&[1; PACKET_LEN]), Ok(()));
+    assert_eq!(driver.take_sent(), [packet, [1; PACKET_LEN]]);
+}
+
+#[test]
+fn receive() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    driver.add_received([5; PACKET_LEN]);
+    let mut packet = [0; PACKET_LEN];
+    assert_eq!(Ctap::receive(&mut packet), Ok(()));
+    assert_eq!(packet, [5; PACKET_LEN]);
+    assert!(!driver.is_receiving());
+}
+
+#[test]
+fn listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let event: Cell<Option<CtapEvent>> = Cell::new(None);
+    let listener = CtapListener(|e| event.set(Some(e)));
+    let mut packet = [0; PACKET_LEN];
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, { allow_rw::RECEIVE }>,
+            Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        assert_eq!(Ctap::allow_receive_buffer(allow_rw, &mut packet), Ok(()));
+        assert_eq!(Ctap::register_listener(&listener, subscribe), Ok(()));
+
+        assert_eq!(Ctap::start_receive(), Ok(()));
+        assert_eq!(Ctap::start_receive(), Err(ErrorCode::Busy));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        driver.add_received([9; PACKET_LEN]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(event.get(), Some(CtapEvent::Received));
+
+        assert_eq!(Ctap::start_receive(), Ok(()));
+        assert_eq!(Ctap::cancel(), Ok(()));
+        assert_eq!(Ctap::cancel(), Err(ErrorCode::Already));
+        driver.add_received([10; PACKET_LEN]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        Ctap::unregister_listener();
+        assert_eq!(Ctap::start_receive(), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+    assert_eq!(packet, [10; PACKET_LEN]);
+}
@@ -0,0 +1,61 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The digital to analog converter driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::dac::Dac;
+///
+/// // Output half of the reference voltage of a 10-bit DAC.
+/// Dac::set_value_checked(512, 10)?;
+/// ```
+pub struct Dac<S: Syscalls>(S);
+
+impl<S: Syscalls> Dac<S> {
+    /// Returns Ok() if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Sets the output to `value`.
+    pub fn set_value(value: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, SET_VALUE, value, 0).to_result()
+    }
+
+    /// Sets the output to `value`, after checking that it fits in a DAC with
+    /// `resolution_bits` bits. Returns `Err(ErrorCode::Invalid)` if it does
+    /// not. The driver does not report its resolution, so it has to come from
+    /// the board's documentation.
+    pub fn set_value_checked(value: u32, resolution_bits: u32) -> Result<(), ErrorCode> {
+        if value > max_value(resolution_bits) {
+            return Err(ErrorCode::Invalid);
+        }
+        Self::set_value(value)
+    }
+}
+
+/// Returns the largest value a DAC with `resolution_bits` bits converts,
+/// which outputs the reference voltage.
+pub fn max_value(resolution_bits: u32) -> u32 {
+    match resolution_bits {
+        0..=31 => (1 << resolution_bits) - 1,
+        _ => u32::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x6;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const SET_VALUE: u32 = 1;
@@ -0,0 +1,63 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Dac = super::Dac<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Dac::exists(), Err(ErrorCode::NoDevice));
+    assert_eq!(Dac::set_value(0), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Dac::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Dac::exists(), Ok(()));
+}
+
+#[test]
+fn max_value() {
+    assert_eq!(crate::max_value(0), 0);
+    assert_eq!(crate::max_value(10), 1023);
+    assert_eq!(crate::max_value(12), 4095);
+    assert_eq!(crate::max_value(32), u32::MAX);
+}
+
+#[test]
+fn set_value() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Dac::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Dac::set_value(0), Ok(()));
+    assert_eq!(driver.value(), Some(0));
+    assert_eq!(Dac::set_value(1023), Ok(()));
+    assert_eq!(driver.value(), Some(1023));
+    // The fake DAC has 10 bits, and rejects larger values itself.
+    assert_eq!(Dac::set_value(1024), Err(ErrorCode::Invalid));
+    assert_eq!(driver.value(), Some(1023));
+
+    driver.set_resolution_bits(12);
+    assert_eq!(Dac::set_value(1024), Ok(()));
+    assert_eq!(driver.value(), Some(1024));
+}
+
+#[test]
+fn set_value_checked() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Dac::new();
+    driver.set_resolution_bits(12);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Dac::set_value_checked(1023, 10), Ok(()));
+    assert_eq!(driver.value(), Some(1023));
+    // The value is checked without a system call.
+    assert_eq!(Dac::set_value_checked(1024, 10), Err(ErrorCode::Invalid));
+    assert_eq!(driver.value(), Some(1023));
+    assert_eq!(Dac::set_value_checked(4095, 12), Ok(()));
+    assert_eq!(driver.value(), Some(4095));
+}
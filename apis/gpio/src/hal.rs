@@ -0,0 +1,101 @@
+//! `embedded-hal` digital pin traits.
+
+use crate::{Flex, GpioState, InputPin, OutputPin, Pull};
+use embedded_hal::digital::{self, ErrorKind, ErrorType};
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The error type of the `embedded-hal` pin traits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GpioError(pub ErrorCode);
+
+impl From<ErrorCode> for GpioError {
+    fn from(error: ErrorCode) -> GpioError {
+        GpioError(error)
+    }
+}
+
+impl digital::Error for GpioError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<S: Syscalls> ErrorType for OutputPin<'_, S> {
+    type Error = GpioError;
+}
+
+impl<S: Syscalls> digital::OutputPin for OutputPin<'_, S> {
+    fn set_low(&mut self) -> Result<(), GpioError> {
+        Ok(self.clear()?)
+    }
+
+    fn set_high(&mut self) -> Result<(), GpioError> {
+        Ok(self.set()?)
+    }
+}
+
+impl<S: Syscalls> digital::StatefulOutputPin for OutputPin<'_, S> {
+    fn is_set_high(&mut self) -> Result<bool, GpioError> {
+        Ok(self.state.ok_or(ErrorCode::Invalid)? == GpioState::High)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, GpioError> {
+        Ok(self.state.ok_or(ErrorCode::Invalid)? == GpioState::Low)
+    }
+
+    fn toggle(&mut self) -> Result<(), GpioError> {
+        Ok(OutputPin::toggle(self)?)
+    }
+}
+
+impl<S: Syscalls, P: Pull> ErrorType for InputPin<'_, S, P> {
+    type Error = GpioError;
+}
+
+impl<S: Syscalls, P: Pull> digital::InputPin for InputPin<'_, S, P> {
+    fn is_high(&mut self) -> Result<bool, GpioError> {
+        Ok(self.read()? == GpioState::High)
+    }
+
+    fn is_low(&mut self) -> Result<bool, GpioError> {
+        Ok(self.read()? == GpioState::Low)
+    }
+}
+
+impl<S: Syscalls> ErrorType for Flex<'_, S> {
+    type Error = GpioError;
+}
+
+impl<S: Syscalls> digital::OutputPin for Flex<'_, S> {
+    fn set_low(&mut self) -> Result<(), GpioError> {
+        Ok(self.write(GpioState::Low)?)
+    }
+
+    fn set_high(&mut self) -> Result<(), GpioError> {
+        Ok(self.write(GpioState::High)?)
+    }
+}
+
+impl<S: Syscalls> digital::StatefulOutputPin for Flex<'_, S> {
+    fn is_set_high(&mut self) -> Result<bool, GpioError> {
+        Ok(self.output.ok_or(ErrorCode::Invalid)? == GpioState::High)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, GpioError> {
+        Ok(self.output.ok_or(ErrorCode::Invalid)? == GpioState::Low)
+    }
+
+    fn toggle(&mut self) -> Result<(), GpioError> {
+        Ok(Flex::toggle(self)?)
+    }
+}
+
+impl<S: Syscalls> digital::InputPin for Flex<'_, S> {
+    fn is_high(&mut self) -> Result<bool, GpioError> {
+        Ok(self.read()? == GpioState::High)
+    }
+
+    fn is_low(&mut self) -> Result<bool, GpioError> {
+        Ok(self.read()? == GpioState::Low)
+    }
+}
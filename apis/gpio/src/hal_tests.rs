@@ -0,0 +1,67 @@
+use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::{GpioError, GpioState, PullDown};
+
+type Gpio = crate::Gpio<fake::Syscalls>;
+
+#[test]
+fn output_pin() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let mut pin = Gpio::new_pin(0).unwrap();
+    let mut output = pin.make_output().unwrap();
+    // The level is unknown until the pin is written.
+    assert_eq!(output.is_set_low(), Err(GpioError(ErrorCode::Invalid)));
+    assert_eq!(output.set_low(), Ok(()));
+    assert_eq!(output.is_set_low(), Ok(true));
+    assert_eq!(output.set_high(), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output.is_set_high(), Ok(true));
+    assert_eq!(StatefulOutputPin::toggle(&mut output), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output.is_set_low(), Ok(true));
+    assert_eq!(output.set_high(), Ok(()));
+    assert_eq!(output.set_low(), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output.state(), Some(GpioState::Low));
+}
+
+#[test]
+fn input_pin() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin = Gpio::new_pin(0).unwrap();
+    let mut input = pin.make_input::<PullDown>().unwrap();
+    assert_eq!(input.is_low(), Ok(true));
+    assert_eq!(driver.set_value(0, true), Ok(()));
+    assert_eq!(input.is_high(), Ok(true));
+    assert_eq!(input.is_low(), Ok(false));
+}
+
+#[test]
+fn flex() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let mut pin = Gpio::new_pin(0).unwrap();
+    let mut flex = pin.make_flex().unwrap();
+    assert_eq!(flex.set_high(), Err(GpioError(ErrorCode::Invalid)));
+    assert_eq!(flex.is_set_high(), Err(GpioError(ErrorCode::Invalid)));
+    assert_eq!(driver.set_value(0, true), Ok(()));
+    assert_eq!(flex.is_high(), Ok(true));
+
+    assert_eq!(flex.set_as_output(GpioState::High), Ok(()));
+    assert_eq!(flex.is_set_high(), Ok(true));
+    assert_eq!(flex.set_low(), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(flex.is_low(), Ok(true));
+    assert_eq!(StatefulOutputPin::toggle(&mut flex), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+}
@@ -0,0 +1,449 @@
+use crate::{Error, Gpio, GpioState, InputPin, PinInterruptEdge, Pull, DRIVER_NUM};
+use core::cell::Cell;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::task::{Context, Poll, Waker};
+use libtock_platform::{
+    share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+/// How the edges of a pin are debounced.
+#[derive(Clone, Copy)]
+pub enum Debounce {
+    /// Every interrupt is reported.
+    None,
+    /// An edge is reported only if it changes the level, and at least `ticks`
+    /// have passed since the last reported edge. `now` returns the current
+    /// time, for example from the alarm driver. `ticks` should cover the
+    /// contact's bounce, typically a few milliseconds for a button.
+    ///
+    /// If the bounces leave the pin at a new level, that level is reported
+    /// before the next edge.
+    Interval { now: fn() -> u32, ticks: u32 },
+}
+
+/// Dispatches the GPIO driver's interrupts to per-pin callbacks and waits.
+///
+/// The driver has a single interrupt subscription, shared by all pins. This
+/// type is registered with that subscription, and tracks up to `N` callbacks
+/// and pending waits at once. Interrupts are enabled on a pin while it has a
+/// callback or a wait, and edges are filtered and debounced per callback.
+///
+/// Waits only complete while the dispatcher is registered.
+///
+/// # Example
+/// ```ignore
+/// use libtock::alarm::{Alarm, Convert, Milliseconds};
+/// use libtock::gpio::{Debounce, PinInterruptEdge, PinInterrupts, PullUp};
+///
+/// fn now() -> u32 {
+///     Alarm::get_ticks().map_or(0, |ticks| ticks.0)
+/// }
+///
+/// let interrupts = PinInterrupts::<4>::new();
+/// let pressed = |_pin, _state| { /* ... */ };
+/// let ticks = Milliseconds(20).to_ticks(Alarm::get_frequency()?).0;
+/// let debounce = Debounce::Interval { now, ticks };
+/// share::scope(|subscribe| {
+///     interrupts.register(subscribe)?;
+///     interrupts.add_callback(&button, PinInterruptEdge::Falling, debounce, &pressed)?;
+///     interrupts.wait_for_high(&other_pin, Debounce::None)?;
+/// });
+/// ```
+pub struct PinInterrupts<'a, S: Syscalls, const N: usize> {
+    slots: [Slot<'a>; N],
+    _syscalls: PhantomData<S>,
+}
+
+impl<'a, S: Syscalls, const N: usize> PinInterrupts<'a, S, N> {
+    pub fn new() -> Self {
+        PinInterrupts {
+            slots: [Slot::FREE; N],
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Registers the dispatcher with the GPIO driver's interrupt subscription.
+    /// This replaces any `GpioInterruptListener`.
+    pub fn register<'share>(
+        &'share self,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, self)
+    }
+
+    /// Calls `callback` with the pin number and new level on each `edge` of
+    /// `pin`. Fails with `Error::Full` if all slots are in use.
+    pub fn add_callback<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        edge: PinInterruptEdge,
+        debounce: Debounce,
+        callback: &'a dyn Fn(u32, GpioState),
+    ) -> Result<(), Error> {
+        self.claim(pin.pin.pin_number, edge, debounce, Some(callback))?;
+        Ok(())
+    }
+
+    /// Removes the callbacks of `pin`. Fails with `Error::Invalid` if the pin
+    /// has no callbacks.
+    pub fn remove_callbacks<P: Pull>(&self, pin: &InputPin<'_, S, P>) -> Result<(), Error> {
+        let mut removed = false;
+        for slot in self.slots.iter() {
+            if slot.pin.get() == Some(pin.pin.pin_number) && slot.callback.get().is_some() {
+                self.release(slot);
+                removed = true;
+            }
+        }
+        if removed {
+            Ok(())
+        } else {
+            Err(Error::Invalid)
+        }
+    }
+
+    /// Blocks until an `edge` of `pin`, and returns the pin's new level.
+    pub fn wait_for_edge<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        edge: PinInterruptEdge,
+        debounce: Debounce,
+    ) -> Result<GpioState, Error> {
+        self.wait(pin.pin.pin_number, edge, None, debounce)
+    }
+
+    /// Blocks until `pin` is high. Returns immediately if it already is.
+    pub fn wait_for_high<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        debounce: Debounce,
+    ) -> Result<(), Error> {
+        let level = Some(GpioState::High);
+        self.wait(
+            pin.pin.pin_number,
+            PinInterruptEdge::Rising,
+            level,
+            debounce,
+        )?;
+        Ok(())
+    }
+
+    /// Blocks until `pin` is low. Returns immediately if it already is.
+    pub fn wait_for_low<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        debounce: Debounce,
+    ) -> Result<(), Error> {
+        let level = Some(GpioState::Low);
+        self.wait(
+            pin.pin.pin_number,
+            PinInterruptEdge::Falling,
+            level,
+            debounce,
+        )?;
+        Ok(())
+    }
+
+    /// Returns a future that resolves to the pin's new level on an `edge` of
+    /// `pin`.
+    ///
+    /// The future stores its waker and completes from the dispatcher's upcall,
+    /// so the executor must yield to the kernel while it has no work.
+    pub fn wait_for_edge_async<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        edge: PinInterruptEdge,
+        debounce: Debounce,
+    ) -> WaitFor<'_, 'a, S, N> {
+        WaitFor::new(self, pin.pin.pin_number, edge, None, debounce)
+    }
+
+    /// Returns a future that resolves once `pin` is high.
+    pub fn wait_for_high_async<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        debounce: Debounce,
+    ) -> WaitFor<'_, 'a, S, N> {
+        let level = Some(GpioState::High);
+        WaitFor::new(
+            self,
+            pin.pin.pin_number,
+            PinInterruptEdge::Rising,
+            level,
+            debounce,
+        )
+    }
+
+    /// Returns a future that resolves once `pin` is low.
+    pub fn wait_for_low_async<P: Pull>(
+        &self,
+        pin: &InputPin<'_, S, P>,
+        debounce: Debounce,
+    ) -> WaitFor<'_, 'a, S, N> {
+        let level = Some(GpioState::Low);
+        WaitFor::new(
+            self,
+            pin.pin.pin_number,
+            PinInterruptEdge::Falling,
+            level,
+            debounce,
+        )
+    }
+}
+
+impl<'a, S: Syscalls, const N: usize> Default for PinInterrupts<'a, S, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S: Syscalls, const N: usize> Upcall<OneId<DRIVER_NUM, 0>> for PinInterrupts<'a, S, N> {
+    fn upcall(&self, pin: u32, value: u32, _arg2: u32) {
+        let state = GpioState::from(value);
+        for slot in self.slots.iter() {
+            if slot.pin.get() != Some(pin) {
+                continue;
+            }
+            for &state in self.debounce(slot, state).iter().flatten() {
+                if !edge_matches(slot.edge.get(), state) {
+                    continue;
+                }
+                slot.edges.set(slot.edges.get().wrapping_add(1));
+                slot.state.set(state);
+                if let Some(callback) = slot.callback.get() {
+                    callback(pin, state);
+                }
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// A future that waits for an edge or a level on a pin. Returned by
+/// [`PinInterrupts::wait_for_edge_async`] and related methods.
+pub struct WaitFor<'i, 'a, S: Syscalls, const N: usize> {
+    interrupts: &'i PinInterrupts<'a, S, N>,
+    pin: u32,
+    edge: PinInterruptEdge,
+    level: Option<GpioState>,
+    debounce: Debounce,
+    // The slot claimed on the first poll, and its edge count at the time.
+    claimed: Option<(&'i Slot<'a>, u32)>,
+}
+
+impl<'i, 'a, S: Syscalls, const N: usize> WaitFor<'i, 'a, S, N> {
+    fn new(
+        interrupts: &'i PinInterrupts<'a, S, N>,
+        pin: u32,
+        edge: PinInterruptEdge,
+        level: Option<GpioState>,
+        debounce: Debounce,
+    ) -> Self {
+        WaitFor {
+            interrupts,
+            pin,
+            edge,
+            level,
+            debounce,
+            claimed: None,
+        }
+    }
+
+    fn finish(&mut self, result: Result<GpioState, Error>) -> Poll<Result<GpioState, Error>> {
+        if let Some((slot, _)) = self.claimed.take() {
+            self.interrupts.release(slot);
+        }
+        Poll::Ready(result)
+    }
+}
+
+impl<'i, 'a, S: Syscalls, const N: usize> Future for WaitFor<'i, 'a, S, N> {
+    type Output = Result<GpioState, Error>;
+
+    fn poll(self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (slot, start) = match this.claimed {
+            Some(claimed) => claimed,
+            None => {
+                let interrupts = this.interrupts;
+                let slot = match interrupts.claim(this.pin, this.edge, this.debounce, None) {
+                    Ok(slot) => slot,
+                    Err(error) => return Poll::Ready(Err(error)),
+                };
+                this.claimed = Some((slot, slot.edges.get()));
+                if let Some(level) = this.level {
+                    match Gpio::<S>::read(this.pin) {
+                        Ok(state) if state == level => return this.finish(Ok(level)),
+                        Ok(_) => {}
+                        Err(error) => return this.finish(Err(error.into())),
+                    }
+                }
+                (slot, slot.edges.get())
+            }
+        };
+        if slot.edges.get() != start {
+            return this.finish(Ok(slot.state.get()));
+        }
+        slot.waker.set(Some(cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+impl<S: Syscalls, const N: usize> Drop for WaitFor<'_, '_, S, N> {
+    fn drop(&mut self) {
+        if let Some((slot, _)) = self.claimed.take() {
+            self.interrupts.release(slot);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+// A callback or wait on one pin.
+struct Slot<'a> {
+    // The pin, or `None` if the slot is free.
+    pin: Cell<Option<u32>>,
+    edge: Cell<PinInterruptEdge>,
+    debounce: Cell<Debounce>,
+    callback: Cell<Option<&'a dyn Fn(u32, GpioState)>>,
+    // The debounced level of the pin, and when it last changed.
+    level: Cell<Option<GpioState>>,
+    changed_at: Cell<Option<u32>>,
+    // The level after the last interrupt, including rejected bounces.
+    raw: Cell<Option<GpioState>>,
+    // The number of edges reported, and the level after the last one.
+    edges: Cell<u32>,
+    state: Cell<GpioState>,
+    waker: Cell<Option<Waker>>,
+}
+
+impl<'a> Slot<'a> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const FREE: Slot<'a> = Slot {
+        pin: Cell::new(None),
+        edge: Cell::new(PinInterruptEdge::Either),
+        debounce: Cell::new(Debounce::None),
+        callback: Cell::new(None),
+        level: Cell::new(None),
+        changed_at: Cell::new(None),
+        raw: Cell::new(None),
+        edges: Cell::new(0),
+        state: Cell::new(GpioState::Low),
+        waker: Cell::new(None),
+    };
+}
+
+impl<'a, S: Syscalls, const N: usize> PinInterrupts<'a, S, N> {
+    fn is_listening(&self, pin: u32) -> bool {
+        self.slots.iter().any(|slot| slot.pin.get() == Some(pin))
+    }
+
+    fn claim(
+        &self,
+        pin: u32,
+        edge: PinInterruptEdge,
+        debounce: Debounce,
+        callback: Option<&'a dyn Fn(u32, GpioState)>,
+    ) -> Result<&Slot<'a>, Error> {
+        let slot = self
+            .slots
+            .iter()
+            .find(|slot| slot.pin.get().is_none())
+            .ok_or(Error::Full)?;
+        // Edges are filtered per slot, so the kernel reports both.
+        if !self.is_listening(pin) {
+            Gpio::<S>::enable_interrupts(pin, PinInterruptEdge::Either)?;
+        }
+        slot.pin.set(Some(pin));
+        slot.edge.set(edge);
+        slot.debounce.set(debounce);
+        slot.callback.set(callback);
+        slot.level.set(Gpio::<S>::read(pin).ok());
+        slot.raw.set(slot.level.get());
+        slot.changed_at.set(None);
+        Ok(slot)
+    }
+
+    fn release(&self, slot: &Slot<'a>) {
+        slot.callback.set(None);
+        slot.waker.set(None);
+        if let Some(pin) = slot.pin.take() {
+            if !self.is_listening(pin) {
+                let _ = Gpio::<S>::disable_interrupts(pin);
+            }
+        }
+    }
+
+    // Blocking implementation of the wait_for_* methods. If `level` is set,
+    // returns immediately if the pin is already at that level.
+    fn wait(
+        &self,
+        pin: u32,
+        edge: PinInterruptEdge,
+        level: Option<GpioState>,
+        debounce: Debounce,
+    ) -> Result<GpioState, Error> {
+        let slot = self.claim(pin, edge, debounce, None)?;
+        let start = slot.edges.get();
+        if let Some(level) = level {
+            match Gpio::<S>::read(pin) {
+                Ok(state) if state == level => {
+                    self.release(slot);
+                    return Ok(level);
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    self.release(slot);
+                    return Err(error.into());
+                }
+            }
+        }
+        while slot.edges.get() == start {
+            S::yield_wait();
+        }
+        let state = slot.state.get();
+        self.release(slot);
+        Ok(state)
+    }
+
+    // Applies the slot's debouncing to an interrupt. Returns the levels to
+    // report, oldest first.
+    fn debounce(&self, slot: &Slot<'a>, state: GpioState) -> [Option<GpioState>; 2] {
+        let (now, ticks) = match slot.debounce.get() {
+            Debounce::None => return [None, Some(state)],
+            Debounce::Interval { now, ticks } => (now, ticks),
+        };
+        let previous = slot.raw.replace(Some(state));
+        let time = now();
+        if let Some(changed_at) = slot.changed_at.get() {
+            if time.wrapping_sub(changed_at) < ticks {
+                return [None, None];
+            }
+        }
+        // A bounce may have left the pin at a level that was never reported.
+        // Report it first, so that the reported levels still alternate.
+        let mut missed = None;
+        if previous.is_some() && previous != slot.level.get() {
+            missed = previous;
+            slot.level.set(previous);
+        }
+        if slot.level.get() == Some(state) {
+            return [missed, None];
+        }
+        slot.level.set(Some(state));
+        slot.changed_at.set(Some(time));
+        [missed, Some(state)]
+    }
+}
+
+fn edge_matches(edge: PinInterruptEdge, state: GpioState) -> bool {
+    match edge {
+        PinInterruptEdge::Either => true,
+        PinInterruptEdge::Rising => state == GpioState::High,
+        PinInterruptEdge::Falling => state == GpioState::Low,
+    }
+}
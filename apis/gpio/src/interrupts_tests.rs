@@ -0,0 +1,335 @@
+use core::cell::{Cell, RefCell};
+use core::future::Future;
+use core::task::{Context, Poll};
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake::{self, InterruptEdge};
+
+use crate::{Debounce, Error, GpioState, PinInterruptEdge, PullNone};
+
+extern crate std;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Wake, Waker};
+use std::vec::Vec;
+
+type Gpio = crate::Gpio<fake::Syscalls>;
+type PinInterrupts<'a, const N: usize> = crate::PinInterrupts<'a, fake::Syscalls, N>;
+
+// Counts how many times it was woken.
+#[derive(Default)]
+struct WakeCount(AtomicUsize);
+
+impl Wake for WakeCount {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn callbacks() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin_0 = Gpio::new_pin(0).unwrap();
+    let input_0 = pin_0.make_input::<PullNone>().unwrap();
+    let pin_2 = Gpio::new_pin(2).unwrap();
+    let input_2 = pin_2.make_input::<PullNone>().unwrap();
+
+    let rising_0 = Cell::new(0);
+    let either_2: Cell<Option<(u32, GpioState)>> = Cell::new(None);
+    let on_rising_0 = |pin, state| {
+        assert_eq!((pin, state), (0, GpioState::High));
+        rising_0.set(rising_0.get() + 1);
+    };
+    let on_either_2 = |pin, state| either_2.set(Some((pin, state)));
+
+    let interrupts = PinInterrupts::<3>::new();
+    share::scope(|subscribe| {
+        assert_eq!(interrupts.register(subscribe), Ok(()));
+        assert_eq!(
+            interrupts.add_callback(
+                &input_0,
+                PinInterruptEdge::Rising,
+                Debounce::None,
+                &on_rising_0
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            interrupts.add_callback(
+                &input_2,
+                PinInterruptEdge::Either,
+                Debounce::None,
+                &on_either_2
+            ),
+            Ok(())
+        );
+        // Edges are filtered by the dispatcher, not the kernel.
+        assert_eq!(
+            driver.get_gpio_state(0).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Either)
+        );
+
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(rising_0.get(), 1);
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(rising_0.get(), 1);
+        assert_eq!(either_2.get(), None);
+
+        assert_eq!(driver.set_value(2, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(either_2.get(), Some((2, GpioState::High)));
+        assert_eq!(driver.set_value(2, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(either_2.get(), Some((2, GpioState::Low)));
+        assert_eq!(rising_0.get(), 1);
+
+        assert_eq!(interrupts.remove_callbacks(&input_0), Ok(()));
+        assert_eq!(interrupts.remove_callbacks(&input_0), Err(Error::Invalid));
+        assert_eq!(driver.get_gpio_state(0).unwrap().interrupt_enabled, None);
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(rising_0.get(), 1);
+    });
+}
+
+#[test]
+fn full() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    driver.set_missing_gpio(1);
+    kernel.add_driver(&driver);
+
+    let pin_0 = Gpio::new_pin(0).unwrap();
+    let input_0 = pin_0.make_input::<PullNone>().unwrap();
+    let callback = |_, _| {};
+
+    let interrupts = PinInterrupts::<1>::new();
+    assert_eq!(
+        interrupts.add_callback(
+            &input_0,
+            PinInterruptEdge::Either,
+            Debounce::None,
+            &callback
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        interrupts.add_callback(
+            &input_0,
+            PinInterruptEdge::Either,
+            Debounce::None,
+            &callback
+        ),
+        Err(Error::Full)
+    );
+    assert_eq!(
+        interrupts.wait_for_high(&input_0, Debounce::None),
+        Err(Error::Full)
+    );
+    assert_eq!(Error::from(ErrorCode::NoDevice), Error::Failed);
+}
+
+std::thread_local! {
+    static NOW: Cell<u32> = Cell::new(0);
+}
+
+fn now() -> u32 {
+    NOW.with(|now| now.get())
+}
+
+fn advance(ticks: u32) {
+    NOW.with(|now| now.set(now.get() + ticks));
+}
+
+#[test]
+fn debounce_interval() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin = Gpio::new_pin(0).unwrap();
+    let input = pin.make_input::<PullNone>().unwrap();
+    let last: Cell<Option<GpioState>> = Cell::new(None);
+    let callback = |_, state| last.set(Some(state));
+
+    let interrupts = PinInterrupts::<1>::new();
+    let debounce = Debounce::Interval { now, ticks: 10 };
+    share::scope(|subscribe| {
+        assert_eq!(interrupts.register(subscribe), Ok(()));
+        assert_eq!(
+            interrupts.add_callback(&input, PinInterruptEdge::Either, debounce, &callback),
+            Ok(())
+        );
+
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(last.take(), Some(GpioState::High));
+
+        // Too soon after the last edge.
+        advance(5);
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(last.take(), None);
+
+        // The bounce settles back at the reported level.
+        advance(2);
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(last.take(), None);
+
+        advance(10);
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(last.take(), Some(GpioState::Low));
+    });
+}
+
+// A glitch that is released within the interval must not hide the next press.
+#[test]
+fn debounce_glitch_then_press() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+    assert_eq!(driver.set_value(0, true), Ok(()));
+
+    let pin = Gpio::new_pin(0).unwrap();
+    let input = pin.make_input::<PullNone>().unwrap();
+    let states = RefCell::new(Vec::new());
+    let callback = |_, state| states.borrow_mut().push(state);
+
+    let interrupts = PinInterrupts::<1>::new();
+    let debounce = Debounce::Interval { now, ticks: 10 };
+    share::scope(|subscribe| {
+        assert_eq!(interrupts.register(subscribe), Ok(()));
+        assert_eq!(
+            interrupts.add_callback(&input, PinInterruptEdge::Either, debounce, &callback),
+            Ok(())
+        );
+
+        // The glitch: the release is too soon to be reported.
+        advance(100);
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        advance(2);
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+        assert_eq!(states.take(), [GpioState::Low]);
+
+        // The press reports the missed release first.
+        advance(100);
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+        assert_eq!(states.take(), [GpioState::High, GpioState::Low]);
+
+        advance(100);
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+        assert_eq!(states.take(), [GpioState::High]);
+    });
+}
+
+#[test]
+fn wait_blocking() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin = Gpio::new_pin(0).unwrap();
+    let input = pin.make_input::<PullNone>().unwrap();
+    let callback = |_, _| {};
+    let interrupts = PinInterrupts::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(interrupts.register(subscribe), Ok(()));
+
+        // The pin is already low, so this does not yield.
+        assert_eq!(interrupts.wait_for_low(&input, Debounce::None), Ok(()));
+        assert_eq!(driver.get_gpio_state(0).unwrap().interrupt_enabled, None);
+
+        // The callback keeps interrupts enabled, so edges are queued before the
+        // waits start. The fake kernel's yield-wait then delivers them.
+        assert_eq!(
+            interrupts.add_callback(&input, PinInterruptEdge::Either, Debounce::None, &callback),
+            Ok(())
+        );
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(
+            interrupts.wait_for_edge(&input, PinInterruptEdge::Falling, Debounce::None),
+            Ok(GpioState::Low)
+        );
+        assert_eq!(
+            interrupts.wait_for_edge(&input, PinInterruptEdge::Either, Debounce::None),
+            Ok(GpioState::High)
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        // The pin is already high.
+        assert_eq!(interrupts.wait_for_high(&input, Debounce::None), Ok(()));
+        assert_eq!(
+            driver.get_gpio_state(0).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Either)
+        );
+    });
+}
+
+#[test]
+fn wait_async() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let pin = Gpio::new_pin(0).unwrap();
+    let input = pin.make_input::<PullNone>().unwrap();
+    let wake_count = Arc::new(WakeCount::default());
+    let waker = Waker::from(wake_count.clone());
+    let mut context = Context::from_waker(&waker);
+
+    let interrupts = PinInterrupts::<2>::new();
+    share::scope(|subscribe| {
+        assert_eq!(interrupts.register(subscribe), Ok(()));
+
+        let mut future =
+            interrupts.wait_for_edge_async(&input, PinInterruptEdge::Falling, Debounce::None);
+        let mut future = core::pin::Pin::new(&mut future);
+        assert_eq!(future.as_mut().poll(&mut context), Poll::Pending);
+        assert_eq!(
+            driver.get_gpio_state(0).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Either)
+        );
+
+        // A rising edge does not complete the future.
+        assert_eq!(driver.set_value(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(wake_count.0.load(Ordering::Relaxed), 0);
+        assert_eq!(future.as_mut().poll(&mut context), Poll::Pending);
+
+        assert_eq!(driver.set_value(0, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(wake_count.0.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            future.as_mut().poll(&mut context),
+            Poll::Ready(Ok(GpioState::Low))
+        );
+        assert_eq!(driver.get_gpio_state(0).unwrap().interrupt_enabled, None);
+
+        // The pin is already low.
+        let mut future = interrupts.wait_for_low_async(&input, Debounce::None);
+        assert_eq!(
+            core::pin::Pin::new(&mut future).poll(&mut context),
+            Poll::Ready(Ok(GpioState::Low))
+        );
+
+        // Dropping a pending future releases its slot.
+        let mut future = interrupts.wait_for_high_async(&input, Debounce::None);
+        assert_eq!(
+            core::pin::Pin::new(&mut future).poll(&mut context),
+            Poll::Pending
+        );
+        drop(future);
+        assert_eq!(driver.get_gpio_state(0).unwrap().interrupt_enabled, None);
+    });
+}
@@ -6,15 +6,23 @@ use libtock_platform::{
     share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
+mod hal;
+mod interrupts;
+mod registry;
+pub use hal::GpioError;
+pub use interrupts::{Debounce, PinInterrupts, WaitFor};
+pub use registry::PinRegistry;
+
 /// The Gpio driver
 ///
 /// # Example
 /// ```ignore
-/// use libtock::Gpios;
-///
-/// // Turn on led 0
-/// let pin = Gpios::get_pin(0)?;
+/// use libtock::gpio::PinRegistry;
 ///
+/// // Take pin 0 and drive it high
+/// let pins = PinRegistry::take().unwrap();
+/// let mut pin = pins.take_pin(0)?;
+/// pin.make_output()?.set()?;
 /// ```
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -23,15 +31,31 @@ pub enum GpioState {
     High = 1,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum PinInterruptEdge {
     Either = 0,
     Rising = 1,
     Falling = 2,
 }
 
+/// Errors of [`PinInterrupts`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Error {
+    /// An argument was invalid, such as a pin the kernel does not have.
     Invalid,
+    /// The kernel failed to configure the pin.
     Failed,
+    /// All of the dispatcher's slots are in use.
+    Full,
+}
+
+impl From<ErrorCode> for Error {
+    fn from(error: ErrorCode) -> Error {
+        match error {
+            ErrorCode::Invalid => Error::Invalid,
+            _ => Error::Failed,
+        }
+    }
 }
 
 pub trait Pull {
@@ -65,7 +89,15 @@ impl<S: Syscalls> Gpio<S> {
         S::command(DRIVER_NUM, GPIO_COUNT, 0, 0).to_result()
     }
 
+    /// Disables pin `pin` and returns it. Nothing stops a pin from being
+    /// returned more than once, so prefer [`PinRegistry::take_pin`].
+    #[deprecated(note = "use `PinRegistry::take_pin`, which hands out each pin once")]
     pub fn get_pin(pin: u32) -> Result<Pin<S>, ErrorCode> {
+        Self::new_pin(pin)
+    }
+
+    // Pins are handed out by `PinRegistry`, so that each pin has one owner.
+    pub(crate) fn new_pin(pin: u32) -> Result<Pin<S>, ErrorCode> {
         Self::disable(pin)?;
         Ok(Pin {
             pin_number: pin,
@@ -110,6 +142,17 @@ impl<F: Fn(u32, GpioState)> Upcall<OneId<DRIVER_NUM, 0>> for GpioInterruptListen
     }
 }
 
+impl core::ops::Not for GpioState {
+    type Output = GpioState;
+
+    fn not(self) -> GpioState {
+        match self {
+            GpioState::Low => GpioState::High,
+            GpioState::High => GpioState::Low,
+        }
+    }
+}
+
 impl From<u32> for GpioState {
     fn from(original: u32) -> GpioState {
         match original {
@@ -119,15 +162,35 @@ impl From<u32> for GpioState {
     }
 }
 
+/// A GPIO pin, taken from a [`PinRegistry`]. The pin is disabled until it is
+/// configured as an output, an input, or a [`Flex`] pin.
 pub struct Pin<S: Syscalls> {
     pin_number: u32,
     _syscalls: PhantomData<S>,
 }
 
 impl<S: Syscalls> Pin<S> {
+    pub fn pin_number(&self) -> u32 {
+        self.pin_number
+    }
+
+    /// Configures the pin as an output, leaving its level to the hardware.
     pub fn make_output(&mut self) -> Result<OutputPin<S>, ErrorCode> {
         Gpio::<S>::enable_gpio_output(self.pin_number)?;
-        Ok(OutputPin { pin: self })
+        Ok(OutputPin {
+            pin: self,
+            state: None,
+        })
+    }
+
+    /// Configures the pin as an output, driven to `state`.
+    pub fn make_output_with(&mut self, state: GpioState) -> Result<OutputPin<S>, ErrorCode> {
+        Gpio::<S>::enable_gpio_output(self.pin_number)?;
+        Gpio::<S>::write(self.pin_number, state)?;
+        Ok(OutputPin {
+            pin: self,
+            state: Some(state),
+        })
     }
 
     pub fn make_input<P: Pull>(&self) -> Result<InputPin<S, P>, ErrorCode> {
@@ -137,21 +200,48 @@ impl<S: Syscalls> Pin<S> {
             _pull: PhantomData,
         })
     }
+
+    /// Makes the pin a [`Flex`] pin, which starts out as an input without a
+    /// pull resistor.
+    pub fn make_flex(&mut self) -> Result<Flex<S>, ErrorCode> {
+        Gpio::<S>::enable_gpio_input(self.pin_number, PullNone::MODE)?;
+        Ok(Flex {
+            pin: self,
+            output: None,
+        })
+    }
 }
 
 pub struct OutputPin<'a, S: Syscalls> {
     pin: &'a Pin<S>,
+    // The kernel cannot read back the level of an output, so the last level
+    // written is tracked here. `None` until the level is known.
+    state: Option<GpioState>,
 }
 
 impl<'a, S: Syscalls> OutputPin<'a, S> {
     pub fn toggle(&mut self) -> Result<(), ErrorCode> {
-        Gpio::<S>::toggle(self.pin.pin_number)
+        Gpio::<S>::toggle(self.pin.pin_number)?;
+        self.state = self.state.map(|state| !state);
+        Ok(())
     }
     pub fn set(&mut self) -> Result<(), ErrorCode> {
-        Gpio::<S>::write(self.pin.pin_number, GpioState::High)
+        self.write(GpioState::High)
     }
     pub fn clear(&mut self) -> Result<(), ErrorCode> {
-        Gpio::<S>::write(self.pin.pin_number, GpioState::Low)
+        self.write(GpioState::Low)
+    }
+
+    pub fn write(&mut self, state: GpioState) -> Result<(), ErrorCode> {
+        Gpio::<S>::write(self.pin.pin_number, state)?;
+        self.state = Some(state);
+        Ok(())
+    }
+
+    /// The level the pin was last driven to, or `None` if it was made an
+    /// output with `make_output` and has not been written since.
+    pub fn state(&self) -> Option<GpioState> {
+        self.state
     }
 }
 
@@ -174,6 +264,70 @@ impl<'a, S: Syscalls, P: Pull> InputPin<'a, S, P> {
     }
 }
 
+/// A pin that can switch between input and output without giving up the
+/// [`Pin`].
+pub struct Flex<'a, S: Syscalls> {
+    pin: &'a Pin<S>,
+    // The level last driven, or `None` while the pin is an input.
+    output: Option<GpioState>,
+}
+
+impl<'a, S: Syscalls> Flex<'a, S> {
+    /// Configures the pin as an output, driven to `state`.
+    pub fn set_as_output(&mut self, state: GpioState) -> Result<(), ErrorCode> {
+        Gpio::<S>::enable_gpio_output(self.pin.pin_number)?;
+        Gpio::<S>::write(self.pin.pin_number, state)?;
+        self.output = Some(state);
+        Ok(())
+    }
+
+    /// Configures the pin as an input with the pull resistor `P`.
+    pub fn set_as_input<P: Pull>(&mut self) -> Result<(), ErrorCode> {
+        Gpio::<S>::enable_gpio_input(self.pin.pin_number, P::MODE)?;
+        self.output = None;
+        Ok(())
+    }
+
+    pub fn is_output(&self) -> bool {
+        self.output.is_some()
+    }
+
+    /// Drives the pin to `state`. Fails with `ErrorCode::Invalid` if the pin is
+    /// an input.
+    pub fn write(&mut self, state: GpioState) -> Result<(), ErrorCode> {
+        if self.output.is_none() {
+            return Err(ErrorCode::Invalid);
+        }
+        Gpio::<S>::write(self.pin.pin_number, state)?;
+        self.output = Some(state);
+        Ok(())
+    }
+
+    /// Toggles the pin. Fails with `ErrorCode::Invalid` if the pin is an
+    /// input.
+    pub fn toggle(&mut self) -> Result<(), ErrorCode> {
+        let state = self.output.ok_or(ErrorCode::Invalid)?;
+        Gpio::<S>::toggle(self.pin.pin_number)?;
+        self.output = Some(!state);
+        Ok(())
+    }
+
+    /// Reads the pin. If the pin is an output, returns the level it was last
+    /// driven to.
+    pub fn read(&self) -> Result<GpioState, ErrorCode> {
+        match self.output {
+            Some(state) => Ok(state),
+            None => Gpio::<S>::read(self.pin.pin_number),
+        }
+    }
+}
+
+impl<S: Syscalls> Drop for Flex<'_, S> {
+    fn drop(&mut self) {
+        let _ = Gpio::<S>::disable(self.pin.pin_number);
+    }
+}
+
 impl<S: Syscalls> Drop for OutputPin<'_, S> {
     fn drop(&mut self) {
         let _ = Gpio::<S>::disable(self.pin.pin_number);
@@ -229,6 +383,12 @@ impl<S: Syscalls> Gpio<S> {
     }
 }
 
+#[cfg(test)]
+mod hal_tests;
+#[cfg(test)]
+mod interrupts_tests;
+#[cfg(test)]
+mod registry_tests;
 #[cfg(test)]
 mod tests;
 
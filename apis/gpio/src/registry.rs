@@ -0,0 +1,84 @@
+use crate::{Gpio, Pin};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// Hands out each GPIO pin at most once, so that every pin has a single owner.
+///
+/// There is a single registry, which an app takes at startup with
+/// [`PinRegistry::take`] and uses to pass pins to the code that uses them.
+/// Pins are never returned to the registry. Pin numbers 64 and above are not
+/// supported.
+pub struct PinRegistry<S: Syscalls> {
+    // Bit n is set once pin n has been taken.
+    taken: Cell<u64>,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> PinRegistry<S> {
+    /// Returns the registry the first time it is called, and `None` after
+    /// that.
+    pub fn take() -> Option<Self> {
+        if mark_taken() {
+            return None;
+        }
+        // Safety: this is the only registry `take` returns.
+        Some(unsafe { Self::steal() })
+    }
+
+    /// Returns a new registry, whether or not the registry was already taken.
+    ///
+    /// # Safety
+    /// Every registry hands out pins independently, so the caller must make
+    /// sure no pin is taken from more than one registry.
+    pub unsafe fn steal() -> Self {
+        PinRegistry {
+            taken: Cell::new(0),
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Takes pin `pin`, and disables it. Fails with `ErrorCode::Busy` if the
+    /// pin was already taken.
+    pub fn take_pin(&self, pin: u32) -> Result<Pin<S>, ErrorCode> {
+        let mask = 1u64.checked_shl(pin).ok_or(ErrorCode::Invalid)?;
+        if self.taken.get() & mask != 0 {
+            return Err(ErrorCode::Busy);
+        }
+        let pin = Gpio::<S>::new_pin(pin)?;
+        self.taken.set(self.taken.get() | mask);
+        Ok(pin)
+    }
+
+    /// Returns `true` if pin `pin` has been taken.
+    pub fn is_taken(&self, pin: u32) -> bool {
+        match 1u64.checked_shl(pin) {
+            Some(mask) => self.taken.get() & mask != 0,
+            None => false,
+        }
+    }
+}
+
+// Marks the registry as taken, and returns whether it already was.
+#[cfg(target_has_atomic = "8")]
+fn mark_taken() -> bool {
+    use core::sync::atomic::{AtomicBool, Ordering};
+    static TAKEN: AtomicBool = AtomicBool::new(false);
+    // Tock processes are single-threaded, so a load and a store cannot race.
+    // Unlike a swap, they do not need atomic read-modify-write instructions.
+    let taken = TAKEN.load(Ordering::Relaxed);
+    TAKEN.store(true, Ordering::Relaxed);
+    taken
+}
+
+#[cfg(not(target_has_atomic = "8"))]
+fn mark_taken() -> bool {
+    static mut TAKEN: bool = false;
+    // Safety: the process has a single thread, so nothing else can access
+    // TAKEN at the same time.
+    unsafe {
+        let taken = TAKEN;
+        TAKEN = true;
+        taken
+    }
+}
@@ -0,0 +1,37 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake::{self, GpioMode};
+
+type PinRegistry = crate::PinRegistry<fake::Syscalls>;
+
+#[test]
+fn take() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    driver.set_missing_gpio(1);
+    kernel.add_driver(&driver);
+
+    // Safety: no other registry hands out this driver's pins.
+    let pins = unsafe { PinRegistry::steal() };
+    assert_eq!(pins.take_pin(1).err(), Some(ErrorCode::NoDevice));
+    assert_eq!(pins.take_pin(11).err(), Some(ErrorCode::Invalid));
+    assert_eq!(pins.take_pin(64).err(), Some(ErrorCode::Invalid));
+    assert!(!pins.is_taken(1));
+
+    {
+        let pin = pins.take_pin(0).unwrap();
+        assert_eq!(pin.pin_number(), 0);
+        assert_eq!(driver.get_gpio_state(0).unwrap().mode, GpioMode::Disable);
+        assert!(pins.is_taken(0));
+        assert_eq!(pins.take_pin(0).err(), Some(ErrorCode::Busy));
+    }
+
+    // Pins stay taken after they are dropped.
+    assert_eq!(pins.take_pin(0).err(), Some(ErrorCode::Busy));
+    assert!(pins.take_pin(2).is_ok());
+}
+
+#[test]
+fn singleton() {
+    assert!(PinRegistry::take().is_some());
+    assert!(PinRegistry::take().is_none());
+}
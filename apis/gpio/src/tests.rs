@@ -23,6 +23,7 @@ fn num_gpio() {
 
 // Tests the OutputPin implementation.
 #[test]
+#[allow(deprecated)]
 fn output() {
     let kernel = fake::Kernel::new();
     let driver = fake::Gpio::<10>::new();
@@ -57,6 +58,7 @@ fn output() {
 
 // Tests the InputPin implementation
 #[test]
+#[allow(deprecated)]
 fn input() {
     let kernel = fake::Kernel::new();
     let driver = fake::Gpio::<10>::new();
@@ -105,6 +107,7 @@ fn input() {
 
 // Tests the pin interrupts implementation
 #[test]
+#[allow(deprecated)]
 fn interrupts() {
     let kernel = fake::Kernel::new();
     let driver = fake::Gpio::<10>::new();
@@ -294,3 +297,63 @@ fn subscribe() {
     assert_eq!(driver.set_value(0, false), Ok(()));
     assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
 }
+
+// Tests the Flex implementation
+#[test]
+fn flex() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let mut pin = Gpio::new_pin(0).unwrap();
+    let mut flex = pin.make_flex().unwrap();
+    assert_eq!(
+        driver.get_gpio_state(0).unwrap().mode,
+        GpioMode::Input(PullMode::PullNone)
+    );
+    assert!(!flex.is_output());
+    assert_eq!(flex.write(GpioState::High), Err(ErrorCode::Invalid));
+    assert_eq!(flex.toggle(), Err(ErrorCode::Invalid));
+    assert_eq!(driver.set_value(0, true), Ok(()));
+    assert_eq!(flex.read(), Ok(GpioState::High));
+
+    assert_eq!(flex.set_as_output(GpioState::Low), Ok(()));
+    assert_eq!(driver.get_gpio_state(0).unwrap().mode, GpioMode::Output);
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(flex.toggle(), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(flex.read(), Ok(GpioState::High));
+    assert_eq!(flex.write(GpioState::Low), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+
+    assert_eq!(flex.set_as_input::<PullUp>(), Ok(()));
+    assert_eq!(
+        driver.get_gpio_state(0).unwrap().mode,
+        GpioMode::Input(PullMode::PullUp)
+    );
+    assert_eq!(flex.read(), Ok(GpioState::Low));
+    drop(flex);
+    assert_eq!(driver.get_gpio_state(0).unwrap().mode, GpioMode::Disable);
+}
+
+#[test]
+fn make_output_with() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<10>::new();
+    kernel.add_driver(&driver);
+
+    let mut pin = Gpio::new_pin(0).unwrap();
+    let mut output = pin.make_output_with(GpioState::High).unwrap();
+    assert_eq!(driver.get_gpio_state(0).unwrap().mode, GpioMode::Output);
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output.state(), Some(GpioState::High));
+    assert_eq!(output.toggle(), Ok(()));
+    assert_eq!(output.state(), Some(GpioState::Low));
+    assert_eq!(output.set(), Ok(()));
+    drop(output);
+
+    // `make_output` leaves the level alone.
+    let output = pin.make_output().unwrap();
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(output.state(), None);
+}
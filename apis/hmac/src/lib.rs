@@ -0,0 +1,178 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The HMAC driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::hmac::{Hmac, HmacAlgorithm};
+///
+/// let mut mac = [0; 32];
+/// let mut session = Hmac::start(HmacAlgorithm::Sha256, b"secret key")?;
+/// session.update(b"hello ")?;
+/// session.update(b"world")?;
+/// session.finish(&mut mac)?;
+/// ```
+pub struct Hmac<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Hmac<S, C> {
+    /// Run a check against the HMAC capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Starts computing the MAC of a new message with `algorithm` and `key`.
+    /// Any data added to a previous unfinished session is discarded.
+    pub fn start(algorithm: HmacAlgorithm, key: &[u8]) -> Result<HmacSession<S, C>, ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as u32, 0).to_result()?;
+        Ok(HmacSession {
+            key,
+            syscalls: PhantomData,
+        })
+    }
+
+    /// Computes the MAC of `data` in a single operation, and writes it into
+    /// the start of `mac`.
+    pub fn mac(
+        algorithm: HmacAlgorithm,
+        key: &[u8],
+        data: &[u8],
+        mac: &mut [u8],
+    ) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as u32, 0).to_result()?;
+        Self::run(command::RUN, key, data, mac)
+    }
+}
+
+/// An HMAC computation in progress, returned by `Hmac::start`.
+///
+/// The kernel loads the key when the computation starts, but the session
+/// shares it with every operation so it does not depend on when that happens.
+pub struct HmacSession<'k, S: Syscalls, C: Config = DefaultConfig> {
+    key: &'k [u8],
+    syscalls: PhantomData<(S, C)>,
+}
+
+impl<'k, S: Syscalls, C: Config> HmacSession<'k, S, C> {
+    /// Adds `data` to the message.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ErrorCode> {
+        Hmac::<S, C>::run(command::UPDATE, self.key, data, &mut [])
+    }
+
+    /// Writes the MAC of the message into the start of `mac`, which must hold
+    /// at least `mac_len()` bytes of the session's algorithm.
+    pub fn finish(self, mac: &mut [u8]) -> Result<(), ErrorCode> {
+        Hmac::<S, C>::run(command::FINISH, self.key, &[], mac)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HmacAlgorithm {
+    Sha256 = 0,
+    Sha384 = 1,
+    Sha512 = 2,
+}
+
+impl HmacAlgorithm {
+    /// The length of the MAC, in bytes.
+    pub const fn mac_len(self) -> usize {
+        match self {
+            HmacAlgorithm::Sha256 => 32,
+            HmacAlgorithm::Sha384 => 48,
+            HmacAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+/// The length of the longest MAC, which is large enough for any algorithm.
+pub const MAX_MAC_LEN: usize = 64;
+
+/// System call configuration trait for `Hmac`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> Hmac<S, C> {
+    // Runs a command with the key, data and destination buffers shared, and
+    // waits for its upcall.
+    fn run(command_id: u32, key: &[u8], data: &[u8], mac: &mut [u8]) -> Result<(), ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::DEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_data, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::DATA }>(allow_data, data)?;
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DEST }>(allow_rw, mac)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command_id, 0, 0).to_result::<(), _>()?;
+            loop {
+                S::yield_wait();
+                if let Some((status,)) = done.get() {
+                    return match status {
+                        0 => Ok(()),
+                        _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                    };
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const RUN: u32 = 2;
+    pub const UPDATE: u32 = 3;
+    pub const FINISH: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const DATA: u32 = 1;
+}
+
+mod allow_rw {
+    pub const DEST: u32 = 0;
+}
@@ -0,0 +1,80 @@
+use crate::{HmacAlgorithm, MAX_MAC_LEN};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Hmac = super::Hmac<fake::Syscalls>;
+
+// Test case 2 of RFC 4231.
+const KEY: &[u8] = b"Jefe";
+const DATA: &[u8] = b"what do ya want for nothing?";
+const HMAC_SHA256: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Hmac::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Hmac::exists(), Ok(()));
+}
+
+#[test]
+fn session() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut mac = [0; 32];
+    let mut session = Hmac::start(HmacAlgorithm::Sha256, KEY).unwrap();
+    assert_eq!(session.update(&DATA[..10]), Ok(()));
+    assert_eq!(driver.key(), Some(KEY.to_vec()));
+    assert_eq!(session.update(&DATA[10..]), Ok(()));
+    assert_eq!(session.finish(&mut mac), Ok(()));
+    assert_eq!(mac, HMAC_SHA256);
+
+    // Starting a new session discards the data of an unfinished one.
+    let mut session = Hmac::start(HmacAlgorithm::Sha256, b"other key").unwrap();
+    assert_eq!(session.update(DATA), Ok(()));
+    let mut session = Hmac::start(HmacAlgorithm::Sha256, KEY).unwrap();
+    assert_eq!(driver.message(), b"");
+    assert_eq!(session.update(DATA), Ok(()));
+    assert_eq!(session.finish(&mut mac), Ok(()));
+    assert_eq!(mac, HMAC_SHA256);
+}
+
+#[test]
+fn mac() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut mac = [0; MAX_MAC_LEN];
+    assert_eq!(
+        Hmac::mac(HmacAlgorithm::Sha256, KEY, DATA, &mut mac),
+        Ok(())
+    );
+    assert_eq!(mac[..32], HMAC_SHA256);
+
+    // Only the first bytes of the HMAC-SHA-512 code are checked.
+    assert_eq!(
+        Hmac::mac(HmacAlgorithm::Sha512, KEY, DATA, &mut mac),
+        Ok(())
+    );
+    assert_eq!(mac[..8], [0x16, 0x4b, 0x7a, 0x7b, 0xfc, 0xf8, 0x19, 0xe2]);
+
+    let mut short = [0; 32];
+    assert_eq!(
+        Hmac::mac(HmacAlgorithm::Sha384, KEY, DATA, &mut short),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(HmacAlgorithm::Sha384.mac_len(), 48);
+}
@@ -0,0 +1,110 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::fmt;
+use libtock_platform::{
+    share, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+pub struct Humidity<S: Syscalls>(S);
+
+impl<S: Syscalls> Humidity<S> {
+    /// Returns Ok() if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Initiate a humidity measurement.
+    ///
+    /// This function is used both for synchronous and asynchronous readings
+    pub fn read_humidity() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, READ_HUMIDITY, 0, 0).to_result()
+    }
+
+    /// Register an events listener
+    pub fn register_listener<'share, F: Fn(RelativeHumidity)>(
+        listener: &'share HumidityListener<F>,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
+    }
+
+    /// Unregister the events listener
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, 0)
+    }
+
+    /// Initiate a synchronous humidity measurement.
+    /// Returns Ok(humidity) if the operation was successful.
+    pub fn read_humidity_sync() -> Result<RelativeHumidity, ErrorCode> {
+        let humidity_cell: Cell<Option<RelativeHumidity>> = Cell::new(None);
+        let listener = HumidityListener(|humidity| {
+            humidity_cell.set(Some(humidity));
+        });
+        share::scope(|subscribe| {
+            if let Ok(()) = Self::register_listener(&listener, subscribe) {
+                if let Ok(()) = Self::read_humidity() {
+                    while humidity_cell.get() == None {
+                        S::yield_wait();
+                    }
+                }
+            }
+        });
+
+        match humidity_cell.get() {
+            None => Err(ErrorCode::Busy),
+            Some(humidity) => Ok(humidity),
+        }
+    }
+}
+
+/// A relative humidity, stored in hundredths of a percent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelativeHumidity {
+    centipercent: u32,
+}
+
+impl RelativeHumidity {
+    pub const fn from_centipercent(centipercent: u32) -> RelativeHumidity {
+        RelativeHumidity { centipercent }
+    }
+
+    /// The humidity in hundredths of a percent.
+    pub const fn centipercent(self) -> u32 {
+        self.centipercent
+    }
+
+    /// The humidity in whole percent, rounded down.
+    pub const fn percent(self) -> u32 {
+        self.centipercent / 100
+    }
+}
+
+/// Formats the humidity as a percentage with two decimals, e.g. `45.07%`.
+impl fmt::Display for RelativeHumidity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:02}%", self.percent(), self.centipercent % 100)
+    }
+}
+
+pub struct HumidityListener<F: Fn(RelativeHumidity)>(pub F);
+impl<F: Fn(RelativeHumidity)> Upcall<OneId<DRIVER_NUM, 0>> for HumidityListener<F> {
+    fn upcall(&self, humidity: u32, _arg1: u32, _arg2: u32) {
+        self.0(RelativeHumidity::from_centipercent(humidity))
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60001;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const READ_HUMIDITY: u32 = 1;
@@ -0,0 +1,92 @@
+use crate::RelativeHumidity;
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type Humidity = super::Humidity<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Humidity::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Humidity::exists(), Ok(()));
+}
+
+#[test]
+fn read_humidity() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Humidity::read_humidity(), Ok(()));
+    assert!(driver.is_busy());
+
+    assert_eq!(Humidity::read_humidity(), Err(ErrorCode::Busy));
+    assert_eq!(Humidity::read_humidity_sync(), Err(ErrorCode::Busy));
+}
+
+#[test]
+fn register_unregister_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    let humidity_cell: Cell<Option<RelativeHumidity>> = Cell::new(None);
+    let listener = crate::HumidityListener(|humidity| {
+        humidity_cell.set(Some(humidity));
+    });
+    share::scope(|subscribe| {
+        assert_eq!(Humidity::read_humidity(), Ok(()));
+        driver.set_value(4507);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(Humidity::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(Humidity::read_humidity(), Ok(()));
+        driver.set_value(4507);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            humidity_cell.get(),
+            Some(RelativeHumidity::from_centipercent(4507))
+        );
+
+        Humidity::unregister_listener();
+        assert_eq!(Humidity::read_humidity(), Ok(()));
+        driver.set_value(4507);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
+
+#[test]
+fn read_humidity_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(4507);
+    let humidity = Humidity::read_humidity_sync().unwrap();
+    assert_eq!(humidity.centipercent(), 4507);
+    assert_eq!(humidity.percent(), 45);
+}
+
+#[test]
+fn display() {
+    extern crate std;
+    use std::string::ToString;
+
+    assert_eq!(
+        RelativeHumidity::from_centipercent(4507).to_string(),
+        "45.07%"
+    );
+    assert_eq!(
+        RelativeHumidity::from_centipercent(10000).to_string(),
+        "100.00%"
+    );
+}
@@ -0,0 +1,261 @@
+use libtock_platform::ErrorCode;
+
+/// The maximum length of an 802.15.4 frame (the PHY payload), including the
+/// two-byte frame check sequence appended by the radio.
+pub const MAX_FRAME_LEN: usize = 127;
+
+/// Returns the length of a buffer able to hold an `RxRingBuffer` of
+/// `num_frames` frames.
+pub const fn rx_buffer_len(num_frames: usize) -> usize {
+    // One slot is always left empty to tell a full ring from an empty one.
+    RING_HEADER_LEN + (num_frames + 1) * SLOT_LEN
+}
+
+/// A frame received by the radio, copied out of an `RxRingBuffer`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    header_len: u8,
+    payload_len: u8,
+    mic_len: u8,
+    body: [u8; MAX_FRAME_LEN],
+}
+
+impl Frame {
+    /// The MAC header.
+    pub fn header(&self) -> &[u8] {
+        &self.body[..self.header_len as usize]
+    }
+
+    /// The MAC payload, which the kernel has decrypted if the frame was
+    /// secured.
+    pub fn payload(&self) -> &[u8] {
+        let start = self.header_len as usize;
+        &self.body[start..start + self.payload_len as usize]
+    }
+
+    /// The message integrity code of a secured frame.
+    pub fn mic(&self) -> &[u8] {
+        let start = self.header_len as usize + self.payload_len as usize;
+        &self.body[start..start + self.mic_len as usize]
+    }
+
+    /// Parses the MAC header.
+    pub fn parse_header(&self) -> Option<FrameHeader> {
+        FrameHeader::parse(self.header())
+    }
+}
+
+/// A ring buffer the kernel writes received frames into, shared with the
+/// kernel through allow-rw.
+///
+/// The buffer starts with the index of the next frame to read and the index of
+/// the next frame the kernel will write, followed by the frame slots. Each slot
+/// holds the header, payload and MIC lengths followed by the frame's bytes.
+/// When the ring is full the kernel drops the oldest frame.
+pub struct RxRingBuffer<'a> {
+    buffer: &'a mut [u8],
+}
+
+impl<'a> RxRingBuffer<'a> {
+    /// Wraps `buffer` in an empty ring buffer. `buffer` should be created with
+    /// `rx_buffer_len`; returns `ErrorCode::Size` if its length was not
+    /// computed by `rx_buffer_len` for at least one frame.
+    pub fn new(buffer: &'a mut [u8]) -> Result<RxRingBuffer<'a>, ErrorCode> {
+        let slots_len = buffer.len().checked_sub(RING_HEADER_LEN);
+        match slots_len {
+            Some(len) if len % SLOT_LEN == 0 && len / SLOT_LEN >= 2 => {}
+            _ => return Err(ErrorCode::Size),
+        }
+        buffer[READ_INDEX] = 0;
+        buffer[WRITE_INDEX] = 0;
+        Ok(RxRingBuffer { buffer })
+    }
+
+    /// The number of frames the ring buffer has room for.
+    pub fn capacity(&self) -> usize {
+        self.num_slots() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer[READ_INDEX] == self.buffer[WRITE_INDEX]
+    }
+
+    /// Removes and returns the oldest received frame. Frames with invalid
+    /// lengths are discarded.
+    pub fn pop(&mut self) -> Option<Frame> {
+        while !self.is_empty() {
+            let index = self.buffer[READ_INDEX] as usize;
+            if index >= self.num_slots() {
+                // The indices were corrupted; discard the ring's contents.
+                self.buffer[READ_INDEX] = self.buffer[WRITE_INDEX];
+                return None;
+            }
+            self.buffer[READ_INDEX] = ((index + 1) % self.num_slots()) as u8;
+
+            let start = RING_HEADER_LEN + index * SLOT_LEN;
+            let slot = &self.buffer[start..start + SLOT_LEN];
+            let mut frame = Frame {
+                header_len: slot[0],
+                payload_len: slot[1],
+                mic_len: slot[2],
+                body: [0; MAX_FRAME_LEN],
+            };
+            frame.body.copy_from_slice(&slot[SLOT_HEADER_LEN..]);
+            let len = slot[0] as usize + slot[1] as usize + slot[2] as usize;
+            if len <= MAX_FRAME_LEN {
+                return Some(frame);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.buffer
+    }
+
+    fn num_slots(&self) -> usize {
+        (self.buffer.len() - RING_HEADER_LEN) / SLOT_LEN
+    }
+}
+
+/// The type of an 802.15.4 frame.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FrameType {
+    Beacon,
+    Data,
+    Acknowledgement,
+    MacCommand,
+    /// A frame type reserved by the 2006 version of the standard.
+    Reserved(u8),
+}
+
+/// The address of an 802.15.4 node.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MacAddress {
+    Short(u16),
+    Long(u64),
+}
+
+/// The fields of an 802.15.4 MAC header (MHR).
+///
+/// The auxiliary security header of secured frames is not parsed, so
+/// `header_len` only covers the frame control field through the addressing
+/// fields.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FrameHeader {
+    pub frame_type: FrameType,
+    pub security_enabled: bool,
+    pub frame_pending: bool,
+    pub ack_request: bool,
+    pub pan_id_compression: bool,
+    pub sequence_number: u8,
+    pub dst_pan: Option<u16>,
+    pub dst_addr: Option<MacAddress>,
+    /// The source PAN ID. When PAN ID compression is used, this is the
+    /// destination PAN ID.
+    pub src_pan: Option<u16>,
+    pub src_addr: Option<MacAddress>,
+    /// The length of the header in bytes.
+    pub header_len: usize,
+}
+
+impl FrameHeader {
+    /// Parses the MAC header at the start of `frame`. Returns `None` if the
+    /// header is truncated or uses a reserved addressing mode.
+    pub fn parse(frame: &[u8]) -> Option<FrameHeader> {
+        let mut reader = Reader {
+            data: frame,
+            pos: 0,
+        };
+        let control = reader.u16()?;
+        let sequence_number = reader.u8()?;
+        let pan_id_compression = control & PAN_ID_COMPRESSION != 0;
+
+        let dst_mode = (control >> DST_MODE_SHIFT) & ADDR_MODE_MASK;
+        let (dst_pan, dst_addr) = match dst_mode {
+            ADDR_MODE_NONE => (None, None),
+            _ => (Some(reader.u16()?), Some(reader.address(dst_mode)?)),
+        };
+        let src_mode = (control >> SRC_MODE_SHIFT) & ADDR_MODE_MASK;
+        let (src_pan, src_addr) = match src_mode {
+            ADDR_MODE_NONE => (None, None),
+            _ if pan_id_compression => (dst_pan, Some(reader.address(src_mode)?)),
+            _ => (Some(reader.u16()?), Some(reader.address(src_mode)?)),
+        };
+
+        Some(FrameHeader {
+            frame_type: match (control & FRAME_TYPE_MASK) as u8 {
+                0 => FrameType::Beacon,
+                1 => FrameType::Data,
+                2 => FrameType::Acknowledgement,
+                3 => FrameType::MacCommand,
+                other => FrameType::Reserved(other),
+            },
+            security_enabled: control & SECURITY_ENABLED != 0,
+            frame_pending: control & FRAME_PENDING != 0,
+            ack_request: control & ACK_REQUEST as u16 != 0,
+            pan_id_compression,
+            sequence_number,
+            dst_pan,
+            dst_addr,
+            src_pan,
+            src_addr,
+            header_len: reader.pos,
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.data.get(self.pos..self.pos + N)?.try_into().ok()?;
+        self.pos += N;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes::<1>().map(|[byte]| byte)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes().map(u16::from_le_bytes)
+    }
+
+    fn address(&mut self, mode: u16) -> Option<MacAddress> {
+        match mode {
+            ADDR_MODE_SHORT => self.u16().map(MacAddress::Short),
+            ADDR_MODE_LONG => self
+                .bytes()
+                .map(|b| MacAddress::Long(u64::from_le_bytes(b))),
+            _ => None,
+        }
+    }
+}
+
+// Ring buffer layout
+const READ_INDEX: usize = 0;
+const WRITE_INDEX: usize = 1;
+const RING_HEADER_LEN: usize = 2;
+const SLOT_HEADER_LEN: usize = 3;
+const SLOT_LEN: usize = SLOT_HEADER_LEN + MAX_FRAME_LEN;
+
+// Frame control field
+const FRAME_TYPE_MASK: u16 = 0b111;
+const SECURITY_ENABLED: u16 = 1 << 3;
+const FRAME_PENDING: u16 = 1 << 4;
+pub(crate) const ACK_REQUEST: u8 = 1 << 5;
+const PAN_ID_COMPRESSION: u16 = 1 << 6;
+const DST_MODE_SHIFT: u16 = 10;
+const SRC_MODE_SHIFT: u16 = 14;
+const ADDR_MODE_MASK: u16 = 0b11;
+const ADDR_MODE_NONE: u16 = 0b00;
+const ADDR_MODE_SHORT: u16 = 0b10;
+const ADDR_MODE_LONG: u16 = 0b11;
@@ -0,0 +1,263 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod frame;
+
+pub use frame::{
+    rx_buffer_len, Frame, FrameHeader, FrameType, MacAddress, RxRingBuffer, MAX_FRAME_LEN,
+};
+
+/// The IEEE 802.15.4 raw radio driver.
+///
+/// Configuration changes (addresses, PAN ID, channel and transmit power) are
+/// staged by the setters and only take effect after `commit_config` is
+/// called. Frames are transmitted as-is, so the process builds the MAC header
+/// itself. Received frames are written by the kernel into an `RxRingBuffer`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ieee802154::{rx_buffer_len, Ieee802154, RxRingBuffer};
+///
+/// Ieee802154::turn_on()?;
+/// Ieee802154::set_pan(0xABCD)?;
+/// Ieee802154::set_channel(26)?;
+/// Ieee802154::commit_config()?;
+///
+/// Ieee802154::transmit_frame_sync(&frame)?;
+///
+/// let mut buffer = [0; rx_buffer_len(2)];
+/// let mut ring = RxRingBuffer::new(&mut buffer)?;
+/// let frame = Ieee802154::receive_frame_sync(&mut ring)?;
+/// ```
+pub struct Ieee802154<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Ieee802154<S, C> {
+    /// Run a check against the IEEE 802.15.4 capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns `true` if the radio is powered on.
+    pub fn is_on() -> bool {
+        S::command(DRIVER_NUM, command::STATUS, 0, 0).is_success()
+    }
+
+    pub fn turn_on() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::TURN_ON, 0, 0).to_result()
+    }
+
+    pub fn turn_off() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::TURN_OFF, 0, 0).to_result()
+    }
+
+    /// Stages the 16-bit short address of this node.
+    pub fn set_short_address(address: u16) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_SHORT_ADDR, address as u32, 0).to_result()
+    }
+
+    /// Stages the 64-bit extended address of this node.
+    pub fn set_long_address(address: u64) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            command::SET_LONG_ADDR,
+            address as u32,
+            (address >> 32) as u32,
+        )
+        .to_result()
+    }
+
+    /// Stages the PAN ID of this node.
+    pub fn set_pan(pan: u16) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_PAN, pan as u32, 0).to_result()
+    }
+
+    /// Stages the radio channel. 802.15.4 in the 2.4 GHz band uses channels 11
+    /// through 26; other channels return `ErrorCode::Invalid`.
+    pub fn set_channel(channel: u8) -> Result<(), ErrorCode> {
+        if !(MIN_CHANNEL..=MAX_CHANNEL).contains(&channel) {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(DRIVER_NUM, command::SET_CHAN, channel as u32, 0).to_result()
+    }
+
+    /// Stages the transmit power, in dBm.
+    pub fn set_tx_power(dbm: i8) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_TX_PWR, dbm as i32 as u32, 0).to_result()
+    }
+
+    /// Applies the staged configuration to the radio.
+    pub fn commit_config() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::COMMIT_CFG, 0, 0).to_result()
+    }
+
+    pub fn get_short_address() -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_SHORT_ADDR, 0, 0)
+            .to_result()
+            .map(|address: u32| address as u16)
+    }
+
+    pub fn get_long_address() -> Result<u64, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_LONG_ADDR, 0, 0).to_result()
+    }
+
+    pub fn get_pan() -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_PAN, 0, 0)
+            .to_result()
+            .map(|pan: u32| pan as u16)
+    }
+
+    pub fn get_channel() -> Result<u8, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_CHAN, 0, 0)
+            .to_result()
+            .map(|channel: u32| channel as u8)
+    }
+
+    pub fn get_tx_power() -> Result<i8, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_TX_PWR, 0, 0)
+            .to_result()
+            .map(|dbm: u32| dbm as i8)
+    }
+
+    /// Transmits a complete MAC frame (header and payload, without the FCS)
+    /// and waits for the transmission to complete.
+    ///
+    /// Returns `ErrorCode::NoAck` if the frame requested an acknowledgement but
+    /// none was received, and `ErrorCode::Size` if the frame and its FCS do not
+    /// fit in `MAX_FRAME_LEN`.
+    pub fn transmit_frame_sync(frame: &[u8]) -> Result<(), ErrorCode> {
+        if frame.len() + FCS_LEN > MAX_FRAME_LEN {
+            return Err(ErrorCode::Size);
+        }
+        let called: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::FRAME_TRANSMITTED }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, frame)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::FRAME_TRANSMITTED }>(
+                subscribe, &called,
+            )?;
+            S::command(DRIVER_NUM, command::TRANSMIT, 0, 0).to_result()?;
+
+            loop {
+                S::yield_wait();
+                if let Some((status, acked)) = called.get() {
+                    return match (status, acked) {
+                        (0, 0) if frame_requests_ack(frame) => Err(ErrorCode::NoAck),
+                        (0, _) => Ok(()),
+                        (status, _) => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Returns the next received frame, waiting for one to arrive if `ring` is
+    /// empty.
+    pub fn receive_frame_sync(ring: &mut RxRingBuffer) -> Result<Frame, ErrorCode> {
+        if let Some(frame) = ring.pop() {
+            return Ok(frame);
+        }
+        let called: Cell<bool> = Cell::new(false);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::FRAME_RECEIVED }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::FRAME_RECEIVED }>(subscribe, &called)?;
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, ring.as_bytes_mut())?;
+            while !called.get() {
+                S::yield_wait();
+            }
+            Ok(())
+        })?;
+        ring.pop().ok_or(ErrorCode::Fail)
+    }
+}
+
+/// System call configuration trait for `Ieee802154`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+// Returns whether the frame control field has the acknowledgement request bit
+// set.
+fn frame_requests_ack(frame: &[u8]) -> bool {
+    frame
+        .first()
+        .map_or(false, |&control| control & frame::ACK_REQUEST != 0)
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30001;
+
+const FCS_LEN: usize = 2;
+const MIN_CHANNEL: u8 = 11;
+const MAX_CHANNEL: u8 = 26;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const STATUS: u32 = 1;
+    pub const SET_SHORT_ADDR: u32 = 2;
+    pub const SET_PAN: u32 = 4;
+    pub const SET_CHAN: u32 = 5;
+    pub const SET_TX_PWR: u32 = 6;
+    pub const COMMIT_CFG: u32 = 7;
+    pub const GET_SHORT_ADDR: u32 = 8;
+    pub const GET_PAN: u32 = 10;
+    pub const GET_CHAN: u32 = 11;
+    pub const GET_TX_PWR: u32 = 12;
+    pub const TRANSMIT: u32 = 27;
+    pub const SET_LONG_ADDR: u32 = 28;
+    pub const GET_LONG_ADDR: u32 = 29;
+    pub const TURN_ON: u32 = 30;
+    pub const TURN_OFF: u32 = 31;
+}
+
+mod subscribe {
+    pub const FRAME_RECEIVED: u32 = 0;
+    pub const FRAME_TRANSMITTED: u32 = 1;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
@@ -0,0 +1,204 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::{rx_buffer_len, FrameHeader, FrameType, MacAddress, RxRingBuffer};
+
+type Ieee802154 = super::Ieee802154<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ieee802154::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ieee802154::exists(), Ok(()));
+}
+
+#[test]
+fn power() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert!(!Ieee802154::is_on());
+    assert_eq!(Ieee802154::turn_on(), Ok(()));
+    assert!(Ieee802154::is_on());
+    assert!(driver.is_on());
+    assert_eq!(Ieee802154::turn_off(), Ok(()));
+    assert!(!Ieee802154::is_on());
+}
+
+#[test]
+fn config() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ieee802154::set_short_address(0x1540), Ok(()));
+    assert_eq!(Ieee802154::set_long_address(0x0011_2233_4455_6677), Ok(()));
+    assert_eq!(Ieee802154::set_pan(0xABCD), Ok(()));
+    assert_eq!(Ieee802154::set_channel(10), Err(ErrorCode::Invalid));
+    assert_eq!(Ieee802154::set_channel(27), Err(ErrorCode::Invalid));
+    assert_eq!(Ieee802154::set_channel(11), Ok(()));
+    assert_eq!(Ieee802154::set_tx_power(-10), Ok(()));
+
+    assert_eq!(Ieee802154::get_short_address(), Ok(0x1540));
+    assert_eq!(Ieee802154::get_long_address(), Ok(0x0011_2233_4455_6677));
+    assert_eq!(Ieee802154::get_pan(), Ok(0xABCD));
+    assert_eq!(Ieee802154::get_channel(), Ok(11));
+    assert_eq!(Ieee802154::get_tx_power(), Ok(-10));
+
+    assert_eq!(driver.committed_config(), None);
+    assert_eq!(Ieee802154::commit_config(), Ok(()));
+    assert_eq!(
+        driver.committed_config(),
+        Some(fake::RadioConfig {
+            short_address: 0x1540,
+            long_address: 0x0011_2233_4455_6677,
+            pan: 0xABCD,
+            channel: 11,
+            tx_power: -10,
+        })
+    );
+}
+
+#[test]
+fn transmit() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    // Data frame with short addresses, PAN ID compression and an ack request.
+    let frame = [
+        0x61, 0x88, 7, 0xCD, 0xAB, 0xFF, 0xFF, 0x40, 0x15, b'h', b'i',
+    ];
+    assert_eq!(Ieee802154::transmit_frame_sync(&frame), Err(ErrorCode::Off));
+    assert_eq!(Ieee802154::turn_on(), Ok(()));
+    assert_eq!(Ieee802154::transmit_frame_sync(&frame), Ok(()));
+    assert_eq!(driver.take_transmitted_frames(), [frame.to_vec()]);
+
+    driver.set_acknowledge(false);
+    assert_eq!(
+        Ieee802154::transmit_frame_sync(&frame),
+        Err(ErrorCode::NoAck)
+    );
+    // Frames that do not request an acknowledgement succeed anyway.
+    let broadcast = [0x41, 0x88, 8, 0xCD, 0xAB, 0xFF, 0xFF, 0x40, 0x15];
+    assert_eq!(Ieee802154::transmit_frame_sync(&broadcast), Ok(()));
+    assert_eq!(driver.take_transmitted_frames().len(), 2);
+
+    assert_eq!(
+        Ieee802154::transmit_frame_sync(&[0; 126]),
+        Err(ErrorCode::Size)
+    );
+    assert!(driver.take_transmitted_frames().is_empty());
+}
+
+#[test]
+fn receive() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ieee802154::new();
+    kernel.add_driver(&driver);
+
+    let header = [0x41, 0x88, 1, 0xCD, 0xAB, 0xFF, 0xFF, 0x40, 0x15];
+    driver.add_received_frame(&header, b"one");
+    driver.add_received_frame(&header, b"two");
+    let mut buffer = [0; rx_buffer_len(2)];
+    let mut ring = RxRingBuffer::new(&mut buffer).unwrap();
+    assert_eq!(ring.capacity(), 2);
+    assert!(ring.is_empty());
+
+    let frame = Ieee802154::receive_frame_sync(&mut ring).unwrap();
+    assert_eq!(frame.header(), header);
+    assert_eq!(frame.payload(), b"one");
+    assert!(frame.mic().is_empty());
+    assert_eq!(frame.parse_header().unwrap().dst_pan, Some(0xABCD));
+    // The second frame was received along with the first one.
+    assert!(!ring.is_empty());
+    let frame = Ieee802154::receive_frame_sync(&mut ring).unwrap();
+    assert_eq!(frame.payload(), b"two");
+    assert_eq!(ring.pop(), None);
+
+    driver.add_received_frame(&header, b"three");
+    let frame = Ieee802154::receive_frame_sync(&mut ring).unwrap();
+    assert_eq!(frame.payload(), b"three");
+}
+
+#[test]
+fn ring_buffer() {
+    assert_eq!(
+        RxRingBuffer::new(&mut [0; rx_buffer_len(0)]).map(|_| ()),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(
+        RxRingBuffer::new(&mut [0; rx_buffer_len(1) + 1]).map(|_| ()),
+        Err(ErrorCode::Size)
+    );
+
+    let mut buffer = [0; rx_buffer_len(1)];
+    // Corrupted lengths are discarded.
+    buffer[..5].copy_from_slice(&[0, 1, 100, 100, 0]);
+    let mut ring = RxRingBuffer::new(&mut buffer).unwrap();
+    ring.as_bytes_mut()[1] = 1;
+    assert!(!ring.is_empty());
+    assert_eq!(ring.pop(), None);
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn parse_header() {
+    // Data frame with short addresses, PAN ID compression and an ack request.
+    let header =
+        FrameHeader::parse(&[0x61, 0x88, 7, 0xCD, 0xAB, 0xFF, 0xFF, 0x40, 0x15, 0xAA]).unwrap();
+    assert_eq!(
+        header,
+        FrameHeader {
+            frame_type: FrameType::Data,
+            security_enabled: false,
+            frame_pending: false,
+            ack_request: true,
+            pan_id_compression: true,
+            sequence_number: 7,
+            dst_pan: Some(0xABCD),
+            dst_addr: Some(MacAddress::Short(0xFFFF)),
+            src_pan: Some(0xABCD),
+            src_addr: Some(MacAddress::Short(0x1540)),
+            header_len: 9,
+        }
+    );
+
+    // MAC command frame with a short destination, an extended source and
+    // separate PAN IDs.
+    let frame = [
+        0x03, 0xC8, 1, // Frame control, sequence number
+        0x34, 0x12, 0x00, 0x00, // Destination PAN and address
+        0x78, 0x56, 8, 7, 6, 5, 4, 3, 2, 1, // Source PAN and address
+    ];
+    let header = FrameHeader::parse(&frame).unwrap();
+    assert_eq!(header.frame_type, FrameType::MacCommand);
+    assert!(!header.pan_id_compression);
+    assert_eq!(header.dst_addr, Some(MacAddress::Short(0x0000)));
+    assert_eq!(header.src_pan, Some(0x5678));
+    assert_eq!(
+        header.src_addr,
+        Some(MacAddress::Long(0x0102_0304_0506_0708))
+    );
+    assert_eq!(header.header_len, frame.len());
+
+    // Acknowledgement frames have no addresses.
+    let header = FrameHeader::parse(&[0x12, 0x00, 9]).unwrap();
+    assert_eq!(header.frame_type, FrameType::Acknowledgement);
+    assert!(header.frame_pending);
+    assert_eq!((header.dst_addr, header.src_addr), (None, None));
+    assert_eq!(header.header_len, 3);
+
+    // Truncated headers and reserved addressing modes.
+    assert_eq!(FrameHeader::parse(&frame[..16]), None);
+    assert_eq!(FrameHeader::parse(&[0x41, 0x84, 1, 0, 0, 0, 0]), None);
+}
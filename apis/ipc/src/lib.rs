@@ -0,0 +1,206 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::{OneId, Subscribe};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls, Upcall};
+
+/// The inter-process communication (IPC) driver.
+///
+/// IPC lets processes find each other by package name, exchange
+/// notifications, and share a buffer with a service.
+///
+/// A process becomes a service by registering a service listener; clients
+/// locate it with `discover`, which returns the service's ID. Because Tock uses
+/// the service ID as the subscribe and allow number for client upcalls and
+/// shared buffers, functions operating on a specific service take the service
+/// ID as a const generic parameter.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ipc::Ipc;
+///
+/// // Find the service, then share a buffer with it and wait for its reply.
+/// let service_id = Ipc::discover(b"org.tockos.example.service")?;
+/// assert_eq!(service_id, 1);
+/// let mut buffer = [0; 16];
+/// Ipc::call_service_sync::<1>(&mut buffer)?;
+/// ```
+pub struct Ipc<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Ipc<S, C> {
+    /// Run a check against the IPC driver to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Searches for a service process by its package name.
+    ///
+    /// Returns the ID of the service, or `ErrorCode::NoDevice` if no process
+    /// with the given package name exists.
+    pub fn discover(package_name: &[u8]) -> Result<u32, ErrorCode> {
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::SEARCH }>, _, _>(|allow_ro| {
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::SEARCH }>(allow_ro, package_name)?;
+            S::command(DRIVER_NUM, command::DISCOVER, 0, 0).to_result()
+        })
+    }
+
+    /// Registers this process as a service.
+    ///
+    /// The listener is called with the ID of the client each time a client
+    /// notifies this service. There can be only one service listener
+    /// registered at a time.
+    pub fn register_service_listener<'share, F: Fn(u32)>(
+        listener: &'share IpcListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::SERVICE }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<
+            OneId<DRIVER_NUM, { subscribe::SERVICE }>,
+            _,
+            C,
+            DRIVER_NUM,
+            { subscribe::SERVICE },
+        >(subscribe, listener)
+    }
+
+    /// Unregister the service listener.
+    pub fn unregister_service_listener() {
+        S::unsubscribe(DRIVER_NUM, subscribe::SERVICE)
+    }
+
+    /// Register a listener called with the service's ID each time the service
+    /// `SERVICE_ID` notifies this process.
+    ///
+    /// Returns `ErrorCode::Invalid` if `SERVICE_ID` is 0, as that number is
+    /// reserved for the service listener.
+    pub fn register_client_listener<'share, F: Fn(u32), const SERVICE_ID: u32>(
+        listener: &'share IpcListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, SERVICE_ID>>,
+    ) -> Result<(), ErrorCode> {
+        if SERVICE_ID == subscribe::SERVICE {
+            return Err(ErrorCode::Invalid);
+        }
+        S::subscribe::<OneId<DRIVER_NUM, SERVICE_ID>, _, C, DRIVER_NUM, SERVICE_ID>(
+            subscribe, listener,
+        )
+    }
+
+    /// Unregister the listener for notifications from `service_id`.
+    pub fn unregister_client_listener(service_id: u32) {
+        S::unsubscribe(DRIVER_NUM, service_id)
+    }
+
+    /// Shares `buffer` with the service `SERVICE_ID`. The service is passed
+    /// the buffer's location when this process notifies it.
+    ///
+    /// Returns `ErrorCode::Invalid` if `SERVICE_ID` is 0, as no service can
+    /// have that ID.
+    pub fn share<'share, const SERVICE_ID: u32>(
+        allow_rw: Handle<AllowRw<'share, S, DRIVER_NUM, SERVICE_ID>>,
+        buffer: &'share mut [u8],
+    ) -> Result<(), ErrorCode> {
+        if SERVICE_ID == subscribe::SERVICE {
+            return Err(ErrorCode::Invalid);
+        }
+        S::allow_rw::<C, DRIVER_NUM, SERVICE_ID>(allow_rw, buffer)
+    }
+
+    /// Notify the service `service_id`, invoking its service listener.
+    pub fn notify_service(service_id: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::NOTIFY_SERVICE, service_id, 0).to_result()
+    }
+
+    /// Notify the client `client_id`, invoking the listener it registered for
+    /// this service.
+    pub fn notify_client(client_id: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::NOTIFY_CLIENT, client_id, 0).to_result()
+    }
+
+    /// Shares `buffer` with the service `SERVICE_ID`, notifies it, and waits
+    /// until the service notifies this process back. The service's response
+    /// is left in `buffer`.
+    pub fn call_service_sync<const SERVICE_ID: u32>(buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        let responded: Cell<Option<u32>> = Cell::new(None);
+        let listener = IpcListener(|service_id| responded.set(Some(service_id)));
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, SERVICE_ID>,
+                Subscribe<_, DRIVER_NUM, SERVICE_ID>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            Self::share(allow_rw, buffer)?;
+            Self::register_client_listener(&listener, subscribe)?;
+            Self::notify_service(SERVICE_ID)?;
+            while responded.get().is_none() {
+                S::yield_wait();
+            }
+            Ok(())
+        })
+    }
+}
+
+/// A wrapper around a closure to be registered and called when another
+/// process sends an IPC notification. The closure receives the ID of the
+/// notifying process.
+///
+/// ```ignore
+/// let listener = IpcListener(|client_id| {
+///     // Respond to the client.
+///     let _ = Ipc::notify_client(client_id);
+/// });
+/// ```
+pub struct IpcListener<F: Fn(u32)>(pub F);
+
+impl<F: Fn(u32), const SUBSCRIBE_NUM: u32> Upcall<OneId<DRIVER_NUM, SUBSCRIBE_NUM>>
+    for IpcListener<F>
+{
+    fn upcall(&self, process_id: u32, _len: u32, _address: u32) {
+        self.0(process_id)
+    }
+}
+
+/// System call configuration trait for `Ipc`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const DISCOVER: u32 = 1;
+    pub const NOTIFY_SERVICE: u32 = 2;
+    pub const NOTIFY_CLIENT: u32 = 3;
+}
+
+mod subscribe {
+    /// Upcalls from clients to a service. Upcalls from a service to its
+    /// clients use the service ID as the subscribe number.
+    pub const SERVICE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const SEARCH: u32 = 0;
+}
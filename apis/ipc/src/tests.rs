@@ -0,0 +1,105 @@
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+use crate::IpcListener;
+
+type Ipc = super::Ipc<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ipc::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Ipc::exists(), Ok(()));
+}
+
+#[test]
+fn discover() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::<4>::new();
+    kernel.add_driver(&driver);
+    driver.add_service("org.tockos.first", |_| {});
+    driver.add_service("org.tockos.second", |_| {});
+
+    assert_eq!(Ipc::discover(b"org.tockos.second"), Ok(2));
+    assert_eq!(Ipc::discover(b"org.tockos.first"), Ok(1));
+    assert_eq!(Ipc::discover(b"org.tockos"), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn call_service() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::<4>::new();
+    kernel.add_driver(&driver);
+    let service_id = driver.add_service("org.tockos.increment", |buffer| {
+        buffer.iter_mut().for_each(|byte| *byte += 1)
+    });
+    assert_eq!(service_id, 1);
+
+    let mut buffer = [1, 2, 3];
+    assert_eq!(Ipc::call_service_sync::<1>(&mut buffer), Ok(()));
+    assert_eq!(buffer, [2, 3, 4]);
+
+    assert_eq!(
+        Ipc::call_service_sync::<2>(&mut buffer),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        Ipc::call_service_sync::<0>(&mut buffer),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn client_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::<4>::new();
+    kernel.add_driver(&driver);
+    driver.add_service("org.tockos.service", |_| {});
+
+    let notified: Cell<Option<u32>> = Cell::new(None);
+    let listener = IpcListener(|service_id| notified.set(Some(service_id)));
+    share::scope(|subscribe| {
+        assert_eq!(
+            Ipc::register_client_listener::<_, 1>(&listener, subscribe),
+            Ok(())
+        );
+        assert_eq!(Ipc::notify_service(1), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(notified.get(), Some(1));
+
+        notified.set(None);
+        Ipc::unregister_client_listener(1);
+        assert_eq!(Ipc::notify_service(1), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(notified.get(), None);
+    });
+}
+
+#[test]
+fn service() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::<4>::new();
+    kernel.add_driver(&driver);
+
+    let listener = IpcListener(|client_id| Ipc::notify_client(client_id).unwrap());
+    share::scope(|subscribe| {
+        assert_eq!(Ipc::register_service_listener(&listener, subscribe), Ok(()));
+        driver.notify_from_client(3);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(driver.take_client_notifications(), [3]);
+
+        Ipc::unregister_service_listener();
+        driver.notify_from_client(3);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+    assert_eq!(Ipc::notify_client(5), Err(ErrorCode::Invalid));
+}
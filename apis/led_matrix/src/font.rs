@@ -0,0 +1,65 @@
+//! A 5x5 font for LED matrices.
+//!
+//! The font covers digits, letters and common punctuation. Lowercase letters
+//! are displayed as uppercase, and characters without a glyph are displayed as
+//! `?`.
+
+/// The number of rows in each glyph.
+pub const GLYPH_HEIGHT: usize = 5;
+
+/// The number of columns in each glyph.
+pub const GLYPH_WIDTH: usize = 5;
+
+/// Returns the glyph for `character`, as one byte per row with the leftmost
+/// column in bit 4.
+pub fn glyph(character: char) -> [u8; GLYPH_HEIGHT] {
+    match character.to_ascii_uppercase() {
+        ' ' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000],
+        '0' => [0b01110, 0b10011, 0b10101, 0b11001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b11110, 0b00001, 0b01110, 0b10000, 0b11111],
+        '3' => [0b11110, 0b00001, 0b00110, 0b00001, 0b11110],
+        '4' => [0b10010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b11110],
+        '6' => [0b01110, 0b10000, 0b11110, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00010, 0b00100, 0b01000, 0b10000],
+        '8' => [0b01110, 0b10001, 0b01110, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b01111, 0b00001, 0b01110],
+        'A' => [0b01110, 0b10001, 0b11111, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b11110, 0b10001, 0b11110],
+        'C' => [0b01111, 0b10000, 0b10000, 0b10000, 0b01111],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b11110, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b11110, 0b10000, 0b10000],
+        'G' => [0b01111, 0b10000, 0b10011, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b11111, 0b10001, 0b10001],
+        'I' => [0b11111, 0b00100, 0b00100, 0b00100, 0b11111],
+        'J' => [0b11111, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10010, 0b10100, 0b11000, 0b10100, 0b10010],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10001, 0b10001],
+        'N' => [0b10001, 0b11001, 0b10101, 0b10011, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b11110, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b11110, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b01110, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10101, 0b11011, 0b10001],
+        'X' => [0b10001, 0b01010, 0b00100, 0b01010, 0b10001],
+        'Y' => [0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00010, 0b00100, 0b01000, 0b11111],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00100],
+        ',' => [0b00000, 0b00000, 0b00000, 0b00100, 0b01000],
+        ':' => [0b00000, 0b00100, 0b00000, 0b00100, 0b00000],
+        '\'' => [0b00100, 0b00100, 0b00000, 0b00000, 0b00000],
+        '-' => [0b00000, 0b00000, 0b01110, 0b00000, 0b00000],
+        '+' => [0b00000, 0b00100, 0b01110, 0b00100, 0b00000],
+        '=' => [0b00000, 0b01110, 0b00000, 0b01110, 0b00000],
+        '/' => [0b00001, 0b00010, 0b00100, 0b01000, 0b10000],
+        _ => [0b01110, 0b10001, 0b00110, 0b00000, 0b00100],
+    }
+}
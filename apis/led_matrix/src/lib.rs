@@ -0,0 +1,155 @@
+#![no_std]
+
+pub mod font;
+
+use libtock_leds::Leds;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// An LED matrix, such as the 5x5 matrix on the micro:bit, driven through the
+/// LED driver.
+///
+/// Boards expose the matrix as `ROWS * COLUMNS` LEDs numbered row by row, so
+/// the LED at (`row`, `column`) is LED `row * COLUMNS + column`, with (0, 0)
+/// at the top left. Bitmaps are given one `u32` per row, with the leftmost
+/// column in the most significant used bit, so that binary literals read like
+/// the display.
+///
+/// # Example
+/// ```ignore
+/// use libtock::alarm::{Alarm, Milliseconds};
+/// use libtock::led_matrix::LedMatrix;
+///
+/// // Turn on the LED in the middle of a 5x5 matrix.
+/// let _ = LedMatrix::on(2, 2);
+///
+/// // Draw a heart.
+/// let _ = LedMatrix::show(&[0b01010, 0b11111, 0b11111, 0b01110, 0b00100]);
+///
+/// // Scroll a message across the matrix.
+/// let _ = LedMatrix::scroll_text("Hello", || {
+///     let _ = Alarm::sleep_for(Milliseconds(150));
+/// });
+/// ```
+pub struct LedMatrix<S: Syscalls, const ROWS: u32 = 5, const COLUMNS: u32 = 5>(S);
+
+impl<S: Syscalls, const ROWS: u32, const COLUMNS: u32> LedMatrix<S, ROWS, COLUMNS> {
+    /// Checks that the LED driver is present and has enough LEDs for the
+    /// matrix. Returns `Size` if it has too few.
+    pub fn exists() -> Result<(), ErrorCode> {
+        if Leds::<S>::count()? < ROWS * COLUMNS {
+            return Err(ErrorCode::Size);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of rows and columns of the matrix.
+    pub const fn size() -> (u32, u32) {
+        (ROWS, COLUMNS)
+    }
+
+    pub fn on(row: u32, column: u32) -> Result<(), ErrorCode> {
+        Leds::<S>::on(Self::index(row, column)?)
+    }
+
+    pub fn off(row: u32, column: u32) -> Result<(), ErrorCode> {
+        Leds::<S>::off(Self::index(row, column)?)
+    }
+
+    pub fn set(row: u32, column: u32, on: bool) -> Result<(), ErrorCode> {
+        match on {
+            true => Self::on(row, column),
+            false => Self::off(row, column),
+        }
+    }
+
+    /// Turns off every LED.
+    pub fn clear() -> Result<(), ErrorCode> {
+        for led in 0..ROWS * COLUMNS {
+            Leds::<S>::off(led)?;
+        }
+        Ok(())
+    }
+
+    /// Would set the brightness of the whole matrix. The LED driver only turns
+    /// LEDs on and off, so this always returns `NoSupport`.
+    pub fn set_brightness(_level: u8) -> Result<(), ErrorCode> {
+        Err(ErrorCode::NoSupport)
+    }
+
+    /// Displays `bitmap`, with one entry per row. Rows missing from `bitmap`
+    /// are turned off, as are bits beyond the width of the matrix.
+    pub fn show(bitmap: &[u32]) -> Result<(), ErrorCode> {
+        for row in 0..ROWS {
+            let bits = bitmap.get(row as usize).copied().unwrap_or(0);
+            for column in 0..COLUMNS {
+                let on = column_bit(bits, COLUMNS, column);
+                Self::set(row, column, on)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Displays a single character from the built-in font.
+    pub fn show_char(character: char) -> Result<(), ErrorCode> {
+        Self::show(&font::glyph(character).map(u32::from))
+    }
+
+    /// Scrolls `text` from right to left across the matrix, using the
+    /// built-in font. The text enters from the right edge and scrolls until it
+    /// has left the display. `delay` is called after each one-column step and
+    /// controls the scrolling speed.
+    pub fn scroll_text<D: FnMut()>(text: &str, mut delay: D) -> Result<(), ErrorCode> {
+        let mask = match COLUMNS {
+            0..=31 => (1 << COLUMNS) - 1,
+            _ => u32::MAX,
+        };
+        let mut frame = [0; font::GLYPH_HEIGHT];
+        let text_columns = text.chars().flat_map(|character| {
+            let glyph = font::glyph(character);
+            // Each glyph is followed by a blank column.
+            (0..=font::GLYPH_WIDTH).map(move |column| glyph_column(&glyph, column))
+        });
+        let blank_columns = core::iter::repeat(0).take(COLUMNS as usize);
+        for column in text_columns.chain(blank_columns) {
+            for (row, bits) in frame.iter_mut().enumerate() {
+                *bits = ((*bits << 1) | ((column >> row) & 1) as u32) & mask;
+            }
+            Self::show(&frame)?;
+            delay();
+        }
+        Ok(())
+    }
+
+    // Returns the LED number of (`row`, `column`).
+    fn index(row: u32, column: u32) -> Result<u32, ErrorCode> {
+        if row >= ROWS || column >= COLUMNS {
+            return Err(ErrorCode::Invalid);
+        }
+        Ok(row * COLUMNS + column)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+// Returns whether `column` is lit in a row bitmap for a matrix `columns` wide.
+fn column_bit(bits: u32, columns: u32, column: u32) -> bool {
+    let shift = columns - 1 - column;
+    shift < 32 && (bits >> shift) & 1 == 1
+}
+
+// Returns column `column` of `glyph`, with row `i` in bit `i`. Columns past
+// the width of the glyph are blank.
+fn glyph_column(glyph: &[u8; font::GLYPH_HEIGHT], column: usize) -> u8 {
+    if column >= font::GLYPH_WIDTH {
+        return 0;
+    }
+    let shift = font::GLYPH_WIDTH - 1 - column;
+    glyph.iter().enumerate().fold(0, |bits, (row, glyph_row)| {
+        bits | (((glyph_row >> shift) & 1) << row)
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use crate::font;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type LedMatrix = super::LedMatrix<fake::Syscalls>;
+
+// Reads a row of a matrix `columns` wide back as a bitmap.
+fn get_row<const LEDS: usize>(driver: &fake::Leds<LEDS>, columns: u32, row: u32) -> u32 {
+    (0..columns).fold(0, |bits, column| {
+        let on = driver.get_led(row * columns + column).unwrap();
+        (bits << 1) | on as u32
+    })
+}
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(LedMatrix::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::exists(), Ok(()));
+    assert_eq!(LedMatrix::size(), (5, 5));
+}
+
+#[test]
+fn too_few_leds() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<10>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::exists(), Err(ErrorCode::Size));
+}
+
+#[test]
+fn on_off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::on(0, 4), Ok(()));
+    assert_eq!(driver.get_led(4), Some(true));
+    assert_eq!(LedMatrix::set(3, 2, true), Ok(()));
+    assert_eq!(driver.get_led(17), Some(true));
+    assert_eq!(LedMatrix::off(0, 4), Ok(()));
+    assert_eq!(driver.get_led(4), Some(false));
+    assert_eq!(LedMatrix::set(3, 2, false), Ok(()));
+    assert_eq!(driver.get_led(17), Some(false));
+    assert_eq!(LedMatrix::on(5, 0), Err(ErrorCode::Invalid));
+    assert_eq!(LedMatrix::on(0, 5), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn clear() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::on(1, 1), Ok(()));
+    assert_eq!(LedMatrix::on(4, 3), Ok(()));
+    assert_eq!(LedMatrix::clear(), Ok(()));
+    for row in 0..5 {
+        assert_eq!(get_row(&driver, 5, row), 0);
+    }
+}
+
+#[test]
+fn brightness() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::set_brightness(40), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn show() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    let heart = [0b01010, 0b11111, 0b11111, 0b01110, 0b00100];
+    assert_eq!(LedMatrix::show(&heart), Ok(()));
+    for (row, bits) in heart.iter().enumerate() {
+        assert_eq!(get_row(&driver, 5, row as u32), *bits);
+    }
+
+    // Missing rows and columns beyond the matrix are turned off.
+    assert_eq!(LedMatrix::show(&[0b1100001]), Ok(()));
+    assert_eq!(get_row(&driver, 5, 0), 0b00001);
+    assert_eq!(get_row(&driver, 5, 1), 0);
+}
+
+#[test]
+fn show_char() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::show_char('a'), Ok(()));
+    for (row, bits) in font::glyph('A').iter().enumerate() {
+        assert_eq!(get_row(&driver, 5, row as u32), *bits as u32);
+    }
+}
+
+#[test]
+fn scroll_text() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<25>::new();
+    kernel.add_driver(&driver);
+
+    let mut frames = 0;
+    let glyph = font::glyph('I');
+    assert_eq!(
+        LedMatrix::scroll_text("I", || {
+            frames += 1;
+            let mut rows = [0; 5];
+            for (row, bits) in rows.iter_mut().enumerate() {
+                *bits = get_row(&driver, 5, row as u32);
+            }
+            match frames {
+                // The first column of the glyph enters at the right edge.
+                1 => assert_eq!(rows, glyph.map(|bits| (bits >> 4) as u32)),
+                // The whole glyph is on the display.
+                5 => assert_eq!(rows, glyph.map(u32::from)),
+                // The glyph has been scrolled off the left edge.
+                11 => assert_eq!(rows, [0; 5]),
+                _ => {}
+            }
+        }),
+        Ok(())
+    );
+    // One step per glyph column, one for the gap, and one per matrix column
+    // to scroll the text out.
+    assert_eq!(frames, 11);
+}
+
+#[test]
+fn scroll_text_wide_matrix() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<224>::new();
+    kernel.add_driver(&driver);
+
+    type WideMatrix = super::LedMatrix<fake::Syscalls, 7, 32>;
+    let mut frames = 0;
+    assert_eq!(WideMatrix::scroll_text("OK", || frames += 1), Ok(()));
+    assert_eq!(frames, 2 * 6 + 32);
+    for row in 0..7 {
+        assert_eq!(get_row(&driver, 32, row), 0);
+    }
+}
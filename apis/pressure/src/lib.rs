@@ -0,0 +1,100 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::fmt;
+use libtock_platform::{
+    share, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+pub struct Pressure<S: Syscalls>(S);
+
+impl<S: Syscalls> Pressure<S> {
+    /// Returns Ok() if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Initiate a barometric pressure measurement.
+    ///
+    /// This function is used both for synchronous and asynchronous readings
+    pub fn read_pressure() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, READ_PRESSURE, 0, 0).to_result()
+    }
+
+    /// Register an events listener
+    pub fn register_listener<'share, F: Fn(Hectopascals)>(
+        listener: &'share PressureListener<F>,
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
+    }
+
+    /// Unregister the events listener
+    pub fn unregister_listener() {
+        S::unsubscribe(DRIVER_NUM, 0)
+    }
+
+    /// Initiate a synchronous barometric pressure measurement.
+    /// Returns Ok(pressure) if the operation was successful.
+    pub fn read_pressure_sync() -> Result<Hectopascals, ErrorCode> {
+        let pressure_cell: Cell<Option<Hectopascals>> = Cell::new(None);
+        let listener = PressureListener(|pressure| {
+            pressure_cell.set(Some(pressure));
+        });
+        share::scope(|subscribe| {
+            if let Ok(()) = Self::register_listener(&listener, subscribe) {
+                if let Ok(()) = Self::read_pressure() {
+                    while pressure_cell.get() == None {
+                        S::yield_wait();
+                    }
+                }
+            }
+        });
+
+        match pressure_cell.get() {
+            None => Err(ErrorCode::Busy),
+            Some(pressure) => Ok(pressure),
+        }
+    }
+}
+
+/// A pressure in hectopascals (millibars).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Hectopascals(pub u32);
+
+impl Hectopascals {
+    /// The pressure in pascals.
+    pub const fn pascals(self) -> u32 {
+        self.0.saturating_mul(100)
+    }
+}
+
+impl fmt::Display for Hectopascals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} hPa", self.0)
+    }
+}
+
+/// The listener is called with the pressure, which the capsule reports in
+/// whole hectopascals.
+pub struct PressureListener<F: Fn(Hectopascals)>(pub F);
+impl<F: Fn(Hectopascals)> Upcall<OneId<DRIVER_NUM, 0>> for PressureListener<F> {
+    fn upcall(&self, hectopascals: u32, _arg1: u32, _arg2: u32) {
+        self.0(Hectopascals(hectopascals))
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60008;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const READ_PRESSURE: u32 = 1;
@@ -0,0 +1,82 @@
+use crate::Hectopascals;
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type Pressure = super::Pressure<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Pressure::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Pressure::exists(), Ok(()));
+}
+
+#[test]
+fn read_pressure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Pressure::read_pressure(), Ok(()));
+    assert!(driver.is_busy());
+
+    assert_eq!(Pressure::read_pressure(), Err(ErrorCode::Busy));
+    assert_eq!(Pressure::read_pressure_sync(), Err(ErrorCode::Busy));
+}
+
+#[test]
+fn register_unregister_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    let pressure_cell: Cell<Option<Hectopascals>> = Cell::new(None);
+    let listener = crate::PressureListener(|pressure| {
+        pressure_cell.set(Some(pressure));
+    });
+    share::scope(|subscribe| {
+        assert_eq!(Pressure::read_pressure(), Ok(()));
+        driver.set_value(1013);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(Pressure::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(Pressure::read_pressure(), Ok(()));
+        driver.set_value(1013);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(pressure_cell.get(), Some(Hectopascals(1013)));
+
+        Pressure::unregister_listener();
+        assert_eq!(Pressure::read_pressure(), Ok(()));
+        driver.set_value(1013);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
+
+#[test]
+fn read_pressure_sync() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value_sync(1013);
+    let pressure = Pressure::read_pressure_sync().unwrap();
+    assert_eq!(pressure, Hectopascals(1013));
+    assert_eq!(pressure.pascals(), 101300);
+}
+
+#[test]
+fn display() {
+    extern crate std;
+    use std::string::ToString;
+
+    assert_eq!(Hectopascals(1013).to_string(), "1013 hPa");
+}
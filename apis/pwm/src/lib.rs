@@ -0,0 +1,198 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The PWM driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::pwm::{DutyCycle, Pwm};
+///
+/// // Drive pin 0 at 1 kHz with a 25% duty cycle.
+/// Pwm::start(0, 1000, DutyCycle::from_percent(25).unwrap())?;
+/// ```
+pub struct Pwm<S: Syscalls>(S);
+
+impl<S: Syscalls> Pwm<S> {
+    /// Run a check against the PWM capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the number of PWM pins.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the maximum frequency, in hertz, that `pin` can output.
+    pub fn get_max_frequency(pin: u32) -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, GET_MAX_FREQUENCY, pin, 0).to_result()
+    }
+
+    /// Starts outputting a PWM signal on `pin`. If the pin is already active,
+    /// its frequency and duty cycle are updated.
+    pub fn start(pin: u32, frequency_hz: u32, duty_cycle: DutyCycle) -> Result<(), ErrorCode> {
+        if pin > u16::MAX as u32 {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(
+            DRIVER_NUM,
+            START,
+            (duty_cycle.centipercent() as u32) << 16 | pin,
+            frequency_hz,
+        )
+        .to_result()
+    }
+
+    /// Stops the PWM signal on `pin`.
+    pub fn stop(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, STOP, pin, 0).to_result()
+    }
+
+    /// Returns a handle to `pin` running at `frequency_hz`, which implements
+    /// `embedded_hal::pwm::SetDutyCycle`. The output is not started until a
+    /// duty cycle is set.
+    pub fn output(pin: u32, frequency_hz: u32) -> Result<PwmOutput<S>, ErrorCode> {
+        if pin >= Self::count()? || frequency_hz > Self::get_max_frequency(pin)? {
+            return Err(ErrorCode::Invalid);
+        }
+        Ok(PwmOutput {
+            pin,
+            frequency_hz,
+            syscalls: PhantomData,
+        })
+    }
+}
+
+/// The fraction of each period during which a PWM signal is high, with a
+/// resolution of a hundredth of a percent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DutyCycle {
+    centipercent: u16,
+}
+
+impl DutyCycle {
+    /// The signal is always low.
+    pub const OFF: DutyCycle = DutyCycle { centipercent: 0 };
+    /// The signal is always high.
+    pub const FULL: DutyCycle = DutyCycle {
+        centipercent: MAX_CENTIPERCENT,
+    };
+
+    /// Returns `None` if `percent` is greater than 100.
+    pub const fn from_percent(percent: u8) -> Option<DutyCycle> {
+        Self::from_centipercent(percent as u16 * 100)
+    }
+
+    /// Returns `None` if `centipercent` is greater than 10000.
+    pub const fn from_centipercent(centipercent: u16) -> Option<DutyCycle> {
+        if centipercent > MAX_CENTIPERCENT {
+            return None;
+        }
+        Some(DutyCycle { centipercent })
+    }
+
+    /// Returns the duty cycle `numerator / denominator`, rounded to the
+    /// nearest hundredth of a percent. Returns `None` if `denominator` is 0 or
+    /// smaller than `numerator`.
+    pub const fn from_ratio(numerator: u16, denominator: u16) -> Option<DutyCycle> {
+        if denominator == 0 || numerator > denominator {
+            return None;
+        }
+        let max = MAX_CENTIPERCENT as u32;
+        let denominator = denominator as u32;
+        let centipercent = (numerator as u32 * max + denominator / 2) / denominator;
+        Some(DutyCycle {
+            centipercent: centipercent as u16,
+        })
+    }
+
+    pub const fn centipercent(self) -> u16 {
+        self.centipercent
+    }
+
+    /// The duty cycle in whole percent, rounded down.
+    pub const fn percent(self) -> u8 {
+        (self.centipercent / 100) as u8
+    }
+}
+
+/// A single PWM pin running at a fixed frequency, returned by `Pwm::output`.
+pub struct PwmOutput<S: Syscalls> {
+    pin: u32,
+    frequency_hz: u32,
+    syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> PwmOutput<S> {
+    pub fn pin(&self) -> u32 {
+        self.pin
+    }
+
+    pub fn frequency_hz(&self) -> u32 {
+        self.frequency_hz
+    }
+
+    pub fn set(&mut self, duty_cycle: DutyCycle) -> Result<(), ErrorCode> {
+        Pwm::<S>::start(self.pin, self.frequency_hz, duty_cycle)
+    }
+
+    pub fn stop(&mut self) -> Result<(), ErrorCode> {
+        Pwm::<S>::stop(self.pin)
+    }
+}
+
+/// The error type of the `embedded_hal` implementation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PwmError(pub ErrorCode);
+
+impl From<ErrorCode> for PwmError {
+    fn from(error: ErrorCode) -> PwmError {
+        PwmError(error)
+    }
+}
+
+impl embedded_hal::pwm::Error for PwmError {
+    fn kind(&self) -> embedded_hal::pwm::ErrorKind {
+        embedded_hal::pwm::ErrorKind::Other
+    }
+}
+
+impl<S: Syscalls> embedded_hal::pwm::ErrorType for PwmOutput<S> {
+    type Error = PwmError;
+}
+
+/// The duty cycle is expressed in hundredths of a percent, so
+/// `max_duty_cycle` is 10000.
+impl<S: Syscalls> embedded_hal::pwm::SetDutyCycle for PwmOutput<S> {
+    fn max_duty_cycle(&self) -> u16 {
+        MAX_CENTIPERCENT
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), PwmError> {
+        let duty_cycle = DutyCycle::from_centipercent(duty).ok_or(ErrorCode::Invalid)?;
+        Ok(self.set(duty_cycle)?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const START: u32 = 1;
+const STOP: u32 = 2;
+const GET_MAX_FREQUENCY: u32 = 3;
+const COUNT: u32 = 4;
+
+const MAX_CENTIPERCENT: u16 = 10000;
@@ -0,0 +1,109 @@
+use crate::{DutyCycle, PwmError};
+use embedded_hal::pwm::SetDutyCycle;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Pwm = super::Pwm<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Pwm::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Pwm::exists(), Ok(()));
+    assert_eq!(Pwm::count(), Ok(4));
+    driver.set_max_frequency(16_000);
+    assert_eq!(Pwm::get_max_frequency(3), Ok(16_000));
+    assert_eq!(Pwm::get_max_frequency(4), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn start_stop() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<4>::new();
+    kernel.add_driver(&driver);
+
+    let duty_cycle = DutyCycle::from_percent(25).unwrap();
+    assert_eq!(Pwm::start(2, 1000, duty_cycle), Ok(()));
+    assert_eq!(
+        driver.output(2),
+        Some(fake::PwmSignal {
+            frequency_hz: 1000,
+            duty_cycle: 2500
+        })
+    );
+    assert_eq!(Pwm::start(4, 1000, duty_cycle), Err(ErrorCode::Invalid));
+    assert_eq!(
+        Pwm::start(0x10002, 1000, duty_cycle),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(driver.output(2).unwrap().duty_cycle, 2500);
+
+    assert_eq!(Pwm::stop(2), Ok(()));
+    assert_eq!(driver.output(2), None);
+}
+
+#[test]
+fn duty_cycle() {
+    assert_eq!(DutyCycle::from_percent(100), Some(DutyCycle::FULL));
+    assert_eq!(DutyCycle::from_percent(101), None);
+    assert_eq!(DutyCycle::from_centipercent(0), Some(DutyCycle::OFF));
+    assert_eq!(DutyCycle::from_centipercent(10001), None);
+    assert_eq!(
+        DutyCycle::from_ratio(1, 3).map(DutyCycle::centipercent),
+        Some(3333)
+    );
+    assert_eq!(
+        DutyCycle::from_ratio(2, 3).map(DutyCycle::centipercent),
+        Some(6667)
+    );
+    assert_eq!(
+        DutyCycle::from_ratio(u16::MAX, u16::MAX),
+        Some(DutyCycle::FULL)
+    );
+    assert_eq!(DutyCycle::from_ratio(4, 3), None);
+    assert_eq!(DutyCycle::from_ratio(0, 0), None);
+    assert_eq!(DutyCycle::from_centipercent(4999).unwrap().percent(), 49);
+}
+
+#[test]
+fn set_duty_cycle() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<2>::new();
+    kernel.add_driver(&driver);
+    driver.set_max_frequency(10_000);
+
+    assert!(Pwm::output(2, 1000).is_err());
+    assert!(Pwm::output(1, 20_000).is_err());
+    let mut output = Pwm::output(1, 1000).unwrap();
+    assert_eq!(output.pin(), 1);
+    assert_eq!(driver.output(1), None);
+
+    assert_eq!(output.max_duty_cycle(), 10000);
+    assert_eq!(output.set_duty_cycle_percent(50), Ok(()));
+    assert_eq!(
+        driver.output(1),
+        Some(fake::PwmSignal {
+            frequency_hz: 1000,
+            duty_cycle: 5000
+        })
+    );
+    assert_eq!(output.set_duty_cycle_fraction(1, 4), Ok(()));
+    assert_eq!(driver.output(1).unwrap().duty_cycle, 2500);
+    assert_eq!(output.set_duty_cycle_fully_on(), Ok(()));
+    assert_eq!(driver.output(1).unwrap().duty_cycle, 10000);
+    assert_eq!(
+        output.set_duty_cycle(10001),
+        Err(PwmError(ErrorCode::Invalid))
+    );
+
+    assert_eq!(output.stop(), Ok(()));
+    assert_eq!(driver.output(1), None);
+}
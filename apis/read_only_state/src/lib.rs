@@ -0,0 +1,113 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform as platform;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The read-only state driver, through which the kernel publishes process
+/// state into a buffer the process can read without making system calls.
+///
+/// The buffer is shared once, usually at startup, and stays shared for the
+/// rest of the process' life.
+///
+/// # Example
+/// ```ignore
+/// use libtock::read_only_state::{ReadOnlyState, BUFFER_LEN};
+///
+/// static mut BUFFER: [u8; BUFFER_LEN] = [0; BUFFER_LEN];
+///
+/// // Safety: BUFFER is not accessed anywhere else.
+/// let state = ReadOnlyState::share(unsafe { &mut BUFFER })?;
+/// while state.pending_tasks() == 0 {
+///     // Do some background work.
+/// }
+/// ```
+pub struct ReadOnlyState<S: Syscalls, C: platform::allow_rw::Config = DefaultConfig> {
+    buffer: &'static [Cell<u8>],
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: platform::allow_rw::Config> ReadOnlyState<S, C> {
+    /// Run a check against the read-only state capsule to ensure it is
+    /// present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the version of the buffer layout used by the kernel.
+    pub fn version() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_VERSION, 0, 0).to_result()
+    }
+
+    /// Shares `buffer` with the kernel, which keeps it up to date for the
+    /// rest of the process' life. Returns `Err(ErrorCode::NoSupport)` if the
+    /// kernel uses a different layout than this crate.
+    pub fn share(buffer: &'static mut [u8; BUFFER_LEN]) -> Result<Self, ErrorCode> {
+        if Self::version()? != VERSION {
+            return Err(ErrorCode::NoSupport);
+        }
+        let buffer = Cell::from_mut(&mut buffer[..]).as_slice_of_cells();
+        S::allow_userspace_readable::<C, DRIVER_NUM, { allow_ur::STATE }>(buffer)?;
+        Ok(ReadOnlyState {
+            buffer,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// The number of upcalls queued for the process, which `yield` would run.
+    pub fn pending_tasks(&self) -> u32 {
+        u32::from_ne_bytes(self.read(offset::PENDING_TASKS))
+    }
+
+    /// The value of the kernel's alarm clock, in ticks, when the process was
+    /// last scheduled.
+    pub fn time_ticks(&self) -> u64 {
+        u64::from_ne_bytes(self.read(offset::TIME_TICKS))
+    }
+}
+
+/// The length of the buffer shared with the kernel.
+pub const BUFFER_LEN: usize = 16;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: platform::allow_rw::Config> ReadOnlyState<S, C> {
+    fn read<const N: usize>(&self, offset: usize) -> [u8; N] {
+        core::array::from_fn(|i| self.buffer[offset + i].get())
+    }
+}
+
+// The layout of the buffer that this crate understands.
+const VERSION: u32 = 1;
+
+// Byte offsets of the fields in the buffer, which are in native byte order.
+// The first field is the version.
+mod offset {
+    pub const PENDING_TASKS: usize = 4;
+    pub const TIME_TICKS: usize = 8;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x9;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_VERSION: u32 = 1;
+}
+
+mod allow_ur {
+    pub const STATE: u32 = 0;
+}
@@ -0,0 +1,70 @@
+use crate::BUFFER_LEN;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type ReadOnlyState = super::ReadOnlyState<fake::Syscalls>;
+
+// The buffer is shared for the rest of the process' life, so each test leaks
+// its buffer.
+fn leak_buffer() -> &'static mut [u8; BUFFER_LEN] {
+    extern crate std;
+    std::boxed::Box::leak(std::boxed::Box::new([0; BUFFER_LEN]))
+}
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(ReadOnlyState::exists(), Err(ErrorCode::NoDevice));
+    assert!(matches!(
+        ReadOnlyState::share(leak_buffer()),
+        Err(ErrorCode::NoDevice)
+    ));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::ReadOnlyState::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(ReadOnlyState::exists(), Ok(()));
+    assert_eq!(ReadOnlyState::version(), Ok(1));
+}
+
+#[test]
+fn share() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::ReadOnlyState::new();
+    kernel.add_driver(&driver);
+
+    driver.set_pending_tasks(3);
+    driver.set_time_ticks(1000);
+    let state = ReadOnlyState::share(leak_buffer()).unwrap();
+    assert_eq!(state.pending_tasks(), 3);
+    assert_eq!(state.time_ticks(), 1000);
+
+    // Reading the state does not make system calls.
+    kernel.take_syscall_log();
+    driver.set_pending_tasks(0);
+    driver.set_time_ticks(u32::MAX as u64 + 2);
+    assert_eq!(state.pending_tasks(), 0);
+    assert_eq!(state.time_ticks(), u32::MAX as u64 + 2);
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn share_log() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::ReadOnlyState::new();
+    kernel.add_driver(&driver);
+
+    let _state = ReadOnlyState::share(leak_buffer()).unwrap();
+    assert_eq!(
+        kernel.take_syscall_log().last(),
+        Some(&SyscallLogEntry::AllowUserspaceReadable {
+            driver_num: 0x9,
+            buffer_num: 0,
+            len: BUFFER_LEN,
+        })
+    );
+}
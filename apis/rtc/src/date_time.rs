@@ -0,0 +1,269 @@
+use libtock_platform::ErrorCode;
+
+/// The earliest year a `DateTime` can represent, so that every `DateTime` has
+/// a Unix timestamp.
+pub const MIN_YEAR: u16 = 1970;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Month {
+    January = 1,
+    February = 2,
+    March = 3,
+    April = 4,
+    May = 5,
+    June = 6,
+    July = 7,
+    August = 8,
+    September = 9,
+    October = 10,
+    November = 11,
+    December = 12,
+}
+
+impl Month {
+    /// Returns the month numbered `number`, starting with January as 1.
+    pub fn from_number(number: u8) -> Option<Month> {
+        use Month::*;
+        Some(match number {
+            1 => January,
+            2 => February,
+            3 => March,
+            4 => April,
+            5 => May,
+            6 => June,
+            7 => July,
+            8 => August,
+            9 => September,
+            10 => October,
+            11 => November,
+            12 => December,
+            _ => return None,
+        })
+    }
+
+    /// The number of the month, starting with January as 1.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    /// The number of days in this month of `year`.
+    pub fn days(self, year: u16) -> u8 {
+        use Month::*;
+        match self {
+            April | June | September | November => 30,
+            February if is_leap_year(year) => 29,
+            February => 28,
+            _ => 31,
+        }
+    }
+}
+
+/// The day of the week. The numbering follows the kernel, which starts the
+/// week on Sunday.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Weekday {
+    Sunday = 0,
+    Monday = 1,
+    Tuesday = 2,
+    Wednesday = 3,
+    Thursday = 4,
+    Friday = 5,
+    Saturday = 6,
+}
+
+impl Weekday {
+    pub fn from_number(number: u8) -> Option<Weekday> {
+        use Weekday::*;
+        Some(match number {
+            0 => Sunday,
+            1 => Monday,
+            2 => Tuesday,
+            3 => Wednesday,
+            4 => Thursday,
+            5 => Friday,
+            6 => Saturday,
+            _ => return None,
+        })
+    }
+
+    /// The number of the day, starting with Sunday as 0.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A validated calendar date and time of day, without a time zone.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DateTime {
+    // The field order makes the derived ordering chronological.
+    year: u16,
+    month: Month,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl DateTime {
+    /// Returns `Err(ErrorCode::Invalid)` unless `year` is at least
+    /// `MIN_YEAR`, `day` exists in `month` of `year`, and the time of day is
+    /// between 00:00:00 and 23:59:59.
+    pub fn new(
+        year: u16,
+        month: Month,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<DateTime, ErrorCode> {
+        if year < MIN_YEAR
+            || day == 0
+            || day > month.days(year)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(ErrorCode::Invalid);
+        }
+        Ok(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Returns the date and time `timestamp` seconds after
+    /// 1970-01-01 00:00:00, or `None` if its year does not fit in a `u16`.
+    pub fn from_unix_timestamp(timestamp: u64) -> Option<DateTime> {
+        let days = timestamp / SECONDS_PER_DAY;
+        let seconds = timestamp % SECONDS_PER_DAY;
+        let (year, month, day) = civil_from_days(days);
+        Some(DateTime {
+            year: year.try_into().ok()?,
+            month: Month::from_number(month)?,
+            day,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        })
+    }
+
+    /// The number of seconds since 1970-01-01 00:00:00.
+    pub fn unix_timestamp(&self) -> u64 {
+        self.days_since_epoch() * SECONDS_PER_DAY
+            + self.hour as u64 * 3600
+            + self.minute as u64 * 60
+            + self.second as u64
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        let number = (self.days_since_epoch() + Weekday::Thursday as u64) % 7;
+        Weekday::from_number(number as u8).unwrap_or(Weekday::Sunday)
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    // Decodes the date and time registers reported by the kernel. The weekday
+    // is not checked, as it is derived from the date.
+    pub(crate) fn from_registers(date: u32, time: u32) -> Result<DateTime, ErrorCode> {
+        let year = (date >> 9).try_into().map_err(|_| ErrorCode::Invalid)?;
+        let month = Month::from_number((date >> 5 & 0xf) as u8).ok_or(ErrorCode::Invalid)?;
+        DateTime::new(
+            year,
+            month,
+            (date & 0x1f) as u8,
+            (time >> 12 & 0x1f) as u8,
+            (time >> 6 & 0x3f) as u8,
+            (time & 0x3f) as u8,
+        )
+    }
+
+    // Encodes the date and time into the registers expected by the kernel.
+    pub(crate) fn to_registers(self) -> (u32, u32) {
+        let date = (self.year as u32) << 9 | (self.month as u32) << 5 | self.day as u32;
+        let time = (self.weekday() as u32) << 17
+            | (self.hour as u32) << 12
+            | (self.minute as u32) << 6
+            | self.second as u32;
+        (date, time)
+    }
+
+    fn days_since_epoch(&self) -> u64 {
+        days_from_civil(self.year as u64, self.month as u8, self.day)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// The number of days between 0000-03-01 and 1970-01-01.
+const EPOCH_OFFSET_DAYS: u64 = 719_468;
+
+// The number of days in a 400 year cycle of the Gregorian calendar.
+const DAYS_PER_ERA: u64 = 146_097;
+
+fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// Converts a date to the number of days since 1970-01-01. The computation uses
+// years that start on March 1st, so that the leap day is the last day of the
+// year. Only valid for dates from 1970-01-01 onwards.
+fn days_from_civil(year: u64, month: u8, day: u8) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    // Months since March.
+    let shifted_month = (month as u64 + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * DAYS_PER_ERA + day_of_era - EPOCH_OFFSET_DAYS
+}
+
+// The inverse of `days_from_civil`. Returns (year, month, day).
+fn civil_from_days(days: u64) -> (u64, u8, u8) {
+    let days = days + EPOCH_OFFSET_DAYS;
+    let era = days / DAYS_PER_ERA;
+    let day_of_era = days - era * DAYS_PER_ERA;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u8;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
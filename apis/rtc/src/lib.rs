@@ -0,0 +1,94 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::share;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod date_time;
+
+pub use date_time::{DateTime, Month, Weekday, MIN_YEAR};
+
+/// The real-time clock driver, which keeps the wall-clock date and time.
+///
+/// # Example
+/// ```ignore
+/// use libtock::rtc::{DateTime, Month, Rtc};
+///
+/// Rtc::set_date_time(DateTime::new(2024, Month::March, 14, 15, 9, 26)?)?;
+/// let now = Rtc::get_date_time()?;
+/// ```
+pub struct Rtc<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: platform::subscribe::Config> Rtc<S, C> {
+    /// Run a check against the date-time capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Reads the current date and time from the clock. Returns
+    /// `Err(ErrorCode::Invalid)` if the clock reports an invalid date, which
+    /// happens if it was never set.
+    pub fn get_date_time() -> Result<DateTime, ErrorCode> {
+        let (date, time) = Self::command_sync(command::GET_DATE_TIME, 0, 0)?;
+        DateTime::from_registers(date, time)
+    }
+
+    /// Sets the clock to `date_time`.
+    pub fn set_date_time(date_time: DateTime) -> Result<(), ErrorCode> {
+        let (date, time) = date_time.to_registers();
+        Self::command_sync(command::SET_DATE_TIME, date, time).map(|_| ())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: platform::subscribe::Config> Rtc<S, C> {
+    // Runs a command and waits for its upcall. Returns the upcall's two data
+    // arguments.
+    fn command_sync(
+        command_id: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let called: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &called)?;
+            S::command(DRIVER_NUM, command_id, argument0, argument1).to_result::<(), _>()?;
+            loop {
+                S::yield_wait();
+                if let Some((status, data0, data1)) = called.get() {
+                    return match status {
+                        0 => Ok((data0, data1)),
+                        _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                    };
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90007;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_DATE_TIME: u32 = 1;
+    pub const SET_DATE_TIME: u32 = 2;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
@@ -0,0 +1,133 @@
+use crate::{DateTime, Month, Weekday};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Rtc = super::Rtc<fake::Syscalls>;
+
+// 2024-03-14 15:09:26, a Thursday.
+const TIMESTAMP: u64 = 1_710_428_966;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Rtc::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rtc::new(0);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Rtc::exists(), Ok(()));
+}
+
+#[test]
+fn get_date_time() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rtc::new(TIMESTAMP);
+    kernel.add_driver(&driver);
+
+    let now = Rtc::get_date_time().unwrap();
+    assert_eq!(
+        now,
+        DateTime::new(2024, Month::March, 14, 15, 9, 26).unwrap()
+    );
+    assert_eq!(now.weekday(), Weekday::Thursday);
+
+    driver.advance(34);
+    let later = Rtc::get_date_time().unwrap();
+    assert_eq!((later.hour(), later.minute(), later.second()), (15, 10, 0));
+    assert!(later > now);
+
+    // Crossing into the next month.
+    driver.advance(18 * 24 * 60 * 60);
+    let later = Rtc::get_date_time().unwrap();
+    assert_eq!((later.month(), later.day()), (Month::April, 1));
+    assert_eq!(later.weekday(), Weekday::Monday);
+}
+
+#[test]
+fn set_date_time() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rtc::new(0);
+    kernel.add_driver(&driver);
+
+    let date_time = DateTime::new(2024, Month::March, 14, 15, 9, 26).unwrap();
+    assert_eq!(Rtc::set_date_time(date_time), Ok(()));
+    assert_eq!(driver.unix_timestamp(), TIMESTAMP);
+    assert_eq!(Rtc::get_date_time(), Ok(date_time));
+}
+
+#[test]
+fn validation() {
+    assert!(DateTime::new(2024, Month::February, 29, 0, 0, 0).is_ok());
+    assert!(DateTime::new(2000, Month::February, 29, 0, 0, 0).is_ok());
+    assert_eq!(
+        DateTime::new(2023, Month::February, 29, 0, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2100, Month::February, 29, 0, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2024, Month::April, 31, 0, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2024, Month::April, 0, 0, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(1969, Month::December, 31, 0, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2024, Month::April, 1, 24, 0, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2024, Month::April, 1, 0, 60, 0),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        DateTime::new(2024, Month::April, 1, 0, 0, 60),
+        Err(ErrorCode::Invalid)
+    );
+
+    assert_eq!(Month::from_number(0), None);
+    assert_eq!(Month::from_number(12), Some(Month::December));
+    assert_eq!(Month::from_number(13), None);
+    assert_eq!(Weekday::from_number(6), Some(Weekday::Saturday));
+    assert_eq!(Weekday::from_number(7), None);
+}
+
+#[test]
+fn unix_timestamp() {
+    let epoch = DateTime::from_unix_timestamp(0).unwrap();
+    assert_eq!(
+        epoch,
+        DateTime::new(1970, Month::January, 1, 0, 0, 0).unwrap()
+    );
+    assert_eq!(epoch.weekday(), Weekday::Thursday);
+    assert_eq!(epoch.unix_timestamp(), 0);
+
+    let date_time = DateTime::new(2024, Month::March, 14, 15, 9, 26).unwrap();
+    assert_eq!(date_time.unix_timestamp(), TIMESTAMP);
+    assert_eq!(DateTime::from_unix_timestamp(TIMESTAMP), Some(date_time));
+
+    let leap_day = DateTime::new(2000, Month::February, 29, 23, 59, 59).unwrap();
+    assert_eq!(leap_day.unix_timestamp(), 951_868_799);
+    assert_eq!(leap_day.weekday(), Weekday::Tuesday);
+    assert_eq!(DateTime::from_unix_timestamp(951_868_799), Some(leap_day));
+
+    let end_of_year = DateTime::new(2023, Month::December, 31, 23, 59, 59).unwrap();
+    assert_eq!(end_of_year.unix_timestamp(), 1_704_067_199);
+    assert_eq!(
+        DateTime::from_unix_timestamp(1_704_067_200),
+        DateTime::new(2024, Month::January, 1, 0, 0, 0).ok()
+    );
+
+    assert_eq!(DateTime::from_unix_timestamp(u64::MAX), None);
+}
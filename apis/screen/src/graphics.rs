@@ -0,0 +1,145 @@
+use core::marker::PhantomData;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::{IntoStorage, Rgb565};
+use embedded_graphics_core::primitives::{PointsIter, Rectangle};
+use embedded_graphics_core::Pixel;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+use crate::{Config, PixelFormat, Region, Screen};
+
+/// An `embedded_graphics` draw target that draws on the screen.
+///
+/// The screen must use the `Rgb565` pixel format. Pixels are sent to the
+/// display as they are drawn, so drawing is faster with primitives that fill
+/// whole areas than with individual pixels.
+///
+/// # Example
+/// ```ignore
+/// use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+/// use libtock::screen::ScreenTarget;
+///
+/// let mut target = ScreenTarget::new()?;
+/// target.clear(Rgb565::BLACK)?;
+/// ```
+pub struct ScreenTarget<S: Syscalls, C: Config = DefaultConfig> {
+    size: Size,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> ScreenTarget<S, C> {
+    /// Creates a draw target covering the whole screen at its current
+    /// resolution. Returns `ErrorCode::NoSupport` if the screen does not use
+    /// the `Rgb565` pixel format.
+    pub fn new() -> Result<Self, ErrorCode> {
+        if Screen::<S, C>::pixel_format()? != PixelFormat::Rgb565 {
+            return Err(ErrorCode::NoSupport);
+        }
+        let (width, height) = Screen::<S, C>::resolution()?;
+        Ok(ScreenTarget {
+            size: Size::new(width, height),
+            _syscalls: PhantomData,
+        })
+    }
+}
+
+impl<S: Syscalls, C: Config> OriginDimensions for ScreenTarget<S, C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<S: Syscalls, C: Config> DrawTarget for ScreenTarget<S, C> {
+    type Color = Rgb565;
+    type Error = ErrorCode;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), ErrorCode>
+    where
+        I: IntoIterator<Item = Pixel<Rgb565>>,
+    {
+        let bounds = self.bounding_box();
+        for Pixel(point, color) in pixels {
+            if bounds.contains(point) {
+                let region = Region {
+                    x: point.x as u16,
+                    y: point.y as u16,
+                    width: 1,
+                    height: 1,
+                };
+                Screen::<S, C>::write(region, &color_bytes(color))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), ErrorCode>
+    where
+        I: IntoIterator<Item = Rgb565>,
+    {
+        // Areas partially outside of the screen are clipped pixel by pixel.
+        if area.intersection(&self.bounding_box()) != *area {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+
+        // Write each row in chunks of up to CHUNK_PIXELS pixels, stopping
+        // early if `colors` runs out.
+        let mut colors = colors.into_iter();
+        let mut buffer = [0; CHUNK_PIXELS * BYTES_PER_PIXEL];
+        for y in area.rows() {
+            for x in area.columns().step_by(CHUNK_PIXELS) {
+                let width = core::cmp::min(CHUNK_PIXELS, (area.columns().end - x) as usize);
+                let mut filled = 0;
+                for pixel in buffer.chunks_exact_mut(BYTES_PER_PIXEL).take(width) {
+                    match colors.next() {
+                        Some(color) => pixel.copy_from_slice(&color_bytes(color)),
+                        None => break,
+                    }
+                    filled += 1;
+                }
+                if filled > 0 {
+                    let region = Region {
+                        x: x as u16,
+                        y: y as u16,
+                        width: filled as u16,
+                        height: 1,
+                    };
+                    Screen::<S, C>::write(region, &buffer[..filled * BYTES_PER_PIXEL])?;
+                }
+                if filled < width {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Rgb565) -> Result<(), ErrorCode> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        let region = Region {
+            x: area.top_left.x as u16,
+            y: area.top_left.y as u16,
+            width: area.size.width as u16,
+            height: area.size.height as u16,
+        };
+        Screen::<S, C>::fill(region, &color_bytes(color))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const BYTES_PER_PIXEL: usize = 2;
+const CHUNK_PIXELS: usize = 64;
+
+// Displays expect RGB565 pixels in big-endian byte order.
+fn color_bytes(color: Rgb565) -> [u8; BYTES_PER_PIXEL] {
+    color.into_storage().to_be_bytes()
+}
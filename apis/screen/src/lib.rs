@@ -0,0 +1,299 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod graphics;
+
+pub use graphics::ScreenTarget;
+
+/// The screen driver.
+///
+/// Queries about the resolutions and pixel formats the screen supports return
+/// immediately, while operations that talk to the display controller,
+/// including reading its current settings, wait until the controller has
+/// completed them.
+///
+/// # Example
+/// ```ignore
+/// use libtock::screen::{Region, Screen};
+///
+/// Screen::set_power(true)?;
+/// let (width, height) = Screen::resolution()?;
+/// // Draw a red RGB565 pixel in the top left corner.
+/// Screen::write(Region { x: 0, y: 0, width: 1, height: 1 }, &[0xF8, 0x00])?;
+/// ```
+pub struct Screen<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Screen<S, C> {
+    /// Run a check against the screen capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Turns the display on or off.
+    pub fn set_power(on: bool) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_POWER, on as u32, 0).map(|_| ())
+    }
+
+    /// Sets the backlight brightness, from 0 (off) to 65535 (maximum).
+    pub fn set_brightness(brightness: u16) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_BRIGHTNESS, brightness as u32, 0).map(|_| ())
+    }
+
+    /// Enables or disables color inversion.
+    pub fn set_invert(invert: bool) -> Result<(), ErrorCode> {
+        let command_id = match invert {
+            true => command::INVERT_ON,
+            false => command::INVERT_OFF,
+        };
+        Self::command_sync(command_id, 0, 0).map(|_| ())
+    }
+
+    /// Returns the number of resolutions the screen supports.
+    pub fn supported_resolutions() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::SUPPORTED_RESOLUTIONS, 0, 0).to_result()
+    }
+
+    /// Returns the `(width, height)` of the supported resolution `index`.
+    pub fn supported_resolution(index: u32) -> Result<(u32, u32), ErrorCode> {
+        S::command(DRIVER_NUM, command::SUPPORTED_RESOLUTION, index, 0).to_result()
+    }
+
+    /// Returns the current `(width, height)` of the screen in pixels, which
+    /// accounts for the rotation.
+    pub fn resolution() -> Result<(u32, u32), ErrorCode> {
+        Self::command_sync(command::GET_RESOLUTION, 0, 0)
+    }
+
+    pub fn set_resolution(width: u32, height: u32) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_RESOLUTION, width, height).map(|_| ())
+    }
+
+    /// Returns the number of pixel formats the screen supports.
+    pub fn supported_pixel_formats() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::SUPPORTED_PIXEL_FORMATS, 0, 0).to_result()
+    }
+
+    /// Returns the supported pixel format `index`.
+    pub fn supported_pixel_format(index: u32) -> Result<PixelFormat, ErrorCode> {
+        S::command(DRIVER_NUM, command::SUPPORTED_PIXEL_FORMAT, index, 0)
+            .to_result()
+            .and_then(PixelFormat::from_u32)
+    }
+
+    pub fn pixel_format() -> Result<PixelFormat, ErrorCode> {
+        Self::command_sync(command::GET_PIXEL_FORMAT, 0, 0)
+            .and_then(|(format, _)| PixelFormat::from_u32(format))
+    }
+
+    pub fn set_pixel_format(format: PixelFormat) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_PIXEL_FORMAT, format as u32, 0).map(|_| ())
+    }
+
+    pub fn rotation() -> Result<Rotation, ErrorCode> {
+        Self::command_sync(command::GET_ROTATION, 0, 0)
+            .and_then(|(rotation, _)| Rotation::from_u32(rotation))
+    }
+
+    pub fn set_rotation(rotation: Rotation) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_ROTATION, rotation as u32, 0).map(|_| ())
+    }
+
+    /// Writes `data` to `region` of the screen. The data is in the current
+    /// pixel format, row by row from the region's top left corner.
+    pub fn write(region: Region, data: &[u8]) -> Result<(), ErrorCode> {
+        Self::write_buffer(command::WRITE, region, data)
+    }
+
+    /// Fills `region` of the screen with `color`, a single pixel in the
+    /// current pixel format.
+    pub fn fill(region: Region, color: &[u8]) -> Result<(), ErrorCode> {
+        Self::write_buffer(command::FILL, region, color)
+    }
+}
+
+/// A rectangular area of the screen, in pixels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// The format of the pixel data written to the screen.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PixelFormat {
+    /// 1 bit per pixel, 8 pixels per byte.
+    Mono = 0,
+    /// 8 bits per pixel: 2 bits of red, 3 of green and 3 of blue.
+    Rgb233 = 1,
+    /// 16 bits per pixel: 5 bits of red, 6 of green and 5 of blue.
+    Rgb565 = 2,
+    /// 24 bits per pixel.
+    Rgb888 = 3,
+    /// 32 bits per pixel, with an alpha channel.
+    Argb8888 = 4,
+}
+
+impl PixelFormat {
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Mono => 1,
+            PixelFormat::Rgb233 => 8,
+            PixelFormat::Rgb565 => 16,
+            PixelFormat::Rgb888 => 24,
+            PixelFormat::Argb8888 => 32,
+        }
+    }
+
+    fn from_u32(format: u32) -> Result<PixelFormat, ErrorCode> {
+        match format {
+            0 => Ok(PixelFormat::Mono),
+            1 => Ok(PixelFormat::Rgb233),
+            2 => Ok(PixelFormat::Rgb565),
+            3 => Ok(PixelFormat::Rgb888),
+            4 => Ok(PixelFormat::Argb8888),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+/// The clockwise rotation of the screen's contents.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rotation {
+    Normal = 0,
+    Rotated90 = 1,
+    Rotated180 = 2,
+    Rotated270 = 3,
+}
+
+impl Rotation {
+    fn from_u32(rotation: u32) -> Result<Rotation, ErrorCode> {
+        match rotation {
+            0 => Ok(Rotation::Normal),
+            1 => Ok(Rotation::Rotated90),
+            2 => Ok(Rotation::Rotated180),
+            3 => Ok(Rotation::Rotated270),
+            _ => Err(ErrorCode::Fail),
+        }
+    }
+}
+
+/// System call configuration trait for `Screen`.
+pub trait Config: platform::allow_ro::Config + platform::subscribe::Config {}
+impl<T: platform::allow_ro::Config + platform::subscribe::Config> Config for T {}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> Screen<S, C> {
+    // Runs a command that completes with an upcall, and waits for it. Returns
+    // the values passed to the upcall.
+    fn command_sync(
+        command_id: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let done: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::run_command(&done, command_id, argument0, argument1)
+        })
+    }
+
+    // Sets the write frame to `region`, then runs the `WRITE` or `FILL`
+    // command with `data` shared.
+    fn write_buffer(command_id: u32, region: Region, data: &[u8]) -> Result<(), ErrorCode> {
+        let done: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::BUFFER }>(allow_ro, data)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::run_command(
+                &done,
+                command::SET_WRITE_FRAME,
+                (region.x as u32) << 16 | region.y as u32,
+                (region.width as u32) << 16 | region.height as u32,
+            )?;
+            Self::run_command(&done, command_id, data.len() as u32, 0).map(|_| ())
+        })
+    }
+
+    // Runs a command and yields until `done` is set by its upcall, which passes
+    // the status and up to two values. The caller must have subscribed `done`
+    // to the `DONE` upcall.
+    fn run_command(
+        done: &Cell<Option<(u32, u32, u32)>>,
+        command_id: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        done.set(None);
+        S::command(DRIVER_NUM, command_id, argument0, argument1).to_result()?;
+        loop {
+            S::yield_wait();
+            if let Some((status, value0, value1)) = done.get() {
+                return match status {
+                    0 => Ok((value0, value1)),
+                    _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_POWER: u32 = 2;
+    pub const SET_BRIGHTNESS: u32 = 3;
+    pub const INVERT_ON: u32 = 4;
+    pub const INVERT_OFF: u32 = 5;
+    pub const SUPPORTED_RESOLUTIONS: u32 = 11;
+    pub const SUPPORTED_RESOLUTION: u32 = 12;
+    pub const SUPPORTED_PIXEL_FORMATS: u32 = 13;
+    pub const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+    pub const GET_ROTATION: u32 = 21;
+    pub const SET_ROTATION: u32 = 22;
+    pub const GET_RESOLUTION: u32 = 23;
+    pub const SET_RESOLUTION: u32 = 24;
+    pub const GET_PIXEL_FORMAT: u32 = 25;
+    pub const SET_PIXEL_FORMAT: u32 = 26;
+    pub const SET_WRITE_FRAME: u32 = 100;
+    pub const WRITE: u32 = 200;
+    pub const FILL: u32 = 300;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const BUFFER: u32 = 0;
+}
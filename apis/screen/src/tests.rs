@@ -0,0 +1,146 @@
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics_core::pixelcolor::{Rgb565, RgbColor};
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Pixel;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::{PixelFormat, Region, Rotation};
+
+type Screen = super::Screen<fake::Syscalls>;
+type ScreenTarget = super::ScreenTarget<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Screen::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(4, 3);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::exists(), Ok(()));
+}
+
+#[test]
+fn power_and_brightness() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(4, 3);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::set_power(true), Ok(()));
+    assert!(driver.is_on());
+    assert_eq!(Screen::set_brightness(1000), Ok(()));
+    assert_eq!(driver.brightness(), 1000);
+    assert_eq!(Screen::set_invert(true), Ok(()));
+    assert!(driver.is_inverted());
+    assert_eq!(Screen::set_invert(false), Ok(()));
+    assert!(!driver.is_inverted());
+    assert_eq!(Screen::set_power(false), Ok(()));
+    assert!(!driver.is_on());
+}
+
+#[test]
+fn configuration() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(4, 3);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::supported_resolutions(), Ok(1));
+    assert_eq!(Screen::supported_resolution(0), Ok((4, 3)));
+    assert_eq!(Screen::supported_resolution(1), Err(ErrorCode::Invalid));
+    assert_eq!(Screen::resolution(), Ok((4, 3)));
+    assert_eq!(Screen::set_resolution(4, 3), Ok(()));
+    assert_eq!(Screen::set_resolution(5, 3), Err(ErrorCode::NoSupport));
+
+    assert_eq!(Screen::pixel_format(), Ok(PixelFormat::Rgb565));
+    assert_eq!(Screen::supported_pixel_formats(), Ok(4));
+    assert_eq!(Screen::supported_pixel_format(2), Ok(PixelFormat::Rgb888));
+    assert_eq!(
+        Screen::set_pixel_format(PixelFormat::Mono),
+        Err(ErrorCode::NoSupport)
+    );
+    assert_eq!(Screen::set_pixel_format(PixelFormat::Argb8888), Ok(()));
+    assert_eq!(Screen::pixel_format(), Ok(PixelFormat::Argb8888));
+    assert_eq!(PixelFormat::Argb8888.bits_per_pixel(), 32);
+
+    assert_eq!(Screen::rotation(), Ok(Rotation::Normal));
+    assert_eq!(Screen::set_rotation(Rotation::Rotated270), Ok(()));
+    assert_eq!(Screen::rotation(), Ok(Rotation::Rotated270));
+    assert_eq!(Screen::resolution(), Ok((3, 4)));
+}
+
+#[test]
+fn write_and_fill() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(3, 2);
+    kernel.add_driver(&driver);
+
+    let region = Region {
+        x: 1,
+        y: 0,
+        width: 2,
+        height: 2,
+    };
+    assert_eq!(Screen::write(region, &[1, 1, 2, 2, 3, 3, 4, 4]), Ok(()));
+    let single = Region {
+        x: 0,
+        y: 1,
+        width: 1,
+        height: 1,
+    };
+    assert_eq!(Screen::fill(single, &[9, 9]), Ok(()));
+    #[rustfmt::skip]
+    assert_eq!(
+        driver.snapshot(),
+        [
+            0, 0, 1, 1, 2, 2,
+            9, 9, 3, 3, 4, 4,
+        ]
+    );
+
+    let outside = Region {
+        x: 2,
+        y: 0,
+        width: 2,
+        height: 1,
+    };
+    assert_eq!(Screen::fill(outside, &[9, 9]), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn draw_target() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(4, 3);
+    kernel.add_driver(&driver);
+
+    let mut target = ScreenTarget::new().unwrap();
+    assert_eq!(target.size(), Size::new(4, 3));
+
+    // Clipped to the screen.
+    let area = Rectangle::new(Point::new(2, 1), Size::new(5, 5));
+    assert_eq!(target.fill_solid(&area, Rgb565::RED), Ok(()));
+    let pixels = [
+        Pixel(Point::new(0, 0), Rgb565::GREEN),
+        Pixel(Point::new(-1, 0), Rgb565::GREEN),
+        Pixel(Point::new(4, 0), Rgb565::GREEN),
+    ];
+    assert_eq!(target.draw_iter(pixels), Ok(()));
+    let area = Rectangle::new(Point::new(0, 1), Size::new(2, 2));
+    let colors = [Rgb565::BLUE, Rgb565::WHITE, Rgb565::WHITE];
+    assert_eq!(target.fill_contiguous(&area, colors), Ok(()));
+
+    let (r, g, b, w, k) = ([0xF8, 0], [0x07, 0xE0], [0, 0x1F], [0xFF, 0xFF], [0, 0]);
+    let expected = [[g, k, k, k], [b, w, r, r], [w, k, r, r]];
+    assert_eq!(driver.snapshot(), expected.concat().concat());
+
+    assert_eq!(target.clear(Rgb565::BLACK), Ok(()));
+    assert!(driver.snapshot().iter().all(|&byte| byte == 0));
+
+    assert_eq!(Screen::set_pixel_format(PixelFormat::Rgb888), Ok(()));
+    assert_eq!(ScreenTarget::new().map(|_| ()), Err(ErrorCode::NoSupport));
+}
@@ -0,0 +1,65 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The servo driver, for hobby servo motors driven by a PWM signal.
+///
+/// # Example
+/// ```ignore
+/// use libtock::servo::Servo;
+///
+/// // Move servo 0 to its middle position.
+/// let _ = Servo::set_angle(0, 90);
+/// ```
+pub struct Servo<S: Syscalls>(S);
+
+impl<S: Syscalls> Servo<S> {
+    /// Run a check against the servo capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the number of servos.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, SERVO_COUNT, 0, 0).to_result()
+    }
+
+    /// Moves `servo` to `angle`, in degrees. Returns `Err(ErrorCode::Invalid)`
+    /// if `angle` is greater than `MAX_ANGLE`.
+    pub fn set_angle(servo: u32, angle: u32) -> Result<(), ErrorCode> {
+        if angle > MAX_ANGLE {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(DRIVER_NUM, SET_ANGLE, servo, angle).to_result()
+    }
+
+    /// Returns the angle `servo` was last moved to, in degrees.
+    pub fn get_angle(servo: u32) -> Result<u32, ErrorCode> {
+        let angle = S::command(DRIVER_NUM, GET_ANGLE, servo, 0).to_result()?;
+        if angle > MAX_ANGLE {
+            return Err(ErrorCode::Fail);
+        }
+        Ok(angle)
+    }
+}
+
+/// The largest angle, in degrees, a servo can be moved to.
+pub const MAX_ANGLE: u32 = 180;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90009;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SERVO_COUNT: u32 = 1;
+const SET_ANGLE: u32 = 2;
+const GET_ANGLE: u32 = 3;
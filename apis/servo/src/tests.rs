@@ -0,0 +1,50 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Servo = super::Servo<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Servo::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::exists(), Ok(()));
+    assert_eq!(Servo::count(), Ok(2));
+}
+
+#[test]
+fn set_angle() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::set_angle(1, 45), Ok(()));
+    assert_eq!(driver.angle(1), Some(45));
+    assert_eq!(driver.angle(0), None);
+    assert_eq!(Servo::set_angle(1, 180), Ok(()));
+    assert_eq!(driver.angle(1), Some(180));
+
+    assert_eq!(Servo::set_angle(1, 181), Err(ErrorCode::Invalid));
+    assert_eq!(Servo::set_angle(2, 90), Err(ErrorCode::NoDevice));
+    assert_eq!(driver.angle(1), Some(180));
+}
+
+#[test]
+fn get_angle() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<2>::new();
+    kernel.add_driver(&driver);
+
+    // A servo that was never moved has no known angle.
+    assert_eq!(Servo::get_angle(0), Err(ErrorCode::NoDevice));
+    assert_eq!(Servo::set_angle(0, 90), Ok(()));
+    assert_eq!(Servo::get_angle(0), Ok(90));
+    assert_eq!(Servo::get_angle(2), Err(ErrorCode::NoDevice));
+}
@@ -0,0 +1,153 @@
+#![no_std]
+
+use core::ops::BitOr;
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The seven-segment display driver.
+///
+/// Digits are numbered from 0 at the left of the display.
+///
+/// # Example
+/// ```ignore
+/// use libtock::seven_segment::{Segments, SevenSegment};
+///
+/// let _ = SevenSegment::print_number(-42);
+/// let _ = SevenSegment::set_segments(0, Segments::G | Segments::DP);
+/// ```
+pub struct SevenSegment<S: Syscalls>(S);
+
+impl<S: Syscalls> SevenSegment<S> {
+    /// Run a check against the seven-segment capsule to ensure it is present.
+    ///
+    /// Returns `Ok(number_of_digits)` if the driver was present. This does not
+    /// necessarily mean that the driver is working.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, DIGITS_COUNT, 0, 0).to_result()
+    }
+
+    /// Lights exactly the given segments of `digit`.
+    pub fn set_segments(digit: u32, segments: Segments) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, PRINT_CUSTOM, digit, segments.0 as u32).to_result()
+    }
+
+    /// Displays a hexadecimal digit (0 to 15) on `digit`. Returns `Invalid` if
+    /// `value` is out of range.
+    pub fn set_digit(digit: u32, value: u8) -> Result<(), ErrorCode> {
+        if value > 15 {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(DRIVER_NUM, PRINT_DIGIT, digit, value as u32).to_result()
+    }
+
+    /// Lights the decimal point of `digit`, leaving its other segments as they
+    /// are.
+    pub fn set_dot(digit: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, PRINT_DOT, digit, 0).to_result()
+    }
+
+    /// Turns off every segment of every digit.
+    pub fn clear() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, CLEAR, 0, 0).to_result()
+    }
+
+    /// Displays `value` in decimal, aligned to the right of the display.
+    /// Returns `Size` without changing the display if it has too few digits.
+    pub fn print_number(value: i32) -> Result<(), ErrorCode> {
+        Self::print(value.unsigned_abs(), 10, value < 0)
+    }
+
+    /// Displays `value` in hexadecimal, aligned to the right of the display.
+    /// Returns `Size` without changing the display if it has too few digits.
+    pub fn print_hex(value: u32) -> Result<(), ErrorCode> {
+        Self::print(value, 16, false)
+    }
+
+    fn print(magnitude: u32, radix: u32, negative: bool) -> Result<(), ErrorCode> {
+        let count = Self::count()?;
+        let mut width = negative as u32 + 1;
+        let mut remaining = magnitude / radix;
+        while remaining != 0 {
+            width += 1;
+            remaining /= radix;
+        }
+        if width > count {
+            return Err(ErrorCode::Size);
+        }
+
+        let mut remaining = magnitude;
+        for position in (0..count).rev() {
+            let digits_printed = count - 1 - position;
+            let segments = if digits_printed == 0 || remaining != 0 {
+                let segments = Segments::hex_digit((remaining % radix) as u8);
+                remaining /= radix;
+                segments.unwrap_or(Segments::BLANK)
+            } else if negative && digits_printed == width - 1 {
+                Segments::MINUS
+            } else {
+                Segments::BLANK
+            };
+            Self::set_segments(position, segments)?;
+        }
+        Ok(())
+    }
+}
+
+/// A set of segments of a single digit. Bits 0 to 6 are segments A to G, and
+/// bit 7 is the decimal point.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Segments(pub u8);
+
+impl Segments {
+    pub const BLANK: Segments = Segments(0);
+    pub const A: Segments = Segments(1 << 0);
+    pub const B: Segments = Segments(1 << 1);
+    pub const C: Segments = Segments(1 << 2);
+    pub const D: Segments = Segments(1 << 3);
+    pub const E: Segments = Segments(1 << 4);
+    pub const F: Segments = Segments(1 << 5);
+    pub const G: Segments = Segments(1 << 6);
+    pub const DP: Segments = Segments(1 << 7);
+    pub const MINUS: Segments = Segments::G;
+
+    /// Returns the segments that display the hexadecimal digit `value`, or
+    /// `None` if `value` is greater than 15.
+    pub fn hex_digit(value: u8) -> Option<Segments> {
+        HEX_DIGITS.get(value as usize).map(|&bits| Segments(bits))
+    }
+
+    pub fn contains(self, other: Segments) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Segments {
+    type Output = Segments;
+
+    fn bitor(self, rhs: Segments) -> Segments {
+        Segments(self.0 | rhs.0)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const HEX_DIGITS: [u8; 16] = [
+    0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f, 0x77, 0x7c, 0x39, 0x5e, 0x79, 0x71,
+];
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90004;
+
+// Command IDs
+const DIGITS_COUNT: u32 = 0;
+const PRINT_DIGIT: u32 = 1;
+const CLEAR: u32 = 2;
+const PRINT_DOT: u32 = 3;
+const PRINT_CUSTOM: u32 = 4;
@@ -0,0 +1,125 @@
+use crate::Segments;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type SevenSegment = super::SevenSegment<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(SevenSegment::count(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn driver_check() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::count(), Ok(4));
+    assert_eq!(driver.text(), "    ");
+}
+
+#[test]
+fn segments() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    let segments = Segments::B | Segments::C | Segments::DP;
+    assert!(segments.contains(Segments::B | Segments::C));
+    assert!(!segments.contains(Segments::A));
+    assert_eq!(SevenSegment::set_segments(1, segments), Ok(()));
+    assert_eq!(driver.get_segments(1), Some(0x86));
+    assert_eq!(driver.text(), " 1.  ");
+    assert_eq!(
+        SevenSegment::set_segments(4, segments),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn set_digit() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    for value in 0..16 {
+        assert_eq!(SevenSegment::set_digit(0, value), Ok(()));
+        assert_eq!(
+            driver.text().chars().next(),
+            char::from_digit(value as u32, 16)
+        );
+    }
+    assert_eq!(SevenSegment::set_digit(0, 16), Err(ErrorCode::Invalid));
+    assert_eq!(SevenSegment::set_digit(4, 0), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn set_dot() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::set_digit(2, 7), Ok(()));
+    assert_eq!(SevenSegment::set_dot(2), Ok(()));
+    assert_eq!(driver.text(), "  7. ");
+    assert_eq!(SevenSegment::set_dot(4), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn clear() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::print_number(1234), Ok(()));
+    assert_eq!(SevenSegment::clear(), Ok(()));
+    assert_eq!(driver.text(), "    ");
+}
+
+#[test]
+fn print_number() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::print_number(0), Ok(()));
+    assert_eq!(driver.text(), "   0");
+    assert_eq!(SevenSegment::print_number(1024), Ok(()));
+    assert_eq!(driver.text(), "1024");
+    assert_eq!(SevenSegment::print_number(-42), Ok(()));
+    assert_eq!(driver.text(), " -42");
+    assert_eq!(SevenSegment::print_number(-999), Ok(()));
+    assert_eq!(driver.text(), "-999");
+
+    // Numbers that do not fit leave the display unchanged.
+    assert_eq!(SevenSegment::print_number(10000), Err(ErrorCode::Size));
+    assert_eq!(SevenSegment::print_number(-1000), Err(ErrorCode::Size));
+    assert_eq!(driver.text(), "-999");
+}
+
+#[test]
+fn print_number_extremes() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<11>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::print_number(i32::MIN), Ok(()));
+    assert_eq!(driver.text(), "-2147483648");
+    assert_eq!(SevenSegment::print_number(i32::MAX), Ok(()));
+    assert_eq!(driver.text(), " 2147483647");
+}
+
+#[test]
+fn print_hex() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SevenSegment::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SevenSegment::print_hex(0xbeef), Ok(()));
+    assert_eq!(driver.text(), "beef");
+    assert_eq!(SevenSegment::print_hex(0xa), Ok(()));
+    assert_eq!(driver.text(), "   a");
+    assert_eq!(SevenSegment::print_hex(0x10000), Err(ErrorCode::Size));
+}
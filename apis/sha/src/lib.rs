@@ -0,0 +1,188 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The SHA-2 digest driver.
+///
+/// A digest is computed by selecting an algorithm, adding the message with
+/// any number of `update` calls, and reading the digest with `finish`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::sha::{Sha, ShaAlgorithm};
+///
+/// let mut digest = [0; 32];
+/// Sha::set_algorithm(ShaAlgorithm::Sha256)?;
+/// Sha::update(b"hello ")?;
+/// Sha::update(b"world")?;
+/// Sha::finish(&mut digest)?;
+/// ```
+pub struct Sha<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> Sha<S, C> {
+    /// Run a check against the SHA capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Selects the algorithm of the next digest. Any data added since the
+    /// last `finish` is discarded.
+    pub fn set_algorithm(algorithm: ShaAlgorithm) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as u32, 0).to_result()
+    }
+
+    /// Adds `data` to the message being digested.
+    pub fn update(data: &[u8]) -> Result<(), ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::run_command(&done, command::UPDATE)
+        })
+    }
+
+    /// Writes the digest of the message into the start of `digest`, which
+    /// must hold at least `digest_len()` bytes of the selected algorithm. The
+    /// next message starts empty, with the same algorithm.
+    pub fn finish(digest: &mut [u8]) -> Result<(), ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::DIGEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DIGEST }>(allow_rw, digest)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::run_command(&done, command::FINISH)
+        })
+    }
+
+    /// Computes the digest of `data` with `algorithm` in a single operation.
+    pub fn digest(
+        algorithm: ShaAlgorithm,
+        data: &[u8],
+        digest: &mut [u8],
+    ) -> Result<(), ErrorCode> {
+        Self::set_algorithm(algorithm)?;
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::DIGEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::DIGEST }>(allow_rw, digest)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::run_command(&done, command::RUN)
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ShaAlgorithm {
+    Sha256 = 0,
+    Sha384 = 1,
+    Sha512 = 2,
+}
+
+impl ShaAlgorithm {
+    /// The length of the digest, in bytes.
+    pub const fn digest_len(self) -> usize {
+        match self {
+            ShaAlgorithm::Sha256 => 32,
+            ShaAlgorithm::Sha384 => 48,
+            ShaAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+/// The length of the longest digest, which is large enough for any
+/// algorithm.
+pub const MAX_DIGEST_LEN: usize = 64;
+
+/// System call configuration trait for `Sha`.
+pub trait Config:
+    platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
+{
+}
+impl<T: platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config>
+    Config for T
+{
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> Sha<S, C> {
+    // Runs a command and yields until `done` is set by its upcall. The caller
+    // must have subscribed `done` to the `DONE` upcall.
+    fn run_command(done: &Cell<Option<(u32,)>>, command_id: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command_id, 0, 0).to_result()?;
+        loop {
+            S::yield_wait();
+            if let Some((status,)) = done.get() {
+                return match status {
+                    0 => Ok(()),
+                    _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const RUN: u32 = 2;
+    pub const UPDATE: u32 = 3;
+    pub const FINISH: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const DATA: u32 = 0;
+}
+
+mod allow_rw {
+    pub const DIGEST: u32 = 0;
+}
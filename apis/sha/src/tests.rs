@@ -0,0 +1,97 @@
+use crate::{ShaAlgorithm, MAX_DIGEST_LEN};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Sha = super::Sha<fake::Syscalls>;
+
+// SHA-256("abc"), from FIPS 180-2.
+const SHA256_ABC: [u8; 32] = [
+    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Sha::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Sha::exists(), Ok(()));
+}
+
+#[test]
+fn streaming() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    let mut digest = [0; 32];
+    assert_eq!(Sha::set_algorithm(ShaAlgorithm::Sha256), Ok(()));
+    assert_eq!(Sha::update(b"a"), Ok(()));
+    assert_eq!(Sha::update(b""), Ok(()));
+    assert_eq!(Sha::update(b"bc"), Ok(()));
+    assert_eq!(driver.message(), b"abc");
+    assert_eq!(Sha::finish(&mut digest), Ok(()));
+    assert_eq!(digest, SHA256_ABC);
+
+    // The next digest starts with an empty message.
+    assert_eq!(Sha::update(b"abc"), Ok(()));
+    assert_eq!(Sha::finish(&mut digest), Ok(()));
+    assert_eq!(digest, SHA256_ABC);
+}
+
+#[test]
+fn digest() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    let mut digest = [0; MAX_DIGEST_LEN];
+    assert_eq!(
+        Sha::digest(ShaAlgorithm::Sha256, b"abc", &mut digest),
+        Ok(())
+    );
+    assert_eq!(digest[..32], SHA256_ABC);
+
+    // Only the first bytes of the SHA-384 and SHA-512 digests are checked.
+    assert_eq!(
+        Sha::digest(ShaAlgorithm::Sha384, b"abc", &mut digest),
+        Ok(())
+    );
+    assert_eq!(
+        digest[..8],
+        [0xcb, 0x00, 0x75, 0x3f, 0x45, 0xa3, 0x5e, 0x8b]
+    );
+    assert_eq!(digest[47], 0xa7);
+    assert_eq!(
+        Sha::digest(ShaAlgorithm::Sha512, b"abc", &mut digest),
+        Ok(())
+    );
+    assert_eq!(
+        digest[..8],
+        [0xdd, 0xaf, 0x35, 0xa1, 0x93, 0x61, 0x7a, 0xba]
+    );
+    assert_eq!(digest[63], 0x9f);
+}
+
+#[test]
+fn errors() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    // No algorithm was selected.
+    assert_eq!(Sha::update(b"abc"), Err(ErrorCode::Invalid));
+    let mut digest = [0; 32];
+    assert_eq!(
+        Sha::digest(ShaAlgorithm::Sha384, b"abc", &mut digest),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(ShaAlgorithm::Sha384.digest_len(), 48);
+}
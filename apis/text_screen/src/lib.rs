@@ -0,0 +1,205 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use libtock_platform as platform;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The text screen driver, for character displays such as HD44780 LCDs.
+///
+/// Every operation waits until the display has completed it.
+///
+/// # Example
+/// ```ignore
+/// use libtock::text_screen::TextScreen;
+/// use ufmt::uwrite;
+///
+/// TextScreen::display_on()?;
+/// TextScreen::clear()?;
+/// TextScreen::set_cursor(0, 1)?;
+/// let mut writer = TextScreen::writer();
+/// uwrite!(writer, "T = {}", 21).unwrap();
+/// ```
+pub struct TextScreen<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> TextScreen<S, C> {
+    /// Run a check against the text screen capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the size of the display as `(columns, rows)`.
+    pub fn get_size() -> Result<(u32, u32), ErrorCode> {
+        Self::command_sync(command::GET_SIZE, 0, 0)
+    }
+
+    pub fn display_on() -> Result<(), ErrorCode> {
+        Self::command_sync(command::DISPLAY_ON, 0, 0).map(|_| ())
+    }
+
+    pub fn display_off() -> Result<(), ErrorCode> {
+        Self::command_sync(command::DISPLAY_OFF, 0, 0).map(|_| ())
+    }
+
+    /// Makes the character under the cursor blink.
+    pub fn blink_on() -> Result<(), ErrorCode> {
+        Self::command_sync(command::BLINK_ON, 0, 0).map(|_| ())
+    }
+
+    pub fn blink_off() -> Result<(), ErrorCode> {
+        Self::command_sync(command::BLINK_OFF, 0, 0).map(|_| ())
+    }
+
+    /// Shows an underline cursor at the cursor position.
+    pub fn show_cursor() -> Result<(), ErrorCode> {
+        Self::command_sync(command::SHOW_CURSOR, 0, 0).map(|_| ())
+    }
+
+    pub fn hide_cursor() -> Result<(), ErrorCode> {
+        Self::command_sync(command::HIDE_CURSOR, 0, 0).map(|_| ())
+    }
+
+    /// Clears the display and moves the cursor to the top left corner.
+    pub fn clear() -> Result<(), ErrorCode> {
+        Self::command_sync(command::CLEAR, 0, 0).map(|_| ())
+    }
+
+    /// Moves the cursor to the top left corner.
+    pub fn home() -> Result<(), ErrorCode> {
+        Self::command_sync(command::HOME, 0, 0).map(|_| ())
+    }
+
+    /// Moves the cursor to `column` of `row`, both starting at 0.
+    pub fn set_cursor(column: u32, row: u32) -> Result<(), ErrorCode> {
+        Self::command_sync(command::SET_CURSOR, column, row).map(|_| ())
+    }
+
+    /// Writes `s` at the cursor position, and moves the cursor after it.
+    pub fn write(s: &[u8]) -> Result<(), ErrorCode> {
+        let called: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<C, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, s)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &called)?;
+            Self::wait_for_command(&called, command::WRITE, s.len() as u32, 0)
+        })
+        .map(|_| ())
+    }
+
+    pub fn writer() -> TextScreenWriter<S, C> {
+        TextScreenWriter {
+            syscalls: Default::default(),
+        }
+    }
+}
+
+/// A writer that writes text at the cursor position of the display. It
+/// implements both `core::fmt::Write` and `ufmt::uWrite`.
+pub struct TextScreenWriter<S: Syscalls, C: Config = DefaultConfig> {
+    syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> fmt::Write for TextScreenWriter<S, C> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        TextScreen::<S, C>::write(s.as_bytes()).map_err(|_e| fmt::Error)
+    }
+}
+
+impl<S: Syscalls, C: Config> ufmt_write::uWrite for TextScreenWriter<S, C> {
+    type Error = ErrorCode;
+
+    fn write_str(&mut self, s: &str) -> Result<(), ErrorCode> {
+        TextScreen::<S, C>::write(s.as_bytes())
+    }
+}
+
+/// System call configuration trait for `TextScreen`.
+pub trait Config: platform::allow_ro::Config + platform::subscribe::Config {}
+impl<T: platform::allow_ro::Config + platform::subscribe::Config> Config for T {}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+impl<S: Syscalls, C: Config> TextScreen<S, C> {
+    // Runs a command and waits for its upcall. Returns the upcall's two data
+    // arguments.
+    fn command_sync(
+        command_id: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        let called: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &called)?;
+            Self::wait_for_command(&called, command_id, argument0, argument1)
+        })
+    }
+
+    // Runs a command and yields until `called` is set by its upcall. The
+    // caller must have subscribed `called` to the `DONE` upcall.
+    fn wait_for_command(
+        called: &Cell<Option<(u32, u32, u32)>>,
+        command_id: u32,
+        argument0: u32,
+        argument1: u32,
+    ) -> Result<(u32, u32), ErrorCode> {
+        S::command(DRIVER_NUM, command_id, argument0, argument1).to_result()?;
+        loop {
+            S::yield_wait();
+            if let Some((status, data0, data1)) = called.get() {
+                return match status {
+                    0 => Ok((data0, data1)),
+                    _ => Err(status.try_into().unwrap_or(ErrorCode::Fail)),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_SIZE: u32 = 1;
+    pub const DISPLAY_ON: u32 = 2;
+    pub const DISPLAY_OFF: u32 = 3;
+    pub const BLINK_ON: u32 = 4;
+    pub const BLINK_OFF: u32 = 5;
+    pub const SHOW_CURSOR: u32 = 6;
+    pub const HIDE_CURSOR: u32 = 7;
+    pub const WRITE: u32 = 8;
+    pub const CLEAR: u32 = 9;
+    pub const HOME: u32 = 10;
+    pub const SET_CURSOR: u32 = 11;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
@@ -0,0 +1,75 @@
+use core::fmt::Write;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type TextScreen = super::TextScreen<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(TextScreen::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::exists(), Ok(()));
+    assert_eq!(TextScreen::get_size(), Ok((16, 2)));
+}
+
+#[test]
+fn display_settings() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::display_on(), Ok(()));
+    assert!(driver.is_display_on());
+    assert_eq!(TextScreen::blink_on(), Ok(()));
+    assert!(driver.is_blinking());
+    assert_eq!(TextScreen::show_cursor(), Ok(()));
+    assert!(driver.is_cursor_visible());
+
+    assert_eq!(TextScreen::hide_cursor(), Ok(()));
+    assert!(!driver.is_cursor_visible());
+    assert_eq!(TextScreen::blink_off(), Ok(()));
+    assert!(!driver.is_blinking());
+    assert_eq!(TextScreen::display_off(), Ok(()));
+    assert!(!driver.is_display_on());
+}
+
+#[test]
+fn cursor() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::set_cursor(5, 1), Ok(()));
+    assert_eq!(driver.cursor(), (5, 1));
+    assert_eq!(TextScreen::set_cursor(0, 2), Err(ErrorCode::Invalid));
+    assert_eq!(TextScreen::set_cursor(16, 0), Err(ErrorCode::Invalid));
+    assert_eq!(driver.cursor(), (5, 1));
+    assert_eq!(TextScreen::home(), Ok(()));
+    assert_eq!(driver.cursor(), (0, 0));
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(8, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::write(b"Hello"), Ok(()));
+    assert_eq!(TextScreen::set_cursor(2, 1), Ok(()));
+    let mut writer = TextScreen::writer();
+    write!(writer, "{}C", 21).unwrap();
+    assert_eq!(driver.rows(), ["Hello   ", "  21C   "]);
+
+    assert_eq!(TextScreen::clear(), Ok(()));
+    ufmt::uwrite!(writer, "{}%", 40u8).unwrap();
+    assert_eq!(driver.rows(), ["40%     ", "        "]);
+    assert_eq!(driver.cursor(), (3, 0));
+}
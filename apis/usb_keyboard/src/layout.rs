@@ -0,0 +1,71 @@
+use crate::{KeyStroke, Modifiers};
+
+/// Maps characters to the key strokes that type them, which depends on the
+/// keyboard layout the host is configured with.
+pub trait KeyboardLayout {
+    /// Returns the key stroke that types `c`, or `None` if the layout has no
+    /// key for it.
+    fn key_stroke(&self, c: char) -> Option<KeyStroke>;
+}
+
+/// The US English QWERTY layout.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UsQwerty;
+
+impl KeyboardLayout for UsQwerty {
+    fn key_stroke(&self, c: char) -> Option<KeyStroke> {
+        let (shift, usage) = match c {
+            'a'..='z' => (false, 0x04 + (c as u8 - b'a')),
+            'A'..='Z' => (true, 0x04 + (c as u8 - b'A')),
+            '1'..='9' => (false, 0x1e + (c as u8 - b'1')),
+            '0' => (false, 0x27),
+            '\n' => (false, 0x28),
+            '\x1b' => (false, 0x29),
+            '\x08' => (false, 0x2a),
+            '\t' => (false, 0x2b),
+            ' ' => (false, 0x2c),
+            _ => match SHIFTED_DIGITS.find(c) {
+                // All the characters are ASCII, so byte indices are character
+                // indices.
+                Some(index) => (true, 0x1e + index as u8),
+                None => SYMBOLS.iter().find_map(|&(unshifted, shifted, usage)| {
+                    if c == unshifted {
+                        Some((false, usage))
+                    } else if c == shifted {
+                        Some((true, usage))
+                    } else {
+                        None
+                    }
+                })?,
+            },
+        };
+        let modifiers = if shift {
+            Modifiers::LEFT_SHIFT
+        } else {
+            Modifiers::NONE
+        };
+        Some(KeyStroke { modifiers, usage })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+// The characters typed by the digit keys 1 to 9 and 0 with shift.
+const SHIFTED_DIGITS: &str = "!@#$%^&*()";
+
+// The symbol keys, with the characters they type without and with shift.
+const SYMBOLS: [(char, char, u8); 11] = [
+    ('-', '_', 0x2d),
+    ('=', '+', 0x2e),
+    ('[', '{', 0x2f),
+    (']', '}', 0x30),
+    ('\\', '|', 0x31),
+    (';', ':', 0x33),
+    ('\'', '"', 0x34),
+    ('`', '~', 0x35),
+    (',', '<', 0x36),
+    ('.', '>', 0x37),
+    ('/', '?', 0x38),
+];
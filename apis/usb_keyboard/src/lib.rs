@@ -0,0 +1,185 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform as platform;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod layout;
+
+pub use layout::{KeyboardLayout, UsQwerty};
+
+/// The USB HID keyboard driver, which makes the board appear to the USB host
+/// as a keyboard.
+///
+/// # Example
+/// ```ignore
+/// use libtock::usb_keyboard::{UsQwerty, UsbKeyboard};
+///
+/// UsbKeyboard::type_str("Hello, world!\n", &UsQwerty)?;
+/// ```
+pub struct UsbKeyboard<S: Syscalls, C: Config = DefaultConfig>(S, C);
+
+impl<S: Syscalls, C: Config> UsbKeyboard<S, C> {
+    /// Run a check against the USB keyboard capsule to ensure it is present.
+    ///
+    /// Returns `Ok(())` if the driver was present. This does not necessarily
+    /// mean that the driver is working.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Sends `report` to the host, and waits until the capsule reports that
+    /// it is done. Returns the error the capsule reports if the report was
+    /// not sent, or `Fail` if its status is not an error code.
+    pub fn send_report(report: &KeyboardReport) -> Result<(), ErrorCode> {
+        let mut buffer = report.to_bytes();
+        let sent: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::SEND }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::SEND }>(allow_rw, &mut buffer)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::DONE }>(subscribe, &sent)?;
+            S::command(DRIVER_NUM, command::SEND, 0, 0).to_result()?;
+            loop {
+                S::yield_wait();
+                match sent.get() {
+                    None => {}
+                    Some((SENT,)) => return Ok(()),
+                    Some((status,)) => {
+                        return Err(ErrorCode::try_from(status).unwrap_or(ErrorCode::Fail))
+                    }
+                }
+            }
+        })
+    }
+
+    /// Presses and releases the keys of `stroke`.
+    pub fn send_key_stroke(stroke: KeyStroke) -> Result<(), ErrorCode> {
+        Self::send_report(&KeyboardReport::from(stroke))?;
+        Self::send_report(&KeyboardReport::RELEASED)
+    }
+
+    /// Types `text` with the key strokes given by `layout`. Returns
+    /// `Err(ErrorCode::Invalid)` without typing anything if `layout` cannot
+    /// type every character of `text`.
+    pub fn type_str<L: KeyboardLayout>(text: &str, layout: &L) -> Result<(), ErrorCode> {
+        if text.chars().any(|c| layout.key_stroke(c).is_none()) {
+            return Err(ErrorCode::Invalid);
+        }
+        for stroke in text.chars().filter_map(|c| layout.key_stroke(c)) {
+            Self::send_key_stroke(stroke)?;
+        }
+        Ok(())
+    }
+}
+
+/// The modifier keys of a `KeyboardReport`, as a bit mask.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Modifiers(pub u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const LEFT_CTRL: Modifiers = Modifiers(1 << 0);
+    pub const LEFT_SHIFT: Modifiers = Modifiers(1 << 1);
+    pub const LEFT_ALT: Modifiers = Modifiers(1 << 2);
+    pub const LEFT_GUI: Modifiers = Modifiers(1 << 3);
+    pub const RIGHT_CTRL: Modifiers = Modifiers(1 << 4);
+    pub const RIGHT_SHIFT: Modifiers = Modifiers(1 << 5);
+    pub const RIGHT_ALT: Modifiers = Modifiers(1 << 6);
+    pub const RIGHT_GUI: Modifiers = Modifiers(1 << 7);
+
+    pub const fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+    fn bitor(self, other: Modifiers) -> Modifiers {
+        Modifiers(self.0 | other.0)
+    }
+}
+
+/// A key with the modifiers held while it is pressed. `usage` is the key's
+/// usage ID in the keyboard page of the USB HID usage tables.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct KeyStroke {
+    pub modifiers: Modifiers,
+    pub usage: u8,
+}
+
+/// A report in the boot keyboard format, which lists the modifiers and up to
+/// six other keys that are currently pressed.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyboardReport {
+    pub modifiers: Modifiers,
+    /// The usage IDs of the pressed keys. Unused entries are 0.
+    pub keys: [u8; 6],
+}
+
+impl KeyboardReport {
+    /// The report sent when no key is pressed.
+    pub const RELEASED: KeyboardReport = KeyboardReport {
+        modifiers: Modifiers::NONE,
+        keys: [0; 6],
+    };
+
+    /// The report as sent to the host.
+    pub fn to_bytes(&self) -> [u8; REPORT_LEN] {
+        let mut bytes = [0; REPORT_LEN];
+        bytes[0] = self.modifiers.0;
+        bytes[2..].copy_from_slice(&self.keys);
+        bytes
+    }
+}
+
+impl From<KeyStroke> for KeyboardReport {
+    fn from(stroke: KeyStroke) -> KeyboardReport {
+        KeyboardReport {
+            modifiers: stroke.modifiers,
+            keys: [stroke.usage, 0, 0, 0, 0, 0],
+        }
+    }
+}
+
+/// The length of a boot keyboard report.
+pub const REPORT_LEN: usize = 8;
+
+/// System call configuration trait for `UsbKeyboard`.
+pub trait Config: platform::allow_rw::Config + platform::subscribe::Config {}
+impl<T: platform::allow_rw::Config + platform::subscribe::Config> Config for T {}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90005;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SEND: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const SEND: u32 = 1;
+}
+
+// The first argument of the DONE upcall when a report was sent.
+const SENT: u32 = 1;
@@ -0,0 +1,125 @@
+use crate::{KeyStroke, KeyboardLayout, KeyboardReport, Modifiers, UsQwerty};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type UsbKeyboard = super::UsbKeyboard<fake::Syscalls>;
+
+const RELEASED: [u8; 8] = [0; 8];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(UsbKeyboard::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UsbKeyboard::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(UsbKeyboard::exists(), Ok(()));
+}
+
+#[test]
+fn send_report() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UsbKeyboard::new();
+    kernel.add_driver(&driver);
+
+    let report = KeyboardReport {
+        modifiers: Modifiers::LEFT_CTRL | Modifiers::LEFT_ALT,
+        keys: [0x4c, 0, 0, 0, 0, 0],
+    };
+    assert_eq!(UsbKeyboard::send_report(&report), Ok(()));
+    assert_eq!(driver.take_reports(), [[0x05, 0, 0x4c, 0, 0, 0, 0, 0]]);
+}
+
+#[test]
+fn send_report_error() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UsbKeyboard::new();
+    kernel.add_driver(&driver);
+
+    driver.set_send_error(Some(ErrorCode::Off));
+    assert_eq!(
+        UsbKeyboard::send_report(&KeyboardReport::RELEASED),
+        Err(ErrorCode::Off)
+    );
+    assert!(driver.take_reports().is_empty());
+}
+
+#[test]
+fn send_key_stroke() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UsbKeyboard::new();
+    kernel.add_driver(&driver);
+
+    let stroke = KeyStroke {
+        modifiers: Modifiers::LEFT_GUI,
+        usage: 0x15,
+    };
+    assert_eq!(UsbKeyboard::send_key_stroke(stroke), Ok(()));
+    assert_eq!(
+        driver.take_reports(),
+        [[0x08, 0, 0x15, 0, 0, 0, 0, 0], RELEASED]
+    );
+}
+
+#[test]
+fn type_str() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UsbKeyboard::new();
+    kernel.add_driver(&driver);
+
+    // Each key is released before the next is pressed, so repeated
+    // characters are typed.
+    assert_eq!(UsbKeyboard::type_str("Hoo!\n", &UsQwerty), Ok(()));
+    assert_eq!(
+        driver.take_reports(),
+        [
+            [0x02, 0, 0x0b, 0, 0, 0, 0, 0],
+            RELEASED,
+            [0x00, 0, 0x12, 0, 0, 0, 0, 0],
+            RELEASED,
+            [0x00, 0, 0x12, 0, 0, 0, 0, 0],
+            RELEASED,
+            [0x02, 0, 0x1e, 0, 0, 0, 0, 0],
+            RELEASED,
+            [0x00, 0, 0x28, 0, 0, 0, 0, 0],
+            RELEASED,
+        ]
+    );
+
+    // Nothing is typed if a character is not in the layout.
+    assert_eq!(
+        UsbKeyboard::type_str("caf\u{e9}", &UsQwerty),
+        Err(ErrorCode::Invalid)
+    );
+    assert!(driver.take_reports().is_empty());
+}
+
+#[test]
+fn us_qwerty() {
+    let stroke = |c| {
+        UsQwerty
+            .key_stroke(c)
+            .map(|stroke| (stroke.modifiers, stroke.usage))
+    };
+    let shift = Modifiers::LEFT_SHIFT;
+    assert_eq!(stroke('a'), Some((Modifiers::NONE, 0x04)));
+    assert_eq!(stroke('Z'), Some((shift, 0x1d)));
+    assert_eq!(stroke('1'), Some((Modifiers::NONE, 0x1e)));
+    assert_eq!(stroke('0'), Some((Modifiers::NONE, 0x27)));
+    assert_eq!(stroke('@'), Some((shift, 0x1f)));
+    assert_eq!(stroke(')'), Some((shift, 0x27)));
+    assert_eq!(stroke(' '), Some((Modifiers::NONE, 0x2c)));
+    assert_eq!(stroke('\t'), Some((Modifiers::NONE, 0x2b)));
+    assert_eq!(stroke('\\'), Some((Modifiers::NONE, 0x31)));
+    assert_eq!(stroke('|'), Some((shift, 0x31)));
+    assert_eq!(stroke('"'), Some((shift, 0x34)));
+    assert_eq!(stroke('?'), Some((shift, 0x38)));
+    assert_eq!(stroke('\u{e9}'), None);
+    assert!((shift | Modifiers::RIGHT_ALT).contains(shift));
+    assert!(!Modifiers::NONE.contains(shift));
+}
@@ -4,11 +4,9 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::adc::Adc;
 use libtock::alarm::{Alarm, Milliseconds};
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 
 set_main! {main}
@@ -16,14 +14,17 @@ stack_size! {0x200}
 
 fn main() {
     if Adc::exists().is_err() {
-        writeln!(Console::writer(), "adc driver unavailable").unwrap();
+        println!("adc driver unavailable");
         return;
     }
 
     loop {
         match Adc::read_single_sample_sync() {
-            Ok(adc_val) => writeln!(Console::writer(), "Sample: {}\n", adc_val).unwrap(),
-            Err(_) => writeln!(Console::writer(), "error while reading sample",).unwrap(),
+            Ok(adc_val) => match Adc::to_millivolts(adc_val) {
+                Ok(mv) => println!("Sample: {} ({} mV)\n", adc_val, mv),
+                Err(_) => println!("Sample: {}\n", adc_val),
+            },
+            Err(_) => println!("error while reading sample"),
         }
 
         Alarm::sleep_for(Milliseconds(2000)).unwrap();
@@ -4,11 +4,9 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::alarm::{Alarm, Milliseconds};
 use libtock::ambient_light::AmbientLight;
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 
 set_main! {main}
@@ -16,19 +14,14 @@ stack_size! {0x200}
 
 fn main() {
     if AmbientLight::exists().is_err() {
-        writeln!(Console::writer(), "ambient light driver unavailable").unwrap();
+        println!("ambient light driver unavailable");
         return;
     }
 
     loop {
         match AmbientLight::read_intensity_sync() {
-            Ok(intensity_val) => writeln!(
-                Console::writer(),
-                "Light intensity: {} lux\n",
-                intensity_val
-            )
-            .unwrap(),
-            Err(_) => writeln!(Console::writer(), "error while reading light intensity",).unwrap(),
+            Ok(intensity_val) => println!("Light intensity: {} lux\n", intensity_val),
+            Err(_) => println!("error while reading light intensity"),
         }
 
         Alarm::sleep_for(Milliseconds(2000)).unwrap();
@@ -0,0 +1,34 @@
+//! An example of leveled logging. Logs through the console if it is present,
+//! and through the LowLevelDebug capsule otherwise.
+
+#![no_main]
+#![no_std]
+
+use libtock::logger::{self, Filter, LevelFilter, Logger};
+use libtock::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x400}
+
+mod sensor {
+    use libtock::logger;
+
+    pub fn read() -> u32 {
+        logger::trace!("reading sensor");
+        42
+    }
+}
+
+// Trace records from the sensor module are logged, but other modules only log
+// records up to info. Statements the logger filters out are removed from the
+// binary.
+const LOGGER: Logger = Logger::new(LevelFilter::Info)
+    .with_filters(&[Filter::new("logger::sensor", LevelFilter::Trace)]);
+
+fn main() {
+    LOGGER.init();
+    logger::info!("started");
+    let value = sensor::read();
+    logger::debug!("this record is filtered out");
+    logger::warn!("sensor value: {}", value);
+}
@@ -3,10 +3,9 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
 use core::time::Duration;
 use libtock::buzzer::{Buzzer, Note};
-use libtock::console::Console;
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 
 set_main! {main}
@@ -85,11 +84,11 @@ const WHOLE_NOTE: u32 = (60000 * 4) / TEMPO;
 
 fn main() {
     if let Err(_) = Buzzer::exists() {
-        writeln!(Console::writer(), "There is no available buzzer").unwrap();
+        println!("There is no available buzzer");
         return;
     }
 
-    writeln!(Console::writer(), "Ode to Joy").unwrap();
+    println!("Ode to Joy");
 
     for (frequency, duration) in MELODY.iter() {
         let mut note_duration: Duration =
@@ -3,11 +3,9 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::alarm::{Alarm, Milliseconds};
 use libtock::ninedof::NineDof;
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 
 set_main! {main}
@@ -15,11 +13,11 @@ stack_size! {0x2000}
 
 fn main() {
     if NineDof::exists().is_err() {
-        writeln!(Console::writer(), "NineDof driver unavailable").unwrap();
+        println!("NineDof driver unavailable");
         return;
     }
 
-    writeln!(Console::writer(), "NineDof driver available").unwrap();
+    println!("NineDof driver available");
     loop {
         let accelerometer_data = NineDof::read_accelerometer_sync();
         let magnetomer_data = NineDof::read_magnetometer_sync();
@@ -27,44 +25,23 @@ fn main() {
 
         match accelerometer_data {
             Ok(data) => {
-                writeln!(
-                    Console::writer(),
-                    "Accelerometer: x: {}, y: {}, z: {}",
-                    data.x,
-                    data.y,
-                    data.z
-                )
-                .unwrap();
+                println!("Accelerometer: x: {}, y: {}, z: {}", data.x, data.y, data.z);
             }
-            Err(_) => writeln!(Console::writer(), "error while reading accelerometer").unwrap(),
+            Err(_) => println!("error while reading accelerometer"),
         }
 
         match magnetomer_data {
             Ok(data) => {
-                writeln!(
-                    Console::writer(),
-                    "Magnetometer: x: {}, y: {}, z: {}",
-                    data.x,
-                    data.y,
-                    data.z
-                )
-                .unwrap();
+                println!("Magnetometer: x: {}, y: {}, z: {}", data.x, data.y, data.z);
             }
-            Err(_) => writeln!(Console::writer(), "error while reading magnetometer").unwrap(),
+            Err(_) => println!("error while reading magnetometer"),
         }
 
         match gyroscope_data {
             Ok(data) => {
-                writeln!(
-                    Console::writer(),
-                    "Gyroscope: x: {}, y: {}, z: {}",
-                    data.x,
-                    data.y,
-                    data.z
-                )
-                .unwrap();
+                println!("Gyroscope: x: {}, y: {}, z: {}", data.x, data.y, data.z);
             }
-            Err(_) => writeln!(Console::writer(), "error while reading gyroscope").unwrap(),
+            Err(_) => println!("error while reading gyroscope"),
         }
         Alarm::sleep_for(Milliseconds(700)).unwrap();
     }
@@ -4,10 +4,8 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::alarm::{Alarm, Milliseconds};
+use libtock::println;
 use libtock::proximity::Proximity;
 use libtock::runtime::{set_main, stack_size};
 
@@ -16,14 +14,14 @@ stack_size! {0x200}
 
 fn main() {
     if Proximity::exists().is_err() {
-        writeln!(Console::writer(), "proximity driver unavailable").unwrap();
+        println!("proximity driver unavailable");
         return;
     }
-    writeln!(Console::writer(), "proximity driver available").unwrap();
+    println!("proximity driver available");
     loop {
         match Proximity::read_sync() {
-            Ok(prox_val) => writeln!(Console::writer(), "Proximity: {}\n", prox_val).unwrap(),
-            Err(_) => writeln!(Console::writer(), "error while reading proximity",).unwrap(),
+            Ok(prox_val) => println!("Proximity: {}\n", prox_val),
+            Err(_) => println!("error while reading proximity"),
         }
 
         Alarm::sleep_for(Milliseconds(2000)).unwrap();
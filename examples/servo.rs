@@ -0,0 +1,41 @@
+//! A simple libtock-rs example. Sweeps every servo back and forth between 0
+//! and 180 degrees.
+
+#![no_main]
+#![no_std]
+
+use libtock::alarm::{Alarm, Milliseconds};
+use libtock::println;
+use libtock::runtime::{set_main, stack_size};
+use libtock::servo::{Servo, MAX_ANGLE};
+
+set_main! {main}
+stack_size! {0x200}
+
+fn main() {
+    let servos_count = match Servo::count() {
+        Ok(count) => count,
+        Err(_) => {
+            println!("servo driver unavailable");
+            return;
+        }
+    };
+    println!("found {} servos", servos_count);
+
+    let mut angle = 0;
+    let mut step = 10;
+    loop {
+        for servo in 0..servos_count {
+            if Servo::set_angle(servo, angle).is_err() {
+                println!("failed to move servo {}", servo);
+            }
+        }
+        if angle == 0 {
+            step = 10;
+        } else if angle == MAX_ANGLE {
+            step = -10;
+        }
+        angle = (angle as i32 + step) as u32;
+        Alarm::sleep_for(Milliseconds(100)).unwrap();
+    }
+}
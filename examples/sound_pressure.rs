@@ -4,10 +4,8 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::alarm::{Alarm, Milliseconds};
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 use libtock::sound_pressure::SoundPressure;
 
@@ -16,30 +14,25 @@ stack_size! {0x200}
 
 fn main() {
     if SoundPressure::exists().is_err() {
-        writeln!(Console::writer(), "Sound pressure driver not found").unwrap();
+        println!("Sound pressure driver not found");
         return;
     }
 
-    writeln!(Console::writer(), "Sound pressure driver found").unwrap();
+    println!("Sound pressure driver found");
     let enable = SoundPressure::enable();
     match enable {
         Ok(()) => {
-            writeln!(Console::writer(), "Sound pressure driver enabled").unwrap();
+            println!("Sound pressure driver enabled");
             loop {
                 match SoundPressure::read_sync() {
-                    Ok(sound_pressure_val) => writeln!(
-                        Console::writer(),
-                        "Sound Pressure: {}\n",
-                        sound_pressure_val
-                    )
-                    .unwrap(),
+                    Ok(sound_pressure_val) => println!("Sound Pressure: {}\n", sound_pressure_val),
                     Err(_) => {
-                        writeln!(Console::writer(), "error while reading sound pressure",).unwrap()
+                        println!("error while reading sound pressure")
                     }
                 }
                 Alarm::sleep_for(Milliseconds(1000)).unwrap();
             }
         }
-        Err(_e) => writeln!(Console::writer(), "Sound pressure driver enable failed",).unwrap(),
+        Err(_e) => println!("Sound pressure driver enable failed"),
     }
 }
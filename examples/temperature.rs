@@ -4,10 +4,8 @@
 #![no_main]
 #![no_std]
 
-use core::fmt::Write;
-use libtock::console::Console;
-
 use libtock::alarm::{Alarm, Milliseconds};
+use libtock::println;
 use libtock::runtime::{set_main, stack_size};
 use libtock::temperature::Temperature;
 
@@ -16,24 +14,22 @@ stack_size! {0x200}
 
 fn main() {
     match Temperature::exists() {
-        Ok(()) => writeln!(Console::writer(), "temperature driver available").unwrap(),
+        Ok(()) => println!("temperature driver available"),
         Err(_) => {
-            writeln!(Console::writer(), "temperature driver unavailable").unwrap();
+            println!("temperature driver unavailable");
             return;
         }
     }
 
     loop {
         match Temperature::read_temperature_sync() {
-            Ok(temp_val) => writeln!(
-                Console::writer(),
+            Ok(temp_val) => println!(
                 "Temperature: {}{}.{}*C\n",
                 if temp_val > 0 { "" } else { "-" },
                 i32::abs(temp_val) / 100,
                 i32::abs(temp_val) % 100
-            )
-            .unwrap(),
-            Err(_) => writeln!(Console::writer(), "error while reading temperature",).unwrap(),
+            ),
+            Err(_) => println!("error while reading temperature"),
         }
 
         Alarm::sleep_for(Milliseconds(2000)).unwrap();
@@ -0,0 +1,257 @@
+#![no_std]
+
+use core::fmt::Write;
+use core::marker::PhantomData;
+use libtock_console::{BufferedWriter, Console};
+use libtock_low_level_debug::LowLevelDebug;
+use libtock_platform::{DefaultConfig, Syscalls};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+pub use libtock_console::Config;
+pub use log;
+
+/// A `log` backend for Tock processes.
+///
+/// Each record is written to the console as a single line of the form
+/// `[LEVEL target] message`. If the console driver is not present, the record
+/// is printed with [`LowLevelDebug::print_2`] instead: the first number is the
+/// record's level (1 = error through 5 = trace) and the second is the source
+/// line of the log statement (0 if unknown).
+///
+/// Records are filtered by target: the filter with the longest module path
+/// matching the record's target decides whether it is logged, and records no
+/// filter matches use the default level.
+///
+/// With the `log` crate's macros, filters are checked at runtime, and
+/// filtered-out statements stay in the binary. This crate's [`error!`],
+/// [`warn!`], [`info!`], [`debug!`] and [`trace!`] macros instead check the
+/// filters at compile time, against a `const LOGGER` at the root of the calling
+/// crate. Statements that logger would not log are removed from the binary,
+/// along with their format strings. Select the filter table with `cfg`
+/// attributes to choose the filters with cargo features, as in the example
+/// below.
+///
+/// # Example
+/// ```ignore
+/// use libtock::logger::{self, Filter, LevelFilter, Logger};
+///
+/// #[cfg(not(feature = "radio_debug"))]
+/// const FILTERS: &[Filter] = &[];
+/// #[cfg(feature = "radio_debug")]
+/// const FILTERS: &[Filter] = &[Filter::new("app::radio", LevelFilter::Debug)];
+///
+/// // At the crate root, so the logging macros find it.
+/// const LOGGER: Logger = Logger::new(LevelFilter::Info).with_filters(FILTERS);
+///
+/// LOGGER.init();
+/// logger::info!("started");
+/// ```
+pub struct Logger<S: Syscalls, C: Config = DefaultConfig> {
+    default: LevelFilter,
+    filters: &'static [Filter],
+    // Uses fn() so the logger is Send and Sync, which `Log` requires.
+    _syscalls: PhantomData<fn() -> (S, C)>,
+}
+
+/// Sets the maximum level of records whose target is `module` or one of its
+/// submodules. Filters are applied at runtime; see [`Logger`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub module: &'static str,
+    pub level: LevelFilter,
+}
+
+impl Filter {
+    pub const fn new(module: &'static str, level: LevelFilter) -> Filter {
+        Filter { module, level }
+    }
+
+    // Written with loops over bytes so that it can be evaluated at compile
+    // time.
+    const fn matches(&self, target: &str) -> bool {
+        let module = self.module.as_bytes();
+        let target = target.as_bytes();
+        if target.len() < module.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < module.len() {
+            if module[i] != target[i] {
+                return false;
+            }
+            i += 1;
+        }
+        target.len() == i || (target.len() > i + 1 && target[i] == b':' && target[i + 1] == b':')
+    }
+}
+
+impl<S: Syscalls, C: Config> Logger<S, C> {
+    /// Creates a logger that logs records up to `default`, with no per-module
+    /// filters.
+    pub const fn new(default: LevelFilter) -> Self {
+        Logger {
+            default,
+            filters: &[],
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Replaces the logger's per-module filters.
+    pub const fn with_filters(self, filters: &'static [Filter]) -> Self {
+        Logger { filters, ..self }
+    }
+
+    /// The maximum level of records logged for `target`.
+    pub const fn level_for(&self, target: &str) -> LevelFilter {
+        let mut level = self.default;
+        let mut longest = None;
+        let mut i = 0;
+        while i < self.filters.len() {
+            let filter = &self.filters[i];
+            let longer = match longest {
+                Some(len) => filter.module.len() > len,
+                None => true,
+            };
+            if longer && filter.matches(target) {
+                level = filter.level;
+                longest = Some(filter.module.len());
+            }
+            i += 1;
+        }
+        level
+    }
+
+    /// Whether records at `level` are logged for `target`. Used by the logging
+    /// macros at compile time.
+    #[doc(hidden)]
+    pub const fn logs(&self, level: Level, target: &str) -> bool {
+        level as usize <= self.level_for(target) as usize
+    }
+
+    /// The most verbose level any record can be logged at.
+    pub fn max_level(&self) -> LevelFilter {
+        self.filters
+            .iter()
+            .map(|filter| filter.level)
+            .fold(self.default, Ord::max)
+    }
+
+    /// Installs this logger as the `log` crate's global logger. Has no effect
+    /// if a logger was already installed.
+    pub fn init(&'static self) {
+        if set_logger(self).is_ok() {
+            set_max_level(self.max_level());
+        }
+    }
+}
+
+impl<S: Syscalls, C: Config> Log for Logger<S, C> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if !Console::<S, C>::driver_check() {
+            LowLevelDebug::<S>::print_2(record.level() as u32, record.line().unwrap_or(0));
+            return;
+        }
+        // The buffer collects the record so it is usually written with a single
+        // system call, and is flushed when it is dropped. There is nowhere to
+        // report errors.
+        let mut writer = BufferedWriter::<S, C, 64>::new();
+        let _ = writeln!(
+            writer,
+            "[{:<5} {}] {}",
+            record.level(),
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+// Tock processes are single-threaded, so on targets without atomic
+// compare-and-swap the racy versions of these functions are safe to use.
+#[cfg(target_has_atomic = "ptr")]
+fn set_logger(logger: &'static dyn Log) -> Result<(), log::SetLoggerError> {
+    log::set_logger(logger)
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+fn set_logger(logger: &'static dyn Log) -> Result<(), log::SetLoggerError> {
+    // Safety: the process has a single thread, so this cannot race with
+    // another call to set_logger or with a log statement.
+    unsafe { log::set_logger_racy(logger) }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+fn set_max_level(level: LevelFilter) {
+    log::set_max_level(level)
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+fn set_max_level(level: LevelFilter) {
+    // Safety: the process has a single thread, so this cannot race with a
+    // log statement reading the level.
+    unsafe { log::set_max_level_racy(level) }
+}
+
+/// Logs at the given level if the calling crate's `const LOGGER` logs it for
+/// the current module, and otherwise compiles to nothing. See [`Logger`].
+// `crate` deliberately names the calling crate, where `LOGGER` is declared.
+#[allow(clippy::crate_in_macro_def)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:ident, $($arg:tt)+) => {{
+        const ENABLED: bool = crate::LOGGER.logs($crate::log::Level::$level, module_path!());
+        if ENABLED {
+            $crate::log::log!($crate::log::Level::$level, $($arg)+);
+        }
+    }};
+}
+
+/// Logs an error, if the calling crate's `const LOGGER` logs errors for the
+/// current module. Otherwise the statement is removed at compile time.
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::__log!(Error, $($arg)+) };
+}
+
+/// Logs a warning, if the calling crate's `const LOGGER` logs warnings for the
+/// current module. Otherwise the statement is removed at compile time.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::__log!(Warn, $($arg)+) };
+}
+
+/// Logs at info level, if the calling crate's `const LOGGER` logs it for the
+/// current module. Otherwise the statement is removed at compile time.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::__log!(Info, $($arg)+) };
+}
+
+/// Logs at debug level, if the calling crate's `const LOGGER` logs it for the
+/// current module. Otherwise the statement is removed at compile time.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::__log!(Debug, $($arg)+) };
+}
+
+/// Logs at trace level, if the calling crate's `const LOGGER` logs it for the
+/// current module. Otherwise the statement is removed at compile time.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::__log!(Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests;
+// The logging macros look up the logger at the crate root.
+#[cfg(test)]
+use tests::LOGGER;
@@ -0,0 +1,141 @@
+use super::Filter;
+use libtock_unittest::fake;
+use log::{Level, LevelFilter, Log, Record};
+
+type Logger = super::Logger<fake::Syscalls>;
+
+const FILTERS: &[Filter] = &[
+    Filter::new("app::radio", LevelFilter::Debug),
+    Filter::new("app::radio::spi", LevelFilter::Off),
+    Filter::new("libtock_alarm", LevelFilter::Error),
+    Filter::new("libtock_logger::tests::radio", LevelFilter::Debug),
+];
+
+pub(crate) const LOGGER: Logger = Logger::new(LevelFilter::Info).with_filters(FILTERS);
+
+#[test]
+fn level_for() {
+    let logger = Logger::new(LevelFilter::Info).with_filters(FILTERS);
+    assert_eq!(logger.level_for("app"), LevelFilter::Info);
+    assert_eq!(logger.level_for("app::radio"), LevelFilter::Debug);
+    assert_eq!(logger.level_for("app::radio::packet"), LevelFilter::Debug);
+    assert_eq!(logger.level_for("app::radio::spi"), LevelFilter::Off);
+    assert_eq!(logger.level_for("app::radio::spi::dma"), LevelFilter::Off);
+    // Filters only match whole module path segments.
+    assert_eq!(logger.level_for("app::radios"), LevelFilter::Info);
+    assert_eq!(logger.level_for("libtock_alarm::mux"), LevelFilter::Error);
+    assert_eq!(logger.max_level(), LevelFilter::Debug);
+    assert_eq!(
+        Logger::new(LevelFilter::Warn).max_level(),
+        LevelFilter::Warn
+    );
+}
+
+#[test]
+fn console() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    let low_level_debug = fake::LowLevelDebug::new();
+    kernel.add_driver(&console);
+    kernel.add_driver(&low_level_debug);
+
+    let logger = Logger::new(LevelFilter::Info).with_filters(FILTERS);
+    logger.log(
+        &Record::builder()
+            .level(Level::Warn)
+            .target("app")
+            .args(format_args!("low battery: {}%", 12))
+            .build(),
+    );
+    assert_eq!(console.take_bytes(), b"[WARN  app] low battery: 12%\n");
+    logger.log(
+        &Record::builder()
+            .level(Level::Debug)
+            .target("app::radio")
+            .args(format_args!("rx"))
+            .build(),
+    );
+    assert_eq!(console.take_bytes(), b"[DEBUG app::radio] rx\n");
+    assert_eq!(low_level_debug.take_messages(), []);
+}
+
+#[test]
+fn filtered() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let logger = Logger::new(LevelFilter::Info).with_filters(FILTERS);
+    logger.log(
+        &Record::builder()
+            .level(Level::Debug)
+            .target("app")
+            .args(format_args!("hidden"))
+            .build(),
+    );
+    logger.log(
+        &Record::builder()
+            .level(Level::Error)
+            .target("app::radio::spi")
+            .args(format_args!("hidden"))
+            .build(),
+    );
+    assert_eq!(console.take_bytes(), b"");
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn low_level_debug_fallback() {
+    let kernel = fake::Kernel::new();
+    let low_level_debug = fake::LowLevelDebug::new();
+    kernel.add_driver(&low_level_debug);
+
+    let logger = Logger::new(LevelFilter::Trace);
+    logger.log(
+        &Record::builder()
+            .level(Level::Info)
+            .target("app")
+            .line(Some(42))
+            .args(format_args!("started"))
+            .build(),
+    );
+    logger.log(
+        &Record::builder()
+            .level(Level::Trace)
+            .target("app")
+            .args(format_args!("no line"))
+            .build(),
+    );
+    assert_eq!(
+        low_level_debug.take_messages(),
+        [fake::Message::Print2(3, 42), fake::Message::Print2(5, 0)]
+    );
+}
+
+// The filters are evaluated at compile time.
+const _: () = assert!(LOGGER.logs(Level::Debug, "app::radio::packet"));
+const _: () = assert!(!LOGGER.logs(Level::Error, "app::radio::spi"));
+
+mod radio {
+    pub fn log() {
+        crate::debug!("radio {}", 1);
+        crate::trace!("radio {}", 2);
+    }
+}
+
+#[test]
+fn macros() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    LOGGER.init();
+    crate::warn!("low battery: {}%", 12);
+    crate::debug!("hidden");
+    radio::log();
+    assert_eq!(
+        console.take_bytes(),
+        b"[WARN  libtock_logger::tests] low battery: 12%\n\
+          [DEBUG libtock_logger::tests::radio] radio 1\n"
+    );
+}
@@ -1,5 +1,4 @@
 #![no_std]
-use core::fmt::Write;
 use libtock_console::Console;
 use libtock_low_level_debug::{AlertCode, LowLevelDebug};
 use libtock_platform::{ErrorCode, Syscalls};
@@ -12,9 +11,33 @@ fn panic_handler(info: &core::panic::PanicInfo) -> ! {
     // Signal a panic using the LowLevelDebug capsule (if available).
     LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
 
-    let mut writer = Console::<TockSyscalls>::writer();
     // If this printing fails, we can't panic harder, and we can't print it either.
-    let _ = writeln!(writer, "{}", info);
+    print_info(info);
     // Exit with a non-zero exit code to indicate failure.
     TockSyscalls::exit_terminate(ErrorCode::Fail as u32);
 }
+
+// Prints the full panic message using core::fmt.
+#[cfg(not(feature = "ufmt"))]
+fn print_info(info: &core::panic::PanicInfo) {
+    use core::fmt::Write;
+    let mut writer = Console::<TockSyscalls>::writer();
+    let _ = writeln!(writer, "{}", info);
+}
+
+// Prints only the panic location, as the message can only be formatted using
+// core::fmt. This avoids pulling core::fmt's formatting code into the binary.
+#[cfg(feature = "ufmt")]
+fn print_info(info: &core::panic::PanicInfo) {
+    let mut writer = Console::<TockSyscalls>::writer();
+    let _ = match info.location() {
+        Some(location) => ufmt::uwriteln!(
+            writer,
+            "panicked at '{}:{}:{}'",
+            location.file(),
+            location.line(),
+            location.column()
+        ),
+        None => ufmt::uwriteln!(writer, "panicked"),
+    };
+}
@@ -12,6 +12,7 @@ pub mod syscall_class {
     pub const ALLOW_RO: usize = 4;
     pub const MEMOP: usize = 5;
     pub const EXIT: usize = 6;
+    pub const USERSPACE_READABLE_ALLOW: usize = 7;
 }
 
 pub mod yield_id {
@@ -195,5 +195,12 @@ pub unsafe trait RawSyscalls: Sized {
     /// boundary. In particular, that means there MUST NOT be a reference
     /// overlapping the passed buffer, until the buffer has been returned by a
     /// Read-Write Allow call.
+    ///
+    /// For Userspace Readable Allow, the aliasing invariants on the buffer are
+    /// equivalent to passing a `&[Cell<u8>]` reference across the system call
+    /// boundary: the kernel may write to the buffer during any system call. In
+    /// particular, that means there MUST NOT be a `&[u8]` or `&mut [u8]`
+    /// reference overlapping the passed buffer, until the buffer has been
+    /// returned by a Userspace Readable Allow call.
     unsafe fn syscall4<const CLASS: usize>(_: [Register; 4]) -> [Register; 4];
 }
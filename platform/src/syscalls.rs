@@ -1,3 +1,5 @@
+use core::cell::Cell;
+
 use crate::{
     allow_ro, allow_rw, share, subscribe, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls,
     Subscribe, Upcall, YieldNoWaitReturn,
@@ -77,6 +79,22 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// `unallow_ro` does nothing.
     fn unallow_ro(driver_num: u32, buffer_num: u32);
 
+    // -------------------------------------------------------------------------
+    // Userspace Readable Allow
+    // -------------------------------------------------------------------------
+
+    /// Shares a buffer with the kernel for the rest of the process' life.
+    /// Unlike a Read-Write Allow buffer, the process may read the buffer while
+    /// the kernel has access to it, so the buffer is made of `Cell`s. There is
+    /// no way to revoke the kernel's access, so the buffer must be `'static`.
+    fn allow_userspace_readable<
+        CONFIG: allow_rw::Config,
+        const DRIVER_NUM: u32,
+        const BUFFER_NUM: u32,
+    >(
+        buffer: &'static [Cell<u8>],
+    ) -> Result<(), ErrorCode>;
+
     // TODO: Add memop() methods.
 
     // -------------------------------------------------------------------------
@@ -1,5 +1,7 @@
 //! Implements `Syscalls` for all types that implement `RawSyscalls`.
 
+use core::cell::Cell;
+
 use crate::{
     allow_ro, allow_rw, exit_id, exit_on_drop, return_variant, share, subscribe, syscall_class,
     yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register, ReturnVariant,
@@ -352,6 +354,59 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 
+    // -------------------------------------------------------------------------
+    // Userspace Readable Allow
+    // -------------------------------------------------------------------------
+
+    fn allow_userspace_readable<
+        CONFIG: allow_rw::Config,
+        const DRIVER_NUM: u32,
+        const BUFFER_NUM: u32,
+    >(
+        buffer: &'static [Cell<u8>],
+    ) -> Result<(), ErrorCode> {
+        // Inner function that does the majority of the work. This is not
+        // monomorphized over DRIVER_NUM and BUFFER_NUM to keep code size small.
+        fn inner<S: Syscalls, CONFIG: allow_rw::Config>(
+            driver_num: u32,
+            buffer_num: u32,
+            buffer: &'static [Cell<u8>],
+        ) -> Result<(), ErrorCode> {
+            // Safety: syscall4's documentation indicates it can be used to call
+            // Userspace Readable Allow. These arguments follow TRD104. The
+            // buffer is 'static, so it outlives the kernel's access to it, and
+            // it is made of Cells, so the kernel may write to it while shared
+            // references to it exist.
+            let [r0, r1, r2, _] = unsafe {
+                S::syscall4::<{ syscall_class::USERSPACE_READABLE_ALLOW }>([
+                    driver_num.into(),
+                    buffer_num.into(),
+                    buffer.as_ptr().into(),
+                    buffer.len().into(),
+                ])
+            };
+
+            // See allow_rw for why the return variant is compared against
+            // Failure with 2 U32.
+            let return_variant: ReturnVariant = r0.as_u32().into();
+            if return_variant == return_variant::FAILURE_2_U32 {
+                // Safety: TRD 104 guarantees that if r0 is Failure with 2 U32,
+                // then r1 will contain a valid error code. ErrorCode is
+                // designed to be safely transmuted directly from a kernel error
+                // code.
+                return Err(unsafe { core::mem::transmute(r1.as_u32()) });
+            }
+
+            let returned_buffer: (usize, usize) = (r1.into(), r2.into());
+            if returned_buffer != (0, 0) {
+                CONFIG::returned_nonzero_buffer(driver_num, buffer_num);
+            }
+            Ok(())
+        }
+
+        inner::<Self, CONFIG>(DRIVER_NUM, BUFFER_NUM, buffer)
+    }
+
     // -------------------------------------------------------------------------
     // Exit
     // -------------------------------------------------------------------------
@@ -127,6 +127,13 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
                      inlateout("r3") r3,
                      options(preserves_flags, nostack),
                 ),
+                syscall_class::USERSPACE_READABLE_ALLOW => asm!("svc 7",
+                     inlateout("r0") r0,
+                     inlateout("r1") r1,
+                     inlateout("r2") r2,
+                     inlateout("r3") r3,
+                     options(preserves_flags, nostack),
+                ),
                 _ => unreachable!(),
             }
         }
@@ -5,26 +5,53 @@ extern crate libtock_debug_panic;
 
 pub use libtock_platform as platform;
 pub use libtock_runtime as runtime;
+pub use ufmt;
+
+mod print;
 
 pub mod adc {
     use libtock_adc as adc;
     pub type Adc = adc::Adc<super::runtime::TockSyscalls>;
-    pub use adc::ADCListener;
+    pub use adc::{sample_to_millivolts, samples, ADCListener, SamplingEvent, SamplingListener};
+}
+pub mod aes {
+    use libtock_aes as aes;
+    pub type Aes = aes::Aes<super::runtime::TockSyscalls>;
+    pub use aes::{AesMode, Direction, BLOCK_LEN, CCM_NONCE_LEN, KEY_LEN};
+}
+pub mod air_quality {
+    use libtock_air_quality as air_quality;
+    pub type AirQuality = air_quality::AirQuality<super::runtime::TockSyscalls>;
+    pub use air_quality::{Co2Listener, PartsPerBillion, PartsPerMillion, TvocListener};
 }
 pub mod alarm {
     use libtock_alarm as alarm;
     pub type Alarm = alarm::Alarm<super::runtime::TockSyscalls>;
-    pub use alarm::{Convert, Hz, Milliseconds, Ticks};
+    pub type AlarmMux<'a, const N: usize> = alarm::AlarmMux<'a, super::runtime::TockSyscalls, N>;
+    pub type Clock = alarm::Clock<super::runtime::TockSyscalls>;
+    pub type Delay = alarm::Delay<super::runtime::TockSyscalls>;
+    pub use alarm::{Convert, Hz, Instant, Milliseconds, Ticks, TimerId, DRIVER_NUM};
 }
 pub mod ambient_light {
     use libtock_ambient_light as ambient_light;
     pub type AmbientLight = ambient_light::AmbientLight<super::runtime::TockSyscalls>;
     pub use ambient_light::IntensityListener;
 }
+pub mod analog_comparator {
+    use libtock_analog_comparator as analog_comparator;
+    pub type AnalogComparator = analog_comparator::AnalogComparator<super::runtime::TockSyscalls>;
+    pub use analog_comparator::ComparatorListener;
+}
+pub mod ble {
+    use libtock_ble as ble;
+    pub type Ble = ble::Ble<super::runtime::TockSyscalls>;
+    pub use ble::{flags, AdStructures, AdvertisingPayload, AdvertisingType, ScanReport};
+}
 pub mod buttons {
     use libtock_buttons as buttons;
     pub type Buttons = buttons::Buttons<super::runtime::TockSyscalls>;
-    pub use buttons::{ButtonListener, ButtonState};
+    pub use buttons::{ButtonListener, ButtonState, Gesture, GestureConfig};
+    pub type Gestures<const N: usize> = buttons::Gestures<super::runtime::TockSyscalls, N>;
 }
 pub mod buzzer {
     use libtock_buzzer as buzzer;
@@ -34,20 +61,77 @@ pub mod buzzer {
 pub mod console {
     use libtock_console as console;
     pub type Console = console::Console<super::runtime::TockSyscalls>;
-    pub use console::ConsoleWriter;
+    pub type BufferedWriter<const N: usize = 64> =
+        console::BufferedWriter<super::runtime::TockSyscalls, libtock_platform::DefaultConfig, N>;
+    pub type ConsoleReader = console::ConsoleReader<super::runtime::TockSyscalls>;
+    pub use console::{ConsoleError, ConsoleWriter};
+}
+pub mod crc {
+    use libtock_crc as crc;
+    pub type Crc = crc::Crc<super::runtime::TockSyscalls>;
+    pub type CrcStream = crc::CrcStream<super::runtime::TockSyscalls>;
+    pub use crc::CrcAlgorithm;
+}
+pub mod ctap {
+    use libtock_ctap as ctap;
+    pub type Ctap = ctap::Ctap<super::runtime::TockSyscalls>;
+    pub use ctap::{CtapEvent, CtapListener, PACKET_LEN};
+}
+pub mod dac {
+    use libtock_dac as dac;
+    pub type Dac = dac::Dac<super::runtime::TockSyscalls>;
+    pub use dac::max_value;
 }
 pub mod gpio {
     use libtock_gpio as gpio;
     pub type Gpio = gpio::Gpio<super::runtime::TockSyscalls>;
+    pub type PinInterrupts<'a, const N: usize> =
+        gpio::PinInterrupts<'a, super::runtime::TockSyscalls, N>;
+    pub type PinRegistry = gpio::PinRegistry<super::runtime::TockSyscalls>;
     pub use gpio::{
-        Error, GpioInterruptListener, GpioState, InputPin, OutputPin, PinInterruptEdge, Pull,
-        PullDown, PullNone, PullUp,
+        Debounce, Error, Flex, GpioError, GpioInterruptListener, GpioState, InputPin, OutputPin,
+        Pin, PinInterruptEdge, Pull, PullDown, PullNone, PullUp, WaitFor,
+    };
+}
+pub mod hmac {
+    use libtock_hmac as hmac;
+    pub type Hmac = hmac::Hmac<super::runtime::TockSyscalls>;
+    pub type HmacSession<'k> = hmac::HmacSession<'k, super::runtime::TockSyscalls>;
+    pub use hmac::{HmacAlgorithm, MAX_MAC_LEN};
+}
+pub mod humidity {
+    use libtock_humidity as humidity;
+    pub type Humidity = humidity::Humidity<super::runtime::TockSyscalls>;
+    pub use humidity::{HumidityListener, RelativeHumidity};
+}
+pub mod ieee802154 {
+    use libtock_ieee802154 as ieee802154;
+    pub type Ieee802154 = ieee802154::Ieee802154<super::runtime::TockSyscalls>;
+    pub use ieee802154::{
+        rx_buffer_len, Frame, FrameHeader, FrameType, MacAddress, RxRingBuffer, MAX_FRAME_LEN,
     };
 }
+pub mod ipc {
+    use libtock_ipc as ipc;
+    pub type Ipc = ipc::Ipc<super::runtime::TockSyscalls>;
+    pub use ipc::IpcListener;
+}
+pub mod led_matrix {
+    use libtock_led_matrix as led_matrix;
+    pub type LedMatrix = led_matrix::LedMatrix<super::runtime::TockSyscalls>;
+    pub use led_matrix::font;
+}
 pub mod leds {
     use libtock_leds as leds;
     pub type Leds = leds::Leds<super::runtime::TockSyscalls>;
 }
+pub mod logger {
+    use libtock_logger as logger;
+    pub type Logger = logger::Logger<super::runtime::TockSyscalls>;
+    pub use logger::log::{self, Level, LevelFilter};
+    pub use logger::Filter;
+    pub use logger::{debug, error, info, trace, warn};
+}
 pub mod low_level_debug {
     use libtock_low_level_debug as lldb;
     pub type LowLevelDebug = lldb::LowLevelDebug<super::runtime::TockSyscalls>;
@@ -58,10 +142,52 @@ pub mod ninedof {
     pub type NineDof = ninedof::NineDof<super::runtime::TockSyscalls>;
     pub use ninedof::NineDofListener;
 }
+pub mod pressure {
+    use libtock_pressure as pressure;
+    pub type Pressure = pressure::Pressure<super::runtime::TockSyscalls>;
+    pub use pressure::{Hectopascals, PressureListener};
+}
 pub mod proximity {
     use libtock_proximity as proximity;
     pub type Proximity = proximity::Proximity<super::runtime::TockSyscalls>;
 }
+pub mod pwm {
+    use libtock_pwm as pwm;
+    pub type Pwm = pwm::Pwm<super::runtime::TockSyscalls>;
+    pub type PwmOutput = pwm::PwmOutput<super::runtime::TockSyscalls>;
+    pub use pwm::{DutyCycle, PwmError};
+}
+pub mod read_only_state {
+    use libtock_read_only_state as read_only_state;
+    pub type ReadOnlyState = read_only_state::ReadOnlyState<super::runtime::TockSyscalls>;
+    pub use read_only_state::BUFFER_LEN;
+}
+pub mod rtc {
+    use libtock_rtc as rtc;
+    pub type Rtc = rtc::Rtc<super::runtime::TockSyscalls>;
+    pub use rtc::{DateTime, Month, Weekday, MIN_YEAR};
+}
+pub mod screen {
+    use libtock_screen as screen;
+    pub type Screen = screen::Screen<super::runtime::TockSyscalls>;
+    pub type ScreenTarget = screen::ScreenTarget<super::runtime::TockSyscalls>;
+    pub use screen::{PixelFormat, Region, Rotation};
+}
+pub mod servo {
+    use libtock_servo as servo;
+    pub type Servo = servo::Servo<super::runtime::TockSyscalls>;
+    pub use servo::MAX_ANGLE;
+}
+pub mod seven_segment {
+    use libtock_seven_segment as seven_segment;
+    pub type SevenSegment = seven_segment::SevenSegment<super::runtime::TockSyscalls>;
+    pub use seven_segment::Segments;
+}
+pub mod sha {
+    use libtock_sha as sha;
+    pub type Sha = sha::Sha<super::runtime::TockSyscalls>;
+    pub use sha::{ShaAlgorithm, MAX_DIGEST_LEN};
+}
 pub mod sound_pressure {
     use libtock_sound_pressure as sound_pressure;
     pub type SoundPressure = sound_pressure::SoundPressure<super::runtime::TockSyscalls>;
@@ -71,3 +197,15 @@ pub mod temperature {
     pub type Temperature = temperature::Temperature<super::runtime::TockSyscalls>;
     pub use temperature::TemperatureListener;
 }
+pub mod text_screen {
+    use libtock_text_screen as text_screen;
+    pub type TextScreen = text_screen::TextScreen<super::runtime::TockSyscalls>;
+    pub type TextScreenWriter = text_screen::TextScreenWriter<super::runtime::TockSyscalls>;
+}
+pub mod usb_keyboard {
+    use libtock_usb_keyboard as usb_keyboard;
+    pub type UsbKeyboard = usb_keyboard::UsbKeyboard<super::runtime::TockSyscalls>;
+    pub use usb_keyboard::{
+        KeyStroke, KeyboardLayout, KeyboardReport, Modifiers, UsQwerty, REPORT_LEN,
+    };
+}
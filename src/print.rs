@@ -0,0 +1,59 @@
+//! `print!` and `println!` macros that write to the console.
+//!
+//! By default the macros format with `ufmt`, which produces much smaller
+//! binaries than `core::fmt` but only accepts `uDisplay`/`uDebug` arguments
+//! and a subset of format specifiers. Enable the `core_fmt` feature to format
+//! with `core::fmt` instead.
+//!
+//! Console errors are ignored, as there is nowhere to report them.
+
+/// Prints to the console.
+#[cfg(not(feature = "core_fmt"))]
+#[macro_export]
+macro_rules! print {
+    // ufmt's macros must be wrapped using `tt` fragments, see the ufmt docs.
+    ($($tt:tt)*) => {{
+        // The ufmt macros expand to `ufmt::` paths, which must resolve at the
+        // call site.
+        use $crate::ufmt;
+        let mut writer = $crate::console::Console::writer();
+        let _ = ufmt::uwrite!(writer, $($tt)*);
+    }};
+}
+
+/// Prints to the console, with a newline appended.
+#[cfg(not(feature = "core_fmt"))]
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($tt:tt)*) => {{
+        use $crate::ufmt;
+        let mut writer = $crate::console::Console::writer();
+        let _ = ufmt::uwriteln!(writer, $($tt)*);
+    }};
+}
+
+/// Prints to the console.
+#[cfg(feature = "core_fmt")]
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {{
+        use core::fmt::Write as _;
+        let _ = core::write!($crate::console::Console::writer(), $($arg)*);
+    }};
+}
+
+/// Prints to the console, with a newline appended.
+#[cfg(feature = "core_fmt")]
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arg:tt)*) => {{
+        use core::fmt::Write as _;
+        let _ = core::writeln!($crate::console::Console::writer(), $($arg)*);
+    }};
+}
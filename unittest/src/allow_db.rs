@@ -1,3 +1,4 @@
+use core::cell::Cell;
 use core::num::NonZeroUsize;
 use libtock_platform::Register;
 
@@ -124,6 +125,29 @@ impl AllowDb {
         Ok(RwAllowBuffer { address, len })
     }
 
+    /// Adds a userspace-readable buffer to the database, and returns it as a
+    /// `UrAllowBuffer`.
+    ///
+    /// # Safety
+    /// `address` and `len` must be valid as specified in TRD 104: either `len`
+    /// is 0 or `address` and `len` represent a valid slice.
+    pub unsafe fn insert_ur_buffer(
+        &mut self,
+        address: Register,
+        len: Register,
+    ) -> Result<UrAllowBuffer, OverlapError> {
+        let address: *const Cell<u8> = address.into();
+        let len: usize = len.into();
+        if let Some(nonzero_len) = NonZeroUsize::new(len) {
+            // The buffer is not zero-sized. Add it to the database (checking it
+            // does not overlap an existing buffer).
+            // Safety: `len` is nonzero, so by this function's precondition
+            // `address` and `len` represent a valid slice.
+            unsafe { self.insert_raw(address as *mut u8, nonzero_len) }?;
+        }
+        Ok(UrAllowBuffer { address, len })
+    }
+
     /// Removes a read-only buffer from the database and returns its raw
     /// register values.
     ///
@@ -143,6 +167,16 @@ impl AllowDb {
         self.buffers.remove(&buffer.address);
         (buffer.address.into(), buffer.len.into())
     }
+
+    /// Removes a userspace-readable buffer from the database and returns its
+    /// raw register values.
+    ///
+    /// The returned value is the tuple (address, len) passed into the
+    /// insert_ur_buffer call that created the UrAllowBuffer.
+    pub fn remove_ur_buffer(&mut self, buffer: UrAllowBuffer) -> (Register, Register) {
+        self.buffers.remove(&(buffer.address as *mut u8));
+        (buffer.address.into(), buffer.len.into())
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
@@ -232,3 +266,40 @@ impl std::ops::DerefMut for RwAllowBuffer {
         }
     }
 }
+
+/// A reference to a buffer that has been shared via the Userspace Readable
+/// Allow system call. The process may read the buffer while it is shared, so
+/// it is accessed as a slice of `Cell`s. This reference is non-Copy, so
+/// `AllowDb` can determine when all references to the buffer have been
+/// destroyed.
+#[derive(Debug)]
+pub struct UrAllowBuffer {
+    // Safety invariant: Either length is 0, or address and length can be
+    // soundly converted to a &'static [Cell<u8>].
+    address: *const Cell<u8>,
+    len: usize,
+}
+
+impl Default for UrAllowBuffer {
+    fn default() -> UrAllowBuffer {
+        UrAllowBuffer {
+            address: core::ptr::null(),
+            len: 0,
+        }
+    }
+}
+
+// Allows access to the pointed-to-buffer. The returned reference has the same
+// lifetime as the &self reference, so the caller can't keep the reference for
+// longer than it has access to the UrAllowBuffer.
+impl std::ops::Deref for UrAllowBuffer {
+    type Target = [Cell<u8>];
+    fn deref(&self) -> &[Cell<u8>] {
+        match self.len {
+            0 => &[],
+            // Safety: Because length is nonzero, the safety invariant on
+            // address and len says this conversion is sound.
+            _ => unsafe { core::slice::from_raw_parts(self.address, self.len) },
+        }
+    }
+}
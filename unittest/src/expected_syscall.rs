@@ -70,6 +70,17 @@ pub enum ExpectedSyscall {
         // invoked and the provided error will be returned instead.
         return_error: Option<libtock_platform::ErrorCode>,
     },
+    // -------------------------------------------------------------------------
+    // Userspace Readable Allow
+    // -------------------------------------------------------------------------
+    AllowUserspaceReadable {
+        driver_num: u32,
+        buffer_num: u32,
+
+        // If set to Some(_), the driver's allow_userspace_readable method will
+        // not be invoked and the provided error will be returned instead.
+        return_error: Option<libtock_platform::ErrorCode>,
+    },
     // TODO: Add Memop.
     // TODO: Add Exit.
 }
@@ -3,10 +3,15 @@
 //! Like the real API, `Adc` controls a fake Adc sensor. It provides
 //! a function `set_value` used to immediately call an upcall with a Adc value read by the sensor
 //! and a function 'set_value_sync' used to call the upcall when the read command is received.
+//!
+//! Repeated, buffered and double-buffered sampling are driven by
+//! `push_samples`, which delivers samples as if the sampling timer had fired
+//! once for each of them.
 
-use crate::{DriverInfo, DriverShareRef};
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
 use libtock_platform::{CommandReturn, ErrorCode};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 
 // The `upcall_on_command` field is set to Some(value) if an upcall(with value as its argument) should be called when read command is received,
 // or None otherwise. It was needed for testing `read_sync` library function which simulates a synchronous Adc read,
@@ -14,14 +19,37 @@ use std::cell::Cell;
 pub struct Adc {
     busy: Cell<bool>,
     upcall_on_command: Cell<Option<i32>>,
+    channel_values: RefCell<HashMap<u32, u16>>,
+    resolution_bits: Cell<u32>,
+    reference_voltage_mv: Cell<u32>,
+    sampling: Cell<Option<Sampling>>,
+    pending: RefCell<VecDeque<u16>>,
+    buffers: [RefCell<RwAllowBuffer>; 2],
     share_ref: DriverShareRef,
 }
 
+// The state of repeated, buffered or double-buffered sampling.
+#[derive(Clone, Copy)]
+struct Sampling {
+    mode: u32,
+    channel: u32,
+    frequency: u32,
+    // The buffer being filled, and how many samples it holds.
+    buffer: usize,
+    filled: usize,
+}
+
 impl Adc {
     pub fn new() -> std::rc::Rc<Adc> {
         std::rc::Rc::new(Adc {
             busy: Cell::new(false),
             upcall_on_command: Cell::new(None),
+            channel_values: Default::default(),
+            resolution_bits: Cell::new(12),
+            reference_voltage_mv: Cell::new(3300),
+            sampling: Cell::new(None),
+            pending: Default::default(),
+            buffers: Default::default(),
             share_ref: Default::default(),
         })
     }
@@ -40,6 +68,108 @@ impl Adc {
     pub fn set_value_sync(&self, value: i32) {
         self.upcall_on_command.set(Some(value));
     }
+
+    /// Makes every single sample of `channel` complete immediately with
+    /// `value`.
+    pub fn set_channel_value(&self, channel: u32, value: u16) {
+        self.channel_values.borrow_mut().insert(channel, value);
+    }
+
+    pub fn set_resolution_bits(&self, bits: u32) {
+        self.resolution_bits.set(bits);
+    }
+
+    pub fn set_reference_voltage_mv(&self, mv: u32) {
+        self.reference_voltage_mv.set(mv);
+    }
+
+    /// Returns the channel and frequency of the repeated, buffered or
+    /// double-buffered sampling in progress, if any.
+    pub fn sampling(&self) -> Option<(u32, u32)> {
+        self.sampling
+            .get()
+            .map(|sampling| (sampling.channel, sampling.frequency))
+    }
+
+    /// Delivers samples to the sampling in progress. Repeated sampling
+    /// schedules an upcall for each sample. Buffered sampling writes the
+    /// samples into the allowed buffers, scheduling an upcall whenever a
+    /// buffer fills. Samples that arrive while no sampling is in progress are
+    /// kept until sampling starts, so tests can provide samples for a
+    /// synchronous read before it begins.
+    pub fn push_samples(&self, samples: &[u16]) {
+        self.pending.borrow_mut().extend(samples);
+        self.deliver();
+    }
+
+    fn deliver(&self) {
+        loop {
+            let mut sampling = match self.sampling.get() {
+                Some(sampling) => sampling,
+                None => return,
+            };
+            let sample = match self.pending.borrow_mut().pop_front() {
+                Some(sample) => sample,
+                None => return,
+            };
+            if sampling.mode == REPEAT_SINGLE_SAMPLE {
+                self.schedule(MODE_REPEATED, sampling.channel, sample as u32);
+                continue;
+            }
+            let mut buffer = self.buffers[sampling.buffer].borrow_mut();
+            let offset = 2 * sampling.filled;
+            buffer[offset..offset + 2].copy_from_slice(&sample.to_le_bytes());
+            sampling.filled += 1;
+            if sampling.filled == buffer.len() / 2 {
+                let mode = match sampling.mode {
+                    MULTIPLE_SAMPLE => MODE_BUFFERED,
+                    _ => MODE_DOUBLE_BUFFERED,
+                };
+                let len_channel = ((sampling.filled as u32) << 8) | (sampling.channel & 0xff);
+                self.schedule(mode, len_channel, buffer.as_ptr() as u32);
+                drop(buffer);
+                if sampling.mode == MULTIPLE_SAMPLE {
+                    self.sampling.set(None);
+                    continue;
+                }
+                // Double-buffered sampling keeps using the first buffer if
+                // there is no second buffer.
+                sampling.filled = 0;
+                if self.buffers[1 - sampling.buffer].borrow().len() >= 2 {
+                    sampling.buffer = 1 - sampling.buffer;
+                }
+            }
+            self.sampling.set(Some(sampling));
+        }
+    }
+
+    fn schedule(&self, mode: u32, arg1: u32, arg2: u32) {
+        self.share_ref
+            .schedule_upcall(0, (mode, arg1, arg2))
+            .expect("Unable to schedule upcall");
+    }
+
+    // Starts repeated, buffered or double-buffered sampling.
+    fn start(&self, mode: u32, channel: u32, frequency: u32) -> CommandReturn {
+        if self.busy.get() || self.sampling.get().is_some() {
+            return crate::command_return::failure(ErrorCode::Busy);
+        }
+        if frequency == 0 {
+            return crate::command_return::failure(ErrorCode::Invalid);
+        }
+        if mode != REPEAT_SINGLE_SAMPLE && self.buffers[0].borrow().len() < 2 {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        self.sampling.set(Some(Sampling {
+            mode,
+            channel,
+            frequency,
+            buffer: 0,
+            filled: 0,
+        }));
+        self.deliver();
+        crate::command_return::success()
+    }
 }
 
 impl crate::fake::SyscallDriver for Adc {
@@ -51,20 +181,42 @@ impl crate::fake::SyscallDriver for Adc {
         self.share_ref.replace(share_ref);
     }
 
-    fn command(&self, command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        match self.buffers.get(buffer_num as usize) {
+            Some(slot) => Ok(slot.replace(buffer)),
+            None => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
         match command_id {
             EXISTS => crate::command_return::success(),
 
             SINGLE_SAMPLE => {
-                if self.busy.get() {
+                if self.busy.get() || self.sampling.get().is_some() {
                     return crate::command_return::failure(ErrorCode::Busy);
                 }
                 self.busy.set(true);
                 if let Some(val) = self.upcall_on_command.take() {
                     self.set_value(val);
+                } else if let Some(&val) = self.channel_values.borrow().get(&argument0) {
+                    self.set_value(val as i32);
                 }
                 crate::command_return::success()
             }
+            REPEAT_SINGLE_SAMPLE | MULTIPLE_SAMPLE | CONTINUOUS_BUFF_SAMPLE => {
+                self.start(command_id, argument0, argument1)
+            }
+            STOP_SAMPLE => {
+                self.sampling.set(None);
+                crate::command_return::success()
+            }
+            GET_RES_BITS => crate::command_return::success_u32(self.resolution_bits.get()),
+            GET_VOLTAGE_REF => crate::command_return::success_u32(self.reference_voltage_mv.get()),
             _ => crate::command_return::failure(ErrorCode::NoSupport),
         }
     }
@@ -82,3 +234,14 @@ const DRIVER_NUM: u32 = 0x5;
 
 const EXISTS: u32 = 0;
 const SINGLE_SAMPLE: u32 = 1;
+const REPEAT_SINGLE_SAMPLE: u32 = 2;
+const MULTIPLE_SAMPLE: u32 = 3;
+const CONTINUOUS_BUFF_SAMPLE: u32 = 4;
+const STOP_SAMPLE: u32 = 5;
+const GET_RES_BITS: u32 = 101;
+const GET_VOLTAGE_REF: u32 = 102;
+
+// Sampling modes, reported as the first argument of sampling upcalls.
+const MODE_REPEATED: u32 = 1;
+const MODE_BUFFERED: u32 = 2;
+const MODE_DOUBLE_BUFFERED: u32 = 3;
@@ -65,3 +65,44 @@ fn kernel_integration() {
         assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
     });
 }
+
+#[test]
+fn streaming() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let adc = Adc::new();
+    kernel.add_driver(&adc);
+
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, GET_RES_BITS, 0, 0).get_success_u32(),
+        Some(12)
+    );
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, GET_VOLTAGE_REF, 0, 0).get_success_u32(),
+        Some(3300)
+    );
+    // Buffered sampling needs a buffer.
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, MULTIPLE_SAMPLE, 0, 10).get_failure(),
+        Some(ErrorCode::Size)
+    );
+
+    // Samples pushed before sampling starts are delivered once it does.
+    adc.push_samples(&[7]);
+    let listener = Cell::<Option<(u32, u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &listener),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, REPEAT_SINGLE_SAMPLE, 4, 10).is_success());
+        assert_eq!(adc.sampling(), Some((4, 10)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((MODE_REPEATED, 4, 7)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, STOP_SAMPLE, 0, 0).is_success());
+        assert_eq!(adc.sampling(), None);
+        adc.push_samples(&[8]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
@@ -0,0 +1,325 @@
+//! Fake implementation of the AES API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40006_aes.md
+//!
+//! Like the real API, `Aes` encrypts and decrypts data with AES-128, using
+//! the `aes` and `ccm` crates so tests can compare the results against known
+//! test vectors. CCM supports 13-byte nonces and 4, 8 or 16-byte MICs. Every
+//! operation completes immediately, by scheduling its upcall.
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+use ccm::aead::AeadInPlace;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::{Cell, RefCell};
+
+pub struct Aes {
+    // The mode, and whether to encrypt.
+    mode: Cell<Option<(u32, bool)>>,
+    key: Cell<Option<[u8; BLOCK_LEN]>>,
+    // The counter block in CTR mode, or the previous ciphertext block in CBC
+    // mode.
+    chaining: Cell<[u8; BLOCK_LEN]>,
+    // Unused bytes of the last CTR keystream block.
+    keystream: RefCell<Vec<u8>>,
+    nonce: RefCell<Vec<u8>>,
+    key_buffer: RefCell<RoAllowBuffer>,
+    iv_buffer: RefCell<RoAllowBuffer>,
+    source_buffer: RefCell<RoAllowBuffer>,
+    aad_buffer: RefCell<RoAllowBuffer>,
+    destination_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Aes {
+    pub fn new() -> std::rc::Rc<Aes> {
+        std::rc::Rc::new(Aes {
+            mode: Cell::new(None),
+            key: Cell::new(None),
+            chaining: Cell::new([0; BLOCK_LEN]),
+            keystream: Default::default(),
+            nonce: Default::default(),
+            key_buffer: Default::default(),
+            iv_buffer: Default::default(),
+            source_buffer: Default::default(),
+            aad_buffer: Default::default(),
+            destination_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the loaded key, or `None` if no operation is set up.
+    pub fn key(&self) -> Option<[u8; BLOCK_LEN]> {
+        self.key.get()
+    }
+
+    fn setup(&self, mode: u32) -> CommandReturn {
+        let key = match <[u8; BLOCK_LEN]>::try_from(&self.key_buffer.borrow()[..]) {
+            Ok(key) => key,
+            Err(_) => return crate::command_return::failure(ErrorCode::Invalid),
+        };
+        let iv = self.iv_buffer.borrow();
+        match mode {
+            MODE_CTR | MODE_CBC => match <[u8; BLOCK_LEN]>::try_from(&iv[..]) {
+                Ok(iv) => self.chaining.set(iv),
+                Err(_) => return crate::command_return::failure(ErrorCode::Invalid),
+            },
+            MODE_CCM if iv.len() != CCM_NONCE_LEN => {
+                return crate::command_return::failure(ErrorCode::Invalid)
+            }
+            MODE_CCM => *self.nonce.borrow_mut() = iv.to_vec(),
+            _ => {}
+        }
+        self.keystream.borrow_mut().clear();
+        self.key.set(Some(key));
+        crate::command_return::success()
+    }
+
+    // Processes the source buffer into the destination buffer in the CTR, CBC
+    // or ECB mode.
+    fn crypt(&self, mode: u32, encrypt: bool, cipher: &Aes128) -> CommandReturn {
+        let source = self.source_buffer.borrow();
+        let mut destination = self.destination_buffer.borrow_mut();
+        if mode != MODE_CTR && source.len() % BLOCK_LEN != 0 {
+            return crate::command_return::failure(ErrorCode::Invalid);
+        }
+        if destination.len() < source.len() {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        let output = &mut destination[..source.len()];
+        output.copy_from_slice(&source);
+        match mode {
+            MODE_CTR => {
+                let mut keystream = self.keystream.borrow_mut();
+                for byte in output.iter_mut() {
+                    if keystream.is_empty() {
+                        let mut counter = self.chaining.get();
+                        let mut block = GenericArray::from(counter);
+                        cipher.encrypt_block(&mut block);
+                        keystream.extend(block.iter().rev());
+                        // Increments the counter block as a big-endian number.
+                        for counter_byte in counter.iter_mut().rev() {
+                            *counter_byte = counter_byte.wrapping_add(1);
+                            if *counter_byte != 0 {
+                                break;
+                            }
+                        }
+                        self.chaining.set(counter);
+                    }
+                    *byte ^= keystream.pop().unwrap();
+                }
+            }
+            _ => {
+                for chunk in output.chunks_exact_mut(BLOCK_LEN) {
+                    let block = GenericArray::from_mut_slice(chunk);
+                    match (mode, encrypt) {
+                        (MODE_ECB, true) => cipher.encrypt_block(block),
+                        (MODE_ECB, false) => cipher.decrypt_block(block),
+                        (_, true) => {
+                            let mut chaining = self.chaining.get();
+                            xor(block, &chaining);
+                            cipher.encrypt_block(block);
+                            chaining.copy_from_slice(block);
+                            self.chaining.set(chaining);
+                        }
+                        (_, false) => {
+                            let mut chaining = self.chaining.get();
+                            let ciphertext: [u8; BLOCK_LEN] = (*block).into();
+                            cipher.decrypt_block(block);
+                            xor(block, &chaining);
+                            chaining = ciphertext;
+                            self.chaining.set(chaining);
+                        }
+                    }
+                }
+            }
+        }
+        self.complete(0, source.len() as u32)
+    }
+
+    // Encrypts or decrypts the whole source buffer in CCM mode.
+    fn ccm_crypt(&self, encrypt: bool, key: &[u8; BLOCK_LEN], mic_len: u32) -> CommandReturn {
+        let source = self.source_buffer.borrow();
+        let aad = self.aad_buffer.borrow();
+        let nonce = self.nonce.borrow();
+        let mic_len = mic_len as usize;
+        if ![4, 8, 16].contains(&mic_len) {
+            return crate::command_return::failure(ErrorCode::Invalid);
+        }
+        let mut destination = self.destination_buffer.borrow_mut();
+        if encrypt {
+            if destination.len() < source.len() + mic_len {
+                return crate::command_return::failure(ErrorCode::Size);
+            }
+            let (output, mic) = destination[..source.len() + mic_len].split_at_mut(source.len());
+            output.copy_from_slice(&source);
+            mic.copy_from_slice(&match mic_len {
+                4 => ccm_seal::<ccm::consts::U4>(key, &nonce, &aad, output),
+                8 => ccm_seal::<ccm::consts::U8>(key, &nonce, &aad, output),
+                _ => ccm_seal::<ccm::consts::U16>(key, &nonce, &aad, output),
+            });
+            self.complete(0, (source.len() + mic_len) as u32)
+        } else {
+            if source.len() < mic_len || destination.len() < source.len() - mic_len {
+                return crate::command_return::failure(ErrorCode::Size);
+            }
+            let (ciphertext, mic) = source.split_at(source.len() - mic_len);
+            let output = &mut destination[..ciphertext.len()];
+            output.copy_from_slice(ciphertext);
+            let authentic = match mic_len {
+                4 => ccm_open::<ccm::consts::U4>(key, &nonce, &aad, output, mic),
+                8 => ccm_open::<ccm::consts::U8>(key, &nonce, &aad, output, mic),
+                _ => ccm_open::<ccm::consts::U16>(key, &nonce, &aad, output, mic),
+            };
+            match authentic {
+                true => self.complete(0, ciphertext.len() as u32),
+                false => self.complete(ErrorCode::Fail as u32, 0),
+            }
+        }
+    }
+
+    fn complete(&self, status: u32, len: u32) -> CommandReturn {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (status, len, 0))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Aes {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key_buffer.replace(buffer)),
+            ALLOW_IV => Ok(self.iv_buffer.replace(buffer)),
+            ALLOW_SOURCE => Ok(self.source_buffer.replace(buffer)),
+            ALLOW_AAD => Ok(self.aad_buffer.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DESTINATION {
+            Ok(self.destination_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SET_MODE => {
+                if argument0 > MODE_CCM {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.mode.set(Some((argument0, argument1 != 0)));
+                self.key.set(None);
+                crate::command_return::success()
+            }
+            SETUP => match self.mode.get() {
+                None => crate::command_return::failure(ErrorCode::Invalid),
+                Some((mode, _)) => self.setup(mode),
+            },
+            CRYPT | CCM_CRYPT => {
+                let (mode, encrypt, key) = match (self.mode.get(), self.key.get()) {
+                    (Some((mode, encrypt)), Some(key)) => (mode, encrypt, key),
+                    _ => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                match (command_id, mode) {
+                    (CCM_CRYPT, MODE_CCM) => self.ccm_crypt(encrypt, &key, argument0),
+                    (CRYPT, MODE_CTR | MODE_CBC | MODE_ECB) => {
+                        let cipher = Aes128::new(&key.into());
+                        self.crypt(mode, encrypt, &cipher)
+                    }
+                    _ => crate::command_return::failure(ErrorCode::Invalid),
+                }
+            }
+            FINISH => {
+                self.key.set(None);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+fn xor(block: &mut [u8], other: &[u8; BLOCK_LEN]) {
+    for (byte, other) in block.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+}
+
+type Ccm<M> = ccm::Ccm<Aes128, M, ccm::consts::U13>;
+
+fn ccm_seal<M>(key: &[u8; BLOCK_LEN], nonce: &[u8], aad: &[u8], data: &mut [u8]) -> Vec<u8>
+where
+    M: ccm::aead::generic_array::ArrayLength<u8> + ccm::TagSize,
+{
+    Ccm::<M>::new(key.into())
+        .encrypt_in_place_detached(GenericArray::from_slice(nonce), aad, data)
+        .expect("CCM encryption failed")
+        .to_vec()
+}
+
+fn ccm_open<M>(key: &[u8; BLOCK_LEN], nonce: &[u8], aad: &[u8], data: &mut [u8], mic: &[u8]) -> bool
+where
+    M: ccm::aead::generic_array::ArrayLength<u8> + ccm::TagSize,
+{
+    Ccm::<M>::new(key.into())
+        .decrypt_in_place_detached(
+            GenericArray::from_slice(nonce),
+            aad,
+            data,
+            GenericArray::from_slice(mic),
+        )
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SET_MODE: u32 = 1;
+const SETUP: u32 = 2;
+const CRYPT: u32 = 3;
+const FINISH: u32 = 4;
+const CCM_CRYPT: u32 = 5;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_IV: u32 = 1;
+const ALLOW_SOURCE: u32 = 2;
+const ALLOW_AAD: u32 = 3;
+const ALLOW_DESTINATION: u32 = 0;
+
+const MODE_CTR: u32 = 0;
+const MODE_CBC: u32 = 1;
+const MODE_ECB: u32 = 2;
+const MODE_CCM: u32 = 3;
+
+const BLOCK_LEN: usize = 16;
+const CCM_NONCE_LEN: usize = 13;
@@ -0,0 +1,124 @@
+use crate::fake::{self, SyscallDriver};
+use fake::aes::*;
+use libtock_platform::{share, AllowRo, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// The key and first two plaintext blocks of NIST SP 800-38A.
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const PLAINTEXT: [u8; 32] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+];
+// The ECB ciphertext, from F.1.1.
+const ECB_CIPHERTEXT: [u8; 32] = [
+    0x3a, 0xd7, 0x7b, 0xb4, 0x0d, 0x7a, 0x36, 0x60, 0xa8, 0x9e, 0xca, 0xf3, 0x24, 0x66, 0xef, 0x97,
+    0xf5, 0xd3, 0xd5, 0x85, 0x03, 0xb9, 0x69, 0x9d, 0xe7, 0x85, 0x89, 0x5a, 0x96, 0xfd, 0xba, 0xaf,
+];
+
+// Runs `command_id` with `source` shared, and returns the destination buffer
+// and the upcall arguments.
+fn run(command_id: u32, argument0: u32, source: &[u8], len: usize) -> (Vec<u8>, (u32, u32)) {
+    use libtock_platform::Syscalls;
+    let done = Cell::<Option<(u32, u32)>>::new(None);
+    let mut destination = vec![0; len];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_SOURCE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DESTINATION>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SOURCE>(allow_ro, source)
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DESTINATION>(
+            allow_rw,
+            &mut destination,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, command_id, argument0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    (destination, done.get().unwrap())
+}
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let aes = Aes::new();
+    assert!(aes.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        aes.command(SETUP, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        aes.command(SET_MODE, 4, 1).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(aes.command(SET_MODE, MODE_ECB, 1).is_success());
+    // No key is shared.
+    assert_eq!(
+        aes.command(SETUP, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        aes.command(CRYPT, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(aes.command(FINISH, 0, 0).is_success());
+    assert_eq!(aes.key(), None);
+}
+
+// Integration test that verifies Aes works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let aes = Aes::new();
+    kernel.add_driver(&aes);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_MODE, MODE_ECB, 1).is_success());
+    share::scope::<AllowRo<_, DRIVER_NUM, ALLOW_KEY>, _, _>(|allow_ro| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_ro, &KEY).unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, SETUP, 0, 0).is_success());
+    });
+    assert_eq!(aes.key(), Some(KEY));
+
+    assert_eq!(
+        run(CRYPT, 0, &PLAINTEXT[..16], 16),
+        (ECB_CIPHERTEXT[..16].to_vec(), (0, 16))
+    );
+    assert_eq!(
+        run(CRYPT, 0, &PLAINTEXT[16..], 16),
+        (ECB_CIPHERTEXT[16..].to_vec(), (0, 16))
+    );
+    // ECB only processes whole blocks.
+    share::scope::<AllowRo<_, DRIVER_NUM, ALLOW_SOURCE>, _, _>(|allow_ro| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SOURCE>(allow_ro, &[0; 15])
+            .unwrap();
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, CRYPT, 0, 0).get_failure(),
+            Some(ErrorCode::Invalid)
+        );
+    });
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_MODE, MODE_ECB, 0).is_success());
+    share::scope::<AllowRo<_, DRIVER_NUM, ALLOW_KEY>, _, _>(|allow_ro| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_ro, &KEY).unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, SETUP, 0, 0).is_success());
+    });
+    assert_eq!(
+        run(CRYPT, 0, &ECB_CIPHERTEXT, 32),
+        (PLAINTEXT.to_vec(), (0, 32))
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, FINISH, 0, 0).is_success());
+    assert_eq!(aes.key(), None);
+}
@@ -0,0 +1,89 @@
+//! Fake implementation of the Air Quality API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60007_air_quality.md
+//!
+//! Like the real API, `AirQuality` controls a fake CO2 and TVOC sensor. It provides
+//! a function `set_value` used to immediately call an upcall with the value of the pending measurement
+//! and a function 'set_value_sync' used to call the upcall when a read command is received.
+
+use crate::{DriverInfo, DriverShareRef};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::Cell;
+
+// The `upcall_on_command` field is set to Some(value) if an upcall(with value as its argument) should be called when read command is received,
+// or None otherwise. It was needed for testing `read_sync` library function which simulates a synchronous air quality read,
+// because it was impossible to schedule an upcall during the `synchronous` read in other ways.
+pub struct AirQuality {
+    pending_read: Cell<Option<u32>>,
+    upcall_on_command: Cell<Option<u32>>,
+    share_ref: DriverShareRef,
+}
+
+impl AirQuality {
+    pub fn new() -> std::rc::Rc<AirQuality> {
+        std::rc::Rc::new(AirQuality {
+            pending_read: Cell::new(None),
+            upcall_on_command: Cell::new(None),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending_read.get().is_some()
+    }
+    /// Returns the command ID of the measurement in progress, if any.
+    pub fn pending_read(&self) -> Option<u32> {
+        self.pending_read.get()
+    }
+    pub fn set_value(&self, value: u32) {
+        if self.pending_read.take().is_some() {
+            self.share_ref
+                .schedule_upcall(0, (value, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+    }
+    pub fn set_value_sync(&self, value: u32) {
+        self.upcall_on_command.set(Some(value));
+    }
+}
+
+impl crate::fake::SyscallDriver for AirQuality {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+
+            READ_CO2 | READ_TVOC => {
+                if self.is_busy() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.pending_read.set(Some(command_id));
+                if let Some(val) = self.upcall_on_command.take() {
+                    self.set_value(val);
+                }
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60007;
+
+// Command IDs
+
+const EXISTS: u32 = 0;
+const READ_CO2: u32 = 2;
+const READ_TVOC: u32 = 3;
@@ -0,0 +1,71 @@
+use crate::fake::{self, SyscallDriver};
+use fake::air_quality::*;
+use libtock_platform::{share, DefaultConfig, YieldNoWaitReturn};
+
+//Test the command implementation
+#[test]
+fn command() {
+    let air_quality = AirQuality::new();
+
+    assert!(air_quality.command(EXISTS, 1, 2).is_success());
+
+    assert!(air_quality.command(READ_CO2, 0, 0).is_success());
+    assert_eq!(air_quality.pending_read(), Some(READ_CO2));
+
+    assert_eq!(
+        air_quality.command(READ_CO2, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    assert_eq!(
+        air_quality.command(READ_TVOC, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+
+    air_quality.set_value(400);
+    assert!(!air_quality.is_busy());
+    assert!(air_quality.command(READ_TVOC, 0, 1).is_success());
+    assert_eq!(air_quality.pending_read(), Some(READ_TVOC));
+    air_quality.set_value(10);
+
+    air_quality.set_value_sync(400);
+    assert!(air_quality.command(READ_CO2, 0, 1).is_success());
+    assert!(air_quality.command(READ_CO2, 0, 1).is_success());
+}
+
+// Integration test that verifies AirQuality works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let air_quality = AirQuality::new();
+    kernel.add_driver(&air_quality);
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 1, 2).is_success());
+    assert!(fake::Syscalls::command(DRIVER_NUM, READ_CO2, 0, 0).is_success());
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, READ_TVOC, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    air_quality.set_value(400);
+    assert!(fake::Syscalls::command(DRIVER_NUM, READ_TVOC, 0, 1).is_success());
+
+    let listener = Cell::<Option<(u32,)>>::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &listener),
+            Ok(())
+        );
+
+        air_quality.set_value(12);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((12,)));
+
+        air_quality.set_value(20);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        air_quality.set_value_sync(410);
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_CO2, 0, 1).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((410,)));
+    });
+}
@@ -1,7 +1,11 @@
 //! Fake implementation of the Alarm API.
 //!
-//! Supports frequency and set_relative.
-//! Will schedule the upcall immediately.
+//! Supports frequency, time, stop, set_relative and set_absolute.
+//!
+//! An alarm created with `new` fires as soon as it is set, moving the time
+//! forward to its expiration. An alarm created with `new_controlled` keeps the
+//! time still until the test moves it with `advance`, and fires when the time
+//! reaches the expiration.
 
 use core::cell::Cell;
 use core::num::Wrapping;
@@ -12,17 +16,85 @@ use crate::{DriverInfo, DriverShareRef};
 pub struct Alarm {
     frequency_hz: u32,
     now: Cell<Wrapping<u32>>,
+    // The reference and dt of the alarm that is set, if any.
+    armed: Cell<Option<(Wrapping<u32>, Wrapping<u32>)>>,
+    fire_immediately: bool,
     share_ref: DriverShareRef,
 }
 
 impl Alarm {
     pub fn new(frequency_hz: u32) -> std::rc::Rc<Alarm> {
+        Self::with_mode(frequency_hz, true)
+    }
+
+    /// Creates an alarm whose time only moves when `advance` is called.
+    pub fn new_controlled(frequency_hz: u32) -> std::rc::Rc<Alarm> {
+        Self::with_mode(frequency_hz, false)
+    }
+
+    fn with_mode(frequency_hz: u32, fire_immediately: bool) -> std::rc::Rc<Alarm> {
         std::rc::Rc::new(Alarm {
             frequency_hz,
             now: Cell::new(Wrapping(0)),
+            armed: Cell::new(None),
+            fire_immediately,
             share_ref: Default::default(),
         })
     }
+
+    /// Returns the current value of the tick counter.
+    pub fn now(&self) -> u32 {
+        self.now.get().0
+    }
+
+    /// Sets the tick counter without firing the alarm, for example to test
+    /// behavior close to wraparound.
+    pub fn set_time(&self, now: u32) {
+        self.now.set(Wrapping(now));
+    }
+
+    /// Moves the time forward by `ticks`, wrapping around at `u32::MAX`. If
+    /// the alarm expires in that interval, its upcall is scheduled.
+    pub fn advance(&self, ticks: u32) {
+        let now = self.now.get();
+        self.now.set(now + Wrapping(ticks));
+        if let Some((reference, dt)) = self.armed.get() {
+            let elapsed = (now - reference).0 as u64 + ticks as u64;
+            if elapsed >= dt.0 as u64 {
+                self.fire(reference, dt);
+            }
+        }
+    }
+
+    /// Returns the expiration of the alarm, if it is set.
+    pub fn expiration(&self) -> Option<u32> {
+        self.armed.get().map(|(reference, dt)| (reference + dt).0)
+    }
+
+    fn set(&self, reference: Wrapping<u32>, dt: Wrapping<u32>) -> CommandReturn {
+        let now = self.now.get();
+        if self.fire_immediately {
+            // We're not actually sleeping, just ticking the timer.
+            // The semantics of sleeping aren't clear,
+            // so we're assuming that all future times are equal,
+            // and waking immediately.
+            let remaining = dt.0.saturating_sub((now - reference).0);
+            self.now.set(now + Wrapping(remaining));
+            self.fire(reference, dt);
+        } else if (now - reference) >= dt {
+            self.fire(reference, dt);
+        } else {
+            self.armed.set(Some((reference, dt)));
+        }
+        crate::command_return::success_u32((reference + dt).0)
+    }
+
+    fn fire(&self, reference: Wrapping<u32>, dt: Wrapping<u32>) {
+        self.armed.set(None);
+        self.share_ref
+            .schedule_upcall(subscribe::CALLBACK, (self.now(), (reference + dt).0, 0))
+            .expect("schedule_upcall failed");
+    }
 }
 
 impl crate::fake::SyscallDriver for Alarm {
@@ -34,22 +106,17 @@ impl crate::fake::SyscallDriver for Alarm {
         self.share_ref.replace(share_ref);
     }
 
-    fn command(&self, command_number: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+    fn command(&self, command_number: u32, argument0: u32, argument1: u32) -> CommandReturn {
         match command_number {
+            command::DRIVER_CHECK => crate::command_return::success(),
             command::FREQUENCY => crate::command_return::success_u32(self.frequency_hz),
-            command::SET_RELATIVE => {
-                // We're not actually sleeping, just ticking the timer.
-                // The semantics of sleeping aren't clear,
-                // so we're assuming that all future times are equal,
-                // and waking immediately.
-                let relative = argument0;
-                let wake = self.now.get() + Wrapping(relative);
-                self.share_ref
-                    .schedule_upcall(subscribe::CALLBACK, (wake.0, 0, 0))
-                    .expect("schedule_upcall failed");
-                self.now.set(wake);
-                crate::command_return::success_u32(wake.0)
-            }
+            command::TIME => crate::command_return::success_u32(self.now()),
+            command::STOP => match self.armed.take() {
+                Some(_) => crate::command_return::success(),
+                None => crate::command_return::failure(ErrorCode::Already),
+            },
+            command::SET_RELATIVE => self.set(self.now.get(), Wrapping(argument0)),
+            command::SET_ABSOLUTE => self.set(Wrapping(argument0), Wrapping(argument1)),
             _ => crate::command_return::failure(ErrorCode::NoSupport),
         }
     }
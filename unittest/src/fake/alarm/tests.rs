@@ -1,5 +1,6 @@
 use crate::fake;
 use fake::alarm::*;
+use libtock_platform::ErrorCode;
 
 // Tests the command implementation.
 #[test]
@@ -11,4 +12,79 @@ fn command() {
         alarm.command(command::FREQUENCY, 1, 2).get_success_u32(),
         Some(10)
     );
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(0)
+    );
+}
+
+#[test]
+fn controlled_command() {
+    use fake::SyscallDriver;
+    let alarm = Alarm::new_controlled(10);
+
+    alarm.advance(5);
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(5)
+    );
+    assert_eq!(
+        alarm
+            .command(command::SET_RELATIVE, 10, 0)
+            .get_success_u32(),
+        Some(15)
+    );
+    assert_eq!(alarm.expiration(), Some(15));
+    assert_eq!(
+        alarm
+            .command(command::SET_ABSOLUTE, 0, 20)
+            .get_success_u32(),
+        Some(20)
+    );
+    assert_eq!(alarm.expiration(), Some(20));
+    assert!(alarm.command(command::STOP, 0, 0).is_success());
+    assert_eq!(alarm.expiration(), None);
+    assert_eq!(
+        alarm.command(command::STOP, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+}
+
+// Integration test that verifies Alarm works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let alarm = Alarm::new_controlled(1000);
+    kernel.add_driver(&alarm);
+
+    let fired = core::cell::Cell::<Option<(u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::CALLBACK }>(
+            subscribe, &fired,
+        )
+        .unwrap();
+
+        // The expiration wraps around.
+        alarm.set_time(u32::MAX - 9);
+        assert!(fake::Syscalls::command(DRIVER_NUM, command::SET_RELATIVE, 20, 0).is_success_u32());
+        alarm.advance(19);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        alarm.advance(3);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fired.get(), Some((12, 10)));
+        assert_eq!(alarm.expiration(), None);
+
+        // An alarm whose expiration has passed fires immediately.
+        assert!(fake::Syscalls::command(DRIVER_NUM, command::SET_ABSOLUTE, 0, 5).is_success_u32());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fired.get(), Some((12, 5)));
+
+        // A stopped alarm does not fire.
+        assert!(fake::Syscalls::command(DRIVER_NUM, command::SET_RELATIVE, 5, 0).is_success_u32());
+        assert!(fake::Syscalls::command(DRIVER_NUM, command::STOP, 0, 0).is_success());
+        alarm.advance(10);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
 }
@@ -0,0 +1,103 @@
+//! Fake implementation of the analog comparator API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00007_analog_comparator.md
+//!
+//! Like the real API, `AnalogComparator` controls a set of fake comparator
+//! channels. It provides a function `set_output` used to change whether the
+//! positive input of a channel is above its negative input, which triggers an
+//! interrupt when the output rises and interrupts are enabled.
+
+use crate::{DriverInfo, DriverShareRef};
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct AnalogComparator<const CHANNELS_COUNT: usize> {
+    channels: [Channel; CHANNELS_COUNT],
+    share_ref: DriverShareRef,
+}
+
+struct Channel {
+    output: Cell<bool>,
+    comparing: Cell<bool>,
+}
+
+impl<const CHANNELS_COUNT: usize> AnalogComparator<CHANNELS_COUNT> {
+    pub fn new() -> std::rc::Rc<AnalogComparator<CHANNELS_COUNT>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const LOW: Channel = Channel {
+            output: Cell::new(false),
+            comparing: Cell::new(false),
+        };
+        std::rc::Rc::new(AnalogComparator {
+            channels: [LOW; CHANNELS_COUNT],
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets whether the positive input of `channel` is above its negative
+    /// input. Panics if `channel` does not exist.
+    pub fn set_output(&self, channel: u32, output: bool) {
+        let state = &self.channels[channel as usize];
+        if output && !state.output.get() && state.comparing.get() {
+            self.share_ref
+                .schedule_upcall(0, (channel, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+        state.output.set(output);
+    }
+
+    /// Returns true if interrupts are enabled on `channel`.
+    pub fn is_comparing(&self, channel: u32) -> bool {
+        self.channels[channel as usize].comparing.get()
+    }
+}
+
+impl<const CHANNELS_COUNT: usize> crate::fake::SyscallDriver for AnalogComparator<CHANNELS_COUNT> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        if command_id == EXISTS {
+            return crate::command_return::success();
+        }
+        if command_id == COUNT {
+            return crate::command_return::success_u32(CHANNELS_COUNT as u32);
+        }
+        let channel = match self.channels.get(argument0 as usize) {
+            None => return crate::command_return::failure(ErrorCode::Invalid),
+            Some(channel) => channel,
+        };
+        match command_id {
+            COMPARE => crate::command_return::success_u32(channel.output.get() as u32),
+            START_COMPARING => {
+                channel.comparing.set(true);
+                crate::command_return::success()
+            }
+            STOP_COMPARING => {
+                channel.comparing.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x7;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const COMPARE: u32 = 1;
+const START_COMPARING: u32 = 2;
+const STOP_COMPARING: u32 = 3;
+const COUNT: u32 = 4;
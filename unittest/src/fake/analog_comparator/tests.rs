@@ -0,0 +1,55 @@
+use crate::fake::{self, SyscallDriver};
+use fake::analog_comparator::*;
+use libtock_platform::{share, DefaultConfig, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let comparator = AnalogComparator::<2>::new();
+    assert!(comparator.command(EXISTS, 0, 0).is_success());
+    assert_eq!(comparator.command(COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(comparator.command(COMPARE, 1, 0).get_success_u32(), Some(0));
+    comparator.set_output(1, true);
+    assert_eq!(comparator.command(COMPARE, 1, 0).get_success_u32(), Some(1));
+    assert_eq!(
+        comparator.command(COMPARE, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(comparator.command(START_COMPARING, 0, 0).is_success());
+    assert!(comparator.is_comparing(0));
+    assert!(!comparator.is_comparing(1));
+    assert!(comparator.command(STOP_COMPARING, 0, 0).is_success());
+    assert!(!comparator.is_comparing(0));
+}
+
+// Integration test that verifies AnalogComparator works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let comparator = AnalogComparator::<2>::new();
+    kernel.add_driver(&comparator);
+
+    let interrupt = Cell::<Option<(u32,)>>::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &interrupt)
+            .unwrap();
+
+        // No interrupt while interrupts are disabled.
+        comparator.set_output(1, true);
+        comparator.set_output(1, false);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, START_COMPARING, 1, 0).is_success());
+        comparator.set_output(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(interrupt.get(), Some((1,)));
+
+        // Only rising outputs trigger interrupts.
+        comparator.set_output(1, true);
+        comparator.set_output(1, false);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
@@ -0,0 +1,183 @@
+//! Fake implementation of the BLE advertising API.
+//!
+//! Like the real API, `Ble` broadcasts the advertising data shared with it,
+//! which tests can retrieve with `take_advertisements`. Scan reports are
+//! injected with `add_scan_report`: each report is delivered to the process
+//! while it is scanning, one report per scan.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+/// An advertisement started by the process.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Advertisement {
+    pub advertising_type: u32,
+    pub interval_ms: u32,
+    pub data: Vec<u8>,
+}
+
+pub struct Ble {
+    advertising: Cell<bool>,
+    advertisements: RefCell<Vec<Advertisement>>,
+    scanning: Cell<bool>,
+    scan_reports: RefCell<VecDeque<Vec<u8>>>,
+    tx_power: Cell<Option<i8>>,
+    advertising_buffer: Cell<RoAllowBuffer>,
+    scan_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Ble {
+    pub fn new() -> std::rc::Rc<Ble> {
+        std::rc::Rc::new(Ble {
+            advertising: Cell::new(false),
+            advertisements: Default::default(),
+            scanning: Cell::new(false),
+            scan_reports: Default::default(),
+            tx_power: Cell::new(None),
+            advertising_buffer: Default::default(),
+            scan_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn is_advertising(&self) -> bool {
+        self.advertising.get()
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scanning.get()
+    }
+
+    /// Returns the transmit power most recently configured, in dBm.
+    pub fn tx_power(&self) -> Option<i8> {
+        self.tx_power.get()
+    }
+
+    /// Returns the advertisements started so far, and clears them.
+    pub fn take_advertisements(&self) -> Vec<Advertisement> {
+        self.advertisements.take()
+    }
+
+    /// Queues an advertising PDU (header, address and advertising data) to be
+    /// received by the process. It is delivered immediately if the process is
+    /// scanning, otherwise when it next starts scanning.
+    pub fn add_scan_report(&self, pdu: &[u8]) {
+        self.scan_reports.borrow_mut().push_back(pdu.to_vec());
+        if self.scanning.get() {
+            self.deliver_scan_report();
+        }
+    }
+
+    fn deliver_scan_report(&self) {
+        let pdu = match self.scan_reports.borrow_mut().pop_front() {
+            Some(pdu) => pdu,
+            None => return,
+        };
+        let mut buffer = self.scan_buffer.borrow_mut();
+        let len = std::cmp::min(pdu.len(), buffer.len());
+        buffer[..len].copy_from_slice(&pdu[..len]);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_SCAN, (0, len as u32, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Ble {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_ADVERTISING_DATA {
+            Ok(self.advertising_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SCAN_BUFFER {
+            Ok(self.scan_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            START_ADVERTISING => {
+                if self.advertising.get() || self.scanning.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                let buffer = self.advertising_buffer.take();
+                self.advertisements.borrow_mut().push(Advertisement {
+                    advertising_type: argument0,
+                    interval_ms: argument1,
+                    data: buffer.to_vec(),
+                });
+                self.advertising_buffer.set(buffer);
+                self.advertising.set(true);
+                crate::command_return::success()
+            }
+            STOP => {
+                if !self.advertising.get() && !self.scanning.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.advertising.set(false);
+                self.scanning.set(false);
+                crate::command_return::success()
+            }
+            SET_TX_POWER => match argument0 {
+                0..=10 | 0xEC..=0xFF => {
+                    self.tx_power.set(Some(argument0 as u8 as i8));
+                    crate::command_return::success()
+                }
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            PASSIVE_SCAN => {
+                if self.advertising.get() || self.scanning.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.scanning.set(true);
+                self.deliver_scan_report();
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30000;
+
+// Command IDs
+const START_ADVERTISING: u32 = 0;
+const STOP: u32 = 1;
+const SET_TX_POWER: u32 = 2;
+const PASSIVE_SCAN: u32 = 5;
+
+const SUBSCRIBE_SCAN: u32 = 0;
+const ALLOW_ADVERTISING_DATA: u32 = 0;
+const ALLOW_SCAN_BUFFER: u32 = 0;
@@ -0,0 +1,92 @@
+use crate::fake::{self, SyscallDriver};
+use fake::ble::*;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let ble = Ble::new();
+    assert_eq!(ble.command(STOP, 0, 0).get_failure(), Some(ErrorCode::Busy));
+
+    assert!(ble.command(START_ADVERTISING, 2, 100).is_success());
+    assert!(ble.is_advertising());
+    assert_eq!(
+        ble.command(PASSIVE_SCAN, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    assert_eq!(
+        ble.take_advertisements(),
+        [Advertisement {
+            advertising_type: 2,
+            interval_ms: 100,
+            data: vec![],
+        }]
+    );
+    assert!(ble.command(STOP, 0, 0).is_success());
+    assert!(!ble.is_advertising());
+
+    assert!(ble.command(PASSIVE_SCAN, 0, 0).is_success());
+    assert!(ble.is_scanning());
+    assert!(ble.command(STOP, 0, 0).is_success());
+    assert!(!ble.is_scanning());
+
+    assert!(ble.command(SET_TX_POWER, 0xEC, 0).is_success());
+    assert_eq!(ble.tx_power(), Some(-20));
+    assert_eq!(
+        ble.command(SET_TX_POWER, 11, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(ble.tx_power(), Some(-20));
+}
+
+// Integration test that verifies Ble works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ble = Ble::new();
+    kernel.add_driver(&ble);
+
+    share::scope(|allow_ro| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_ADVERTISING_DATA>(
+            allow_ro,
+            &[2, 1, 6],
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, START_ADVERTISING, 0, 20).is_success());
+    });
+    assert_eq!(ble.take_advertisements()[0].data, [2, 1, 6]);
+    assert!(fake::Syscalls::command(DRIVER_NUM, STOP, 0, 0).is_success());
+
+    ble.add_scan_report(&[0x02, 0x07, 1, 2, 3, 4, 5, 6, 0]);
+    let mut buffer = [0; 8];
+    let listener = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_SCAN_BUFFER>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SCAN>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_SCAN_BUFFER>(
+            allow_rw,
+            &mut buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SCAN>(
+            subscribe, &listener,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, PASSIVE_SCAN, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        // The report is truncated to the buffer's length.
+        assert_eq!(listener.get(), Some((0, 8)));
+
+        ble.add_scan_report(&[0x02, 0x06, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    assert_eq!(buffer, [0x02, 0x06, 6, 5, 4, 3, 2, 1]);
+}
@@ -4,6 +4,10 @@
 //! Like the real API, `Console` stores each message written to it.
 //! The resulting byte stream can be retrieved via `take_bytes`
 //! for use in unit tests.
+//!
+//! Reads return the available input immediately. A read started when no
+//! input is available waits until input is added with `add_input`, or until
+//! it is aborted.
 
 use core::cell::{Cell, RefCell};
 use core::cmp;
@@ -18,6 +22,8 @@ pub struct Console {
     read_buffer: RefCell<RwAllowBuffer>,
     /// To be returned on read
     input: Cell<Vec<u8>>,
+    /// The length of the read waiting for input, if any.
+    pending_read: Cell<Option<usize>>,
 
     share_ref: DriverShareRef,
 }
@@ -33,6 +39,7 @@ impl Console {
             buffer: Default::default(),
             read_buffer: Default::default(),
             input: Cell::new(Vec::from(inputs)),
+            pending_read: Cell::new(None),
             share_ref: Default::default(),
         })
     }
@@ -42,6 +49,36 @@ impl Console {
     pub fn take_bytes(&self) -> Vec<u8> {
         self.messages.take()
     }
+
+    /// Adds bytes to be returned on read. If a read is waiting for input, it
+    /// completes.
+    pub fn add_input(&self, input: &[u8]) {
+        let mut bytes = self.input.take();
+        bytes.extend_from_slice(input);
+        self.input.set(bytes);
+        if let Some(count_wanted) = self.pending_read.take() {
+            self.complete_read(count_wanted);
+        }
+    }
+
+    /// Returns true if a read is waiting for input.
+    pub fn is_reading(&self) -> bool {
+        self.pending_read.get().is_some()
+    }
+
+    fn complete_read(&self, count_wanted: usize) {
+        let bytes = self.input.take();
+        let count_wanted = cmp::min(count_wanted, bytes.len());
+        let to_send = &bytes[..count_wanted];
+        let to_keep = &bytes[count_wanted..];
+        self.input.set(Vec::from(to_keep));
+
+        let count_available = to_send.len();
+        self.read_buffer.borrow_mut()[..count_wanted].copy_from_slice(to_send);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_READ, (0, count_available as u32, 0))
+            .expect("Unable to schedule upcall {}");
+    }
 }
 
 impl crate::fake::SyscallDriver for Console {
@@ -92,18 +129,25 @@ impl crate::fake::SyscallDriver for Console {
                     .expect("Unable to schedule upcall {}");
             }
             READ => {
+                if self.pending_read.get().is_some() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
                 let count_wanted = argument0 as usize;
-                let bytes = self.input.take();
-                let count_wanted = cmp::min(count_wanted, bytes.len());
-                let to_send = &bytes[..count_wanted];
-                let to_keep = &bytes[count_wanted..];
-                self.input.set(Vec::from(to_keep));
-
-                let count_available = to_send.len();
-                self.read_buffer.borrow_mut()[..count_wanted].copy_from_slice(to_send);
-                self.share_ref
-                    .schedule_upcall(SUBSCRIBE_READ, (0, count_available as u32, 0))
-                    .expect("Unable to schedule upcall {}");
+                let input = self.input.take();
+                let input_available = !input.is_empty();
+                self.input.set(input);
+                if input_available {
+                    self.complete_read(count_wanted);
+                } else {
+                    self.pending_read.set(Some(count_wanted));
+                }
+            }
+            ABORT => {
+                if self.pending_read.take().is_some() {
+                    self.share_ref
+                        .schedule_upcall(SUBSCRIBE_READ, (ErrorCode::Cancel as u32, 0, 0))
+                        .expect("Unable to schedule upcall {}");
+                }
             }
             _ => return crate::command_return::failure(ErrorCode::NoSupport),
         }
@@ -124,7 +168,7 @@ const DRIVER_NUM: u32 = 1;
 const DRIVER_CHECK: u32 = 0;
 const WRITE: u32 = 1;
 const READ: u32 = 2;
-//const ABORT: u32 = 3;
+const ABORT: u32 = 3;
 const SUBSCRIBE_WRITE: u32 = 1;
 const SUBSCRIBE_READ: u32 = 2;
 const ALLOW_WRITE: u32 = 1;
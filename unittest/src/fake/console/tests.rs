@@ -62,3 +62,78 @@ fn kernel_integration() {
         );
     });
 }
+
+// Tests reads that wait for input, and aborting them.
+#[test]
+fn pending_read() {
+    use libtock_platform::{ErrorCode, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let mut buf = [0; 4];
+    let called = core::cell::Cell::<Option<(u32, u32)>>::new(None);
+    share::scope::<
+        (
+            libtock_platform::AllowRw<
+                _,
+                { fake::console::DRIVER_NUM },
+                { fake::console::ALLOW_READ },
+            >,
+            libtock_platform::Subscribe<
+                _,
+                { fake::console::DRIVER_NUM },
+                { fake::console::SUBSCRIBE_READ },
+            >,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::ALLOW_READ },
+        >(allow_rw, &mut buf)
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::SUBSCRIBE_READ },
+        >(subscribe, &called)
+        .unwrap();
+
+        // With no input, the read waits.
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 4, 0)
+                .is_success()
+        );
+        assert!(console.is_reading());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 4, 0)
+                .get_failure(),
+            Some(ErrorCode::Busy)
+        );
+        console.add_input(b"ab");
+        assert!(!console.is_reading());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.get(), Some((0, 2)));
+
+        // Aborting a waiting read completes it with Cancel.
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::READ, 4, 0)
+                .is_success()
+        );
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::ABORT, 0, 0)
+                .is_success()
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.get(), Some((ErrorCode::Cancel as u32, 0)));
+        assert!(!console.is_reading());
+    });
+    assert_eq!(&buf[..2], b"ab");
+}
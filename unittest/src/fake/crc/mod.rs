@@ -0,0 +1,92 @@
+//! Fake implementation of the CRC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40002_crc.md
+//!
+//! Like the real API, `Crc` computes the CRC of the allowed buffer. It uses
+//! the `crc` crate, so tests can compare the results against reference
+//! values. Every computation completes immediately, by scheduling its upcall.
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::RefCell;
+
+pub struct Crc {
+    buffer: RefCell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Crc {
+    pub fn new() -> std::rc::Rc<Crc> {
+        std::rc::Rc::new(Crc {
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+}
+
+impl crate::fake::SyscallDriver for Crc {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_BUFFER {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            COMPUTE => {
+                let buffer = self.buffer.borrow();
+                let data = match buffer.get(..argument1 as usize) {
+                    None => return crate::command_return::failure(ErrorCode::Size),
+                    Some(data) => data,
+                };
+                let crc = match argument0 {
+                    ALGORITHM_CRC32 => crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(data),
+                    ALGORITHM_CRC32C => crc::Crc::<u32>::new(&crc::CRC_32_ISCSI).checksum(data),
+                    ALGORITHM_CRC16_CCITT => {
+                        crc::Crc::<u16>::new(&crc::CRC_16_IBM_3740).checksum(data) as u32
+                    }
+                    _ => return crate::command_return::failure(ErrorCode::NoSupport),
+                };
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (0, crc, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40002;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const COMPUTE: u32 = 1;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_BUFFER: u32 = 0;
+
+const ALGORITHM_CRC32: u32 = 0;
+const ALGORITHM_CRC32C: u32 = 1;
+const ALGORITHM_CRC16_CCITT: u32 = 2;
@@ -0,0 +1,67 @@
+use crate::fake::{self, SyscallDriver};
+use fake::crc::*;
+use libtock_platform::{share, AllowRo, DefaultConfig, Subscribe, YieldNoWaitReturn};
+use std::cell::Cell;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let crc = Crc::new();
+    assert!(crc.command(EXISTS, 0, 0).is_success());
+    assert!(crc.command(COMPUTE, ALGORITHM_CRC32, 0).is_success());
+    // No buffer is shared.
+    assert_eq!(
+        crc.command(COMPUTE, ALGORITHM_CRC32, 1).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(
+        crc.command(COMPUTE, 3, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+}
+
+// Integration test that verifies Crc works with fake::Kernel and
+// libtock_platform::Syscalls. The CRCs are the check values of the algorithms,
+// from the catalogue of parametrised CRC algorithms.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let crc = Crc::new();
+    kernel.add_driver(&crc);
+
+    let done = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_BUFFER>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_BUFFER>(
+            allow_ro,
+            b"123456789abc",
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        for (algorithm, check) in [
+            (ALGORITHM_CRC32, 0xcbf4_3926),
+            (ALGORITHM_CRC32C, 0xe306_9283),
+            (ALGORITHM_CRC16_CCITT, 0x29b1),
+        ] {
+            assert!(fake::Syscalls::command(DRIVER_NUM, COMPUTE, algorithm, 9).is_success());
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+            assert_eq!(done.get(), Some((0, check)));
+        }
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, COMPUTE, ALGORITHM_CRC32, 13).get_failure(),
+            Some(ErrorCode::Size)
+        );
+    });
+}
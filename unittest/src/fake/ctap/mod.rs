@@ -0,0 +1,152 @@
+//! Fake implementation of the CTAP HID API.
+//!
+//! Like the real API, `Ctap` exchanges packets with the host. Sent packets are
+//! captured, and can be retrieved with `take_sent`. Packets from the host are
+//! added by tests with `add_received`, and are delivered when the process is
+//! waiting for one.
+
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+pub struct Ctap {
+    sent: RefCell<Vec<[u8; PACKET_LEN]>>,
+    received: RefCell<VecDeque<[u8; PACKET_LEN]>>,
+    receiving: Cell<bool>,
+    receive_buffer: RefCell<RwAllowBuffer>,
+    send_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Ctap {
+    pub fn new() -> std::rc::Rc<Ctap> {
+        std::rc::Rc::new(Ctap {
+            sent: Default::default(),
+            received: Default::default(),
+            receiving: Cell::new(false),
+            receive_buffer: Default::default(),
+            send_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the packets sent since the last call, in the order they were
+    /// sent.
+    pub fn take_sent(&self) -> Vec<[u8; PACKET_LEN]> {
+        self.sent.take()
+    }
+
+    /// Queues a packet from the host. It is delivered immediately if the
+    /// process is waiting for a packet.
+    pub fn add_received(&self, packet: [u8; PACKET_LEN]) {
+        self.received.borrow_mut().push_back(packet);
+        self.deliver();
+    }
+
+    /// Returns true if the process is waiting for a packet.
+    pub fn is_receiving(&self) -> bool {
+        self.receiving.get()
+    }
+
+    // Delivers the next queued packet, if the process is waiting for one and
+    // has shared a large enough buffer.
+    fn deliver(&self) {
+        if !self.receiving.get() || self.receive_buffer.borrow().len() < PACKET_LEN {
+            return;
+        }
+        let packet = match self.received.borrow_mut().pop_front() {
+            None => return,
+            Some(packet) => packet,
+        };
+        self.receive_buffer.borrow_mut()[..PACKET_LEN].copy_from_slice(&packet);
+        self.receiving.set(false);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (RECEIVED, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Ctap {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_RECEIVE => Ok(self.receive_buffer.replace(buffer)),
+            ALLOW_SEND => Ok(self.send_buffer.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn command(&self, command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SEND => {
+                let buffer = self.send_buffer.borrow();
+                let packet = match buffer.get(..PACKET_LEN) {
+                    None => return crate::command_return::failure(ErrorCode::Size),
+                    Some(packet) => packet.try_into().unwrap(),
+                };
+                self.sent.borrow_mut().push(packet);
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (SENT, 0, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            RECEIVE => {
+                if self.receiving.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                if self.receive_buffer.borrow().len() < PACKET_LEN {
+                    return crate::command_return::failure(ErrorCode::Size);
+                }
+                self.receiving.set(true);
+                self.deliver();
+                crate::command_return::success()
+            }
+            CANCEL => {
+                if !self.receiving.get() {
+                    return crate::command_return::failure(ErrorCode::Already);
+                }
+                self.receiving.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20009;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SEND: u32 = 1;
+const RECEIVE: u32 = 2;
+const CANCEL: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_RECEIVE: u32 = 0;
+const ALLOW_SEND: u32 = 1;
+
+// The first argument of the DONE upcall.
+const RECEIVED: u32 = 0;
+const SENT: u32 = 1;
+
+const PACKET_LEN: usize = 64;
@@ -0,0 +1,92 @@
+use crate::fake::{self, SyscallDriver};
+use fake::ctap::*;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let ctap = Ctap::new();
+    assert!(ctap.command(EXISTS, 0, 0).is_success());
+    // No buffers are shared.
+    assert_eq!(
+        ctap.command(SEND, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(
+        ctap.command(RECEIVE, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(
+        ctap.command(CANCEL, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+}
+
+// Integration test that verifies Ctap works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ctap = Ctap::new();
+    kernel.add_driver(&ctap);
+
+    let done = Cell::<Option<(u32,)>>::new(None);
+    let mut receive_buffer = [0; PACKET_LEN];
+    let mut send_buffer = [7; PACKET_LEN];
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_RECEIVE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_SEND>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_receive, allow_send, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_RECEIVE>(
+            allow_receive,
+            &mut receive_buffer,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_SEND>(
+            allow_send,
+            &mut send_buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((SENT,)));
+        assert_eq!(ctap.take_sent(), [[7; PACKET_LEN]]);
+
+        // A packet received while waiting is delivered immediately.
+        assert!(fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).is_success());
+        assert!(ctap.is_receiving());
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).get_failure(),
+            Some(ErrorCode::Busy)
+        );
+        ctap.add_received([1; PACKET_LEN]);
+        assert!(!ctap.is_receiving());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((RECEIVED,)));
+
+        // A packet received earlier is delivered when the process waits.
+        ctap.add_received([2; PACKET_LEN]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+
+        // A cancelled receive does not deliver packets.
+        assert!(fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, CANCEL, 0, 0).is_success());
+        ctap.add_received([3; PACKET_LEN]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+    assert_eq!(receive_buffer, [2; PACKET_LEN]);
+}
@@ -0,0 +1,70 @@
+//! Fake implementation of the DAC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00006_dac.md
+//!
+//! Like the real API, `Dac` controls a fake DAC. It provides a function
+//! `value` used to retrieve the value the DAC is currently converting.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct Dac {
+    resolution_bits: Cell<u32>,
+    value: Cell<Option<u32>>,
+}
+
+impl Dac {
+    pub fn new() -> std::rc::Rc<Dac> {
+        std::rc::Rc::new(Dac {
+            resolution_bits: Cell::new(DEFAULT_RESOLUTION_BITS),
+            value: Cell::new(None),
+        })
+    }
+
+    /// Sets the number of bits of the values the fake DAC accepts. Like real
+    /// hardware, it rejects larger values with `Invalid`.
+    pub fn set_resolution_bits(&self, resolution_bits: u32) {
+        self.resolution_bits.set(resolution_bits);
+    }
+
+    /// Returns the value the DAC is converting, or `None` if it was never
+    /// set.
+    pub fn value(&self) -> Option<u32> {
+        self.value.get()
+    }
+}
+
+impl crate::fake::SyscallDriver for Dac {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SET_VALUE => {
+                if argument0 as u64 >= 1 << self.resolution_bits.get() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.value.set(Some(argument0));
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x6;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SET_VALUE: u32 = 1;
+
+const DEFAULT_RESOLUTION_BITS: u32 = 10;
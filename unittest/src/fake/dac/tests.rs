@@ -0,0 +1,33 @@
+use crate::fake::{self, SyscallDriver};
+use fake::dac::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let dac = Dac::new();
+    assert!(dac.command(EXISTS, 0, 0).is_success());
+    assert_eq!(dac.value(), None);
+    assert!(dac.command(SET_VALUE, 1023, 0).is_success());
+    assert_eq!(dac.value(), Some(1023));
+    assert_eq!(
+        dac.command(SET_VALUE, 1024, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(dac.value(), Some(1023));
+
+    dac.set_resolution_bits(12);
+    assert!(dac.command(SET_VALUE, 4095, 0).is_success());
+}
+
+// Integration test that verifies Dac works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let dac = Dac::new();
+    kernel.add_driver(&dac);
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_VALUE, 512, 0).is_success());
+    assert_eq!(dac.value(), Some(512));
+}
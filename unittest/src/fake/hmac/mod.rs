@@ -0,0 +1,185 @@
+//! Fake implementation of the HMAC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40003_hmac.md
+//!
+//! Like the real API, `Hmac` computes HMAC-SHA-2 codes, using the `hmac` and
+//! `sha2` crates so tests can compare the results against known test vectors.
+//! The key is loaded from the key buffer by the first operation of each
+//! computation. Every operation completes immediately, by scheduling its
+//! upcall.
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+use hmac::Mac;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::{Cell, RefCell};
+
+pub struct Hmac {
+    algorithm: Cell<Option<u32>>,
+    key: RefCell<Option<Vec<u8>>>,
+    message: RefCell<Vec<u8>>,
+    key_buffer: RefCell<RoAllowBuffer>,
+    data_buffer: RefCell<RoAllowBuffer>,
+    dest_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Hmac {
+    pub fn new() -> std::rc::Rc<Hmac> {
+        std::rc::Rc::new(Hmac {
+            algorithm: Cell::new(None),
+            key: Default::default(),
+            message: Default::default(),
+            key_buffer: Default::default(),
+            data_buffer: Default::default(),
+            dest_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the key of the current computation, or `None` if it has not
+    /// been loaded yet.
+    pub fn key(&self) -> Option<Vec<u8>> {
+        self.key.borrow().clone()
+    }
+
+    /// Returns the data added to the current computation so far.
+    pub fn message(&self) -> Vec<u8> {
+        self.message.borrow().clone()
+    }
+
+    // Loads the key if this is the first operation of the computation.
+    fn load_key(&self) {
+        if self.key.borrow().is_none() {
+            self.key.replace(Some(self.key_buffer.borrow().to_vec()));
+        }
+    }
+
+    fn update(&self) {
+        self.load_key();
+        let data = self.data_buffer.borrow();
+        self.message.borrow_mut().extend_from_slice(&data);
+    }
+
+    // Writes the MAC of the message into the destination buffer, and starts a
+    // new computation.
+    fn finish(&self, algorithm: u32) -> CommandReturn {
+        let key = self.key.take().unwrap_or_default();
+        let message = self.message.take();
+        let mac = match algorithm {
+            ALGORITHM_SHA256 => compute::<hmac::Hmac<sha2::Sha256>>(&key, &message),
+            ALGORITHM_SHA384 => compute::<hmac::Hmac<sha2::Sha384>>(&key, &message),
+            _ => compute::<hmac::Hmac<sha2::Sha512>>(&key, &message),
+        };
+        let mut buffer = self.dest_buffer.borrow_mut();
+        if buffer.len() < mac.len() {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        buffer[..mac.len()].copy_from_slice(&mac);
+        self.complete()
+    }
+
+    fn complete(&self) -> CommandReturn {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (0, 0, 0))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+fn compute<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as hmac::digest::KeyInit>::new_from_slice(key).expect("invalid key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+impl crate::fake::SyscallDriver for Hmac {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key_buffer.replace(buffer)),
+            ALLOW_DATA => Ok(self.data_buffer.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DEST {
+            Ok(self.dest_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        if command_id == EXISTS {
+            return crate::command_return::success();
+        }
+        if command_id == SET_ALGORITHM {
+            if argument0 > ALGORITHM_SHA512 {
+                return crate::command_return::failure(ErrorCode::NoSupport);
+            }
+            self.algorithm.set(Some(argument0));
+            self.key.replace(None);
+            self.message.borrow_mut().clear();
+            return crate::command_return::success();
+        }
+        let algorithm = match self.algorithm.get() {
+            None => return crate::command_return::failure(ErrorCode::Invalid),
+            Some(algorithm) => algorithm,
+        };
+        match command_id {
+            RUN => {
+                self.update();
+                self.finish(algorithm)
+            }
+            UPDATE => {
+                self.update();
+                self.complete()
+            }
+            FINISH => {
+                self.load_key();
+                self.finish(algorithm)
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const RUN: u32 = 2;
+const UPDATE: u32 = 3;
+const FINISH: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_DATA: u32 = 1;
+const ALLOW_DEST: u32 = 0;
+
+const ALGORITHM_SHA256: u32 = 0;
+const ALGORITHM_SHA384: u32 = 1;
+const ALGORITHM_SHA512: u32 = 2;
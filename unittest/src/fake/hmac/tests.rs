@@ -0,0 +1,100 @@
+use crate::fake::{self, SyscallDriver};
+use fake::hmac::*;
+use libtock_platform::{share, AllowRo, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// HMAC-SHA-256 with the key "Jefe", from test case 2 of RFC 4231.
+const HMAC_SHA256: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let hmac = Hmac::new();
+    assert!(hmac.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        hmac.command(RUN, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        hmac.command(SET_ALGORITHM, 3, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(hmac
+        .command(SET_ALGORITHM, ALGORITHM_SHA256, 0)
+        .is_success());
+    assert_eq!(hmac.key(), None);
+    assert!(hmac.command(UPDATE, 0, 0).is_success());
+    assert_eq!(hmac.key(), Some(vec![]));
+    // No destination buffer is shared.
+    assert_eq!(
+        hmac.command(FINISH, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+}
+
+// Integration test that verifies Hmac works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let hmac = Hmac::new();
+    kernel.add_driver(&hmac);
+
+    let done = Cell::<Option<(u32,)>>::new(None);
+    let mut mac = [0; 32];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_KEY>,
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_data, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_key, b"Jefe")
+            .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(
+            allow_data,
+            b"what do ya want ",
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(
+            fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, ALGORITHM_SHA256, 0).is_success()
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, UPDATE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0,)));
+        assert_eq!(hmac.key(), Some(b"Jefe".to_vec()));
+    });
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DEST>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        // The key was loaded by the first update, so it is not shared again.
+        let (allow_data, allow_rw) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(
+            allow_data,
+            b"for nothing?",
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DEST>(allow_rw, &mut mac)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, UPDATE, 0, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, FINISH, 0, 0).is_success());
+        assert_eq!(hmac.key(), None);
+    });
+    assert_eq!(mac, HMAC_SHA256);
+}
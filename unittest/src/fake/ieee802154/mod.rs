@@ -0,0 +1,256 @@
+//! Fake implementation of the IEEE 802.15.4 raw radio API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30001_ieee802154.md
+//!
+//! Frames transmitted by the process are captured and can be retrieved with
+//! `take_transmitted_frames`. Received frames are injected with
+//! `add_received_frame`: they are written into the ring buffer shared by the
+//! process, or queued until the process shares one.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+/// The radio configuration set by the process.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct RadioConfig {
+    pub short_address: u16,
+    pub long_address: u64,
+    pub pan: u16,
+    pub channel: u8,
+    pub tx_power: i8,
+}
+
+pub struct Ieee802154 {
+    on: Cell<bool>,
+    acknowledge: Cell<bool>,
+    staged_config: Cell<RadioConfig>,
+    committed_config: Cell<Option<RadioConfig>>,
+    transmitted_frames: RefCell<Vec<Vec<u8>>>,
+    received_frames: RefCell<VecDeque<(Vec<u8>, Vec<u8>)>>,
+    write_buffer: Cell<RoAllowBuffer>,
+    read_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Ieee802154 {
+    pub fn new() -> std::rc::Rc<Ieee802154> {
+        std::rc::Rc::new(Ieee802154 {
+            on: Cell::new(false),
+            acknowledge: Cell::new(true),
+            staged_config: Cell::new(RadioConfig {
+                channel: 26,
+                ..Default::default()
+            }),
+            committed_config: Cell::new(None),
+            transmitted_frames: Default::default(),
+            received_frames: Default::default(),
+            write_buffer: Default::default(),
+            read_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on.get()
+    }
+
+    /// Returns the configuration most recently committed by the process, if
+    /// any.
+    pub fn committed_config(&self) -> Option<RadioConfig> {
+        self.committed_config.get()
+    }
+
+    /// Sets whether transmitted frames are reported as acknowledged. Defaults
+    /// to `true`.
+    pub fn set_acknowledge(&self, acknowledge: bool) {
+        self.acknowledge.set(acknowledge);
+    }
+
+    /// Returns the frames transmitted so far, and clears them.
+    pub fn take_transmitted_frames(&self) -> Vec<Vec<u8>> {
+        self.transmitted_frames.take()
+    }
+
+    /// Simulates the radio receiving a frame with the given MAC header and
+    /// payload. If the process has not shared a ring buffer, the frame is
+    /// queued until it does.
+    pub fn add_received_frame(&self, header: &[u8], payload: &[u8]) {
+        assert!(
+            header.len() + payload.len() <= MAX_FRAME_LEN,
+            "fake::Ieee802154: frame too long"
+        );
+        self.received_frames
+            .borrow_mut()
+            .push_back((header.to_vec(), payload.to_vec()));
+        self.deliver_received_frames();
+    }
+
+    fn deliver_received_frames(&self) {
+        let mut buffer = self.read_buffer.borrow_mut();
+        if buffer.len() < RING_HEADER_LEN + 2 * SLOT_LEN {
+            return;
+        }
+        let num_slots = (buffer.len() - RING_HEADER_LEN) / SLOT_LEN;
+        while let Some((header, payload)) = self.received_frames.borrow_mut().pop_front() {
+            let write_index = buffer[WRITE_INDEX] as usize % num_slots;
+            let start = RING_HEADER_LEN + write_index * SLOT_LEN;
+            let slot = &mut buffer[start..start + SLOT_LEN];
+            slot[0] = header.len() as u8;
+            slot[1] = payload.len() as u8;
+            slot[2] = 0;
+            let body = &mut slot[SLOT_HEADER_LEN..];
+            body[..header.len()].copy_from_slice(&header);
+            body[header.len()..header.len() + payload.len()].copy_from_slice(&payload);
+
+            // When the ring is full, the oldest frame is dropped.
+            let next_index = ((write_index + 1) % num_slots) as u8;
+            if next_index == buffer[READ_INDEX] {
+                buffer[READ_INDEX] = ((next_index as usize + 1) % num_slots) as u8;
+            }
+            buffer[WRITE_INDEX] = next_index;
+            // The process parses the addresses from the frame itself, so the
+            // upcall arguments are unused.
+            self.share_ref
+                .schedule_upcall(SUBSCRIBE_FRAME_RECEIVED, (0, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+    }
+
+    // Applies `update` to the staged configuration.
+    fn stage<F: FnOnce(&mut RadioConfig)>(&self, update: F) -> CommandReturn {
+        let mut config = self.staged_config.get();
+        update(&mut config);
+        self.staged_config.set(config);
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Ieee802154 {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(2)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num != ALLOW_READ {
+            return Err((buffer, ErrorCode::Invalid));
+        }
+        let previous = self.read_buffer.replace(buffer);
+        self.deliver_received_frames();
+        Ok(previous)
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let config = self.staged_config.get();
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            STATUS => match self.on.get() {
+                true => crate::command_return::success(),
+                false => crate::command_return::failure(ErrorCode::Off),
+            },
+            SET_SHORT_ADDR => self.stage(|config| config.short_address = argument0 as u16),
+            SET_PAN => self.stage(|config| config.pan = argument0 as u16),
+            SET_CHAN => match argument0 {
+                11..=26 => self.stage(|config| config.channel = argument0 as u8),
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            SET_TX_PWR => self.stage(|config| config.tx_power = argument0 as i8),
+            COMMIT_CFG => {
+                self.committed_config.set(Some(config));
+                crate::command_return::success()
+            }
+            GET_SHORT_ADDR => crate::command_return::success_u32(config.short_address as u32),
+            GET_PAN => crate::command_return::success_u32(config.pan as u32),
+            GET_CHAN => crate::command_return::success_u32(config.channel as u32),
+            GET_TX_PWR => crate::command_return::success_u32(config.tx_power as u32),
+            TRANSMIT => {
+                if !self.on.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                let buffer = self.write_buffer.take();
+                self.transmitted_frames.borrow_mut().push(buffer.to_vec());
+                self.write_buffer.set(buffer);
+                self.share_ref
+                    .schedule_upcall(
+                        SUBSCRIBE_FRAME_TRANSMITTED,
+                        (0, self.acknowledge.get() as u32, 0),
+                    )
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            SET_LONG_ADDR => self
+                .stage(|config| config.long_address = (argument1 as u64) << 32 | argument0 as u64),
+            GET_LONG_ADDR => crate::command_return::success_u64(config.long_address),
+            TURN_ON => {
+                self.on.set(true);
+                crate::command_return::success()
+            }
+            TURN_OFF => {
+                self.on.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30001;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const STATUS: u32 = 1;
+const SET_SHORT_ADDR: u32 = 2;
+const SET_PAN: u32 = 4;
+const SET_CHAN: u32 = 5;
+const SET_TX_PWR: u32 = 6;
+const COMMIT_CFG: u32 = 7;
+const GET_SHORT_ADDR: u32 = 8;
+const GET_PAN: u32 = 10;
+const GET_CHAN: u32 = 11;
+const GET_TX_PWR: u32 = 12;
+const TRANSMIT: u32 = 27;
+const SET_LONG_ADDR: u32 = 28;
+const GET_LONG_ADDR: u32 = 29;
+const TURN_ON: u32 = 30;
+const TURN_OFF: u32 = 31;
+
+const SUBSCRIBE_FRAME_RECEIVED: u32 = 0;
+const SUBSCRIBE_FRAME_TRANSMITTED: u32 = 1;
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
+
+// Receive ring buffer layout
+const MAX_FRAME_LEN: usize = 127;
+const READ_INDEX: usize = 0;
+const WRITE_INDEX: usize = 1;
+const RING_HEADER_LEN: usize = 2;
+const SLOT_HEADER_LEN: usize = 3;
+const SLOT_LEN: usize = SLOT_HEADER_LEN + MAX_FRAME_LEN;
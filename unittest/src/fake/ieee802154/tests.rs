@@ -0,0 +1,111 @@
+use crate::fake::{self, SyscallDriver};
+use fake::ieee802154::*;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let radio = Ieee802154::new();
+    assert!(radio.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        radio.command(STATUS, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert_eq!(
+        radio.command(TRANSMIT, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(radio.command(TURN_ON, 0, 0).is_success());
+    assert!(radio.is_on());
+    assert!(radio.command(STATUS, 0, 0).is_success());
+
+    assert!(radio.command(SET_PAN, 0xABCD, 0).is_success());
+    assert!(radio.command(SET_SHORT_ADDR, 0x1234, 0).is_success());
+    assert!(radio
+        .command(SET_LONG_ADDR, 0x89ABCDEF, 0x01234567)
+        .is_success());
+    assert!(radio.command(SET_TX_PWR, -4i32 as u32, 0).is_success());
+    assert_eq!(
+        radio.command(SET_CHAN, 27, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(radio.command(SET_CHAN, 15, 0).is_success());
+    assert_eq!(radio.committed_config(), None);
+    assert_eq!(radio.command(GET_PAN, 0, 0).get_success_u32(), Some(0xABCD));
+    assert_eq!(radio.command(GET_CHAN, 0, 0).get_success_u32(), Some(15));
+    assert_eq!(
+        radio.command(GET_LONG_ADDR, 0, 0).get_success_u64(),
+        Some(0x0123456789ABCDEF)
+    );
+
+    assert!(radio.command(COMMIT_CFG, 0, 0).is_success());
+    assert_eq!(
+        radio.committed_config(),
+        Some(RadioConfig {
+            short_address: 0x1234,
+            long_address: 0x0123456789ABCDEF,
+            pan: 0xABCD,
+            channel: 15,
+            tx_power: -4,
+        })
+    );
+    assert!(radio.command(TURN_OFF, 0, 0).is_success());
+    assert!(!radio.is_on());
+}
+
+// Integration test that verifies Ieee802154 works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let radio = Ieee802154::new();
+    kernel.add_driver(&radio);
+    assert!(fake::Syscalls::command(DRIVER_NUM, TURN_ON, 0, 0).is_success());
+
+    let transmitted = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_FRAME_TRANSMITTED>(
+            subscribe,
+            &transmitted,
+        )
+        .unwrap();
+        radio.set_acknowledge(false);
+        assert!(fake::Syscalls::command(DRIVER_NUM, TRANSMIT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(transmitted.get(), Some((0, 0)));
+    });
+    assert_eq!(radio.take_transmitted_frames(), [Vec::<u8>::new()]);
+
+    // Frames received before the ring buffer is shared are queued.
+    radio.add_received_frame(&[0x41, 0x88, 1], &[0xAA]);
+    radio.add_received_frame(&[0x41, 0x88, 2], &[0xBB, 0xCC]);
+    radio.add_received_frame(&[0x41, 0x88, 3], &[]);
+    let mut buffer = [0; RING_HEADER_LEN + 3 * SLOT_LEN];
+    let received = Cell::new(false);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_FRAME_RECEIVED>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_FRAME_RECEIVED>(
+            subscribe, &received,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(allow_rw, &mut buffer)
+            .unwrap();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert!(received.get());
+    });
+    // Three slots hold two frames, so the first frame was dropped and the next
+    // frame will be written to slot 0.
+    assert_eq!(buffer[..2], [1, 0]);
+    let slot = &buffer[RING_HEADER_LEN + SLOT_LEN..];
+    assert_eq!(slot[..8], [3, 2, 0, 0x41, 0x88, 2, 0xBB, 0xCC]);
+    let slot = &buffer[RING_HEADER_LEN + 2 * SLOT_LEN..];
+    assert_eq!(slot[..6], [3, 0, 0, 0x41, 0x88, 3]);
+}
@@ -0,0 +1,169 @@
+//! Fake implementation of the IPC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/10000_ipc.md
+//!
+//! `Ipc` simulates the other processes on the board. Tests add simulated
+//! services with `add_service`; when the process under test notifies one of
+//! them, the service's responder is called with the buffer the process shared
+//! with it, then the service notifies the process back. Clients of a service
+//! implemented by the process under test are simulated with
+//! `notify_from_client`.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+/// Process IDs range from 1 to `NUM_PROCS`, as 0 is reserved for the service
+/// upcall.
+pub struct Ipc<const NUM_PROCS: usize> {
+    services: RefCell<Vec<Service>>,
+    search_buffer: Cell<RoAllowBuffer>,
+    shared_buffers: Vec<RefCell<RwAllowBuffer>>,
+    client_notifications: Cell<Vec<u32>>,
+    share_ref: DriverShareRef,
+}
+
+struct Service {
+    package_name: Vec<u8>,
+    responder: Box<dyn Fn(&mut [u8])>,
+}
+
+impl<const NUM_PROCS: usize> Ipc<NUM_PROCS> {
+    pub fn new() -> std::rc::Rc<Ipc<NUM_PROCS>> {
+        std::rc::Rc::new(Ipc {
+            services: Default::default(),
+            search_buffer: Default::default(),
+            shared_buffers: (0..=NUM_PROCS).map(|_| Default::default()).collect(),
+            client_notifications: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Adds a simulated service process and returns its ID. `responder` is
+    /// called with the buffer shared with the service each time the process
+    /// under test notifies it.
+    pub fn add_service<F: Fn(&mut [u8]) + 'static>(&self, package_name: &str, responder: F) -> u32 {
+        let mut services = self.services.borrow_mut();
+        assert!(
+            services.len() < NUM_PROCS,
+            "fake::Ipc: no room for another process"
+        );
+        services.push(Service {
+            package_name: package_name.as_bytes().to_vec(),
+            responder: Box::new(responder),
+        });
+        services.len() as u32
+    }
+
+    /// Simulates client `client_id` notifying the service registered by the
+    /// process under test.
+    pub fn notify_from_client(&self, client_id: u32) {
+        self.share_ref
+            .schedule_upcall(SERVICE_UPCALL, (client_id, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+
+    /// Returns the IDs of the clients notified by the process under test so
+    /// far, and clears them.
+    pub fn take_client_notifications(&self) -> Vec<u32> {
+        self.client_notifications.take()
+    }
+
+    fn is_valid_id(id: u32) -> bool {
+        id != 0 && id as usize <= NUM_PROCS
+    }
+}
+
+impl<const NUM_PROCS: usize> crate::fake::SyscallDriver for Ipc<NUM_PROCS> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(NUM_PROCS as u32 + 1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SEARCH {
+            Ok(self.search_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if Self::is_valid_id(buffer_num) {
+            Ok(self.shared_buffers[buffer_num as usize].replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            DISCOVER => {
+                let search = self.search_buffer.take();
+                let position = self
+                    .services
+                    .borrow()
+                    .iter()
+                    .position(|service| service.package_name[..] == search[..]);
+                self.search_buffer.set(search);
+                match position {
+                    Some(index) => crate::command_return::success_u32(index as u32 + 1),
+                    None => crate::command_return::failure(ErrorCode::NoDevice),
+                }
+            }
+            NOTIFY_SERVICE => {
+                let services = self.services.borrow();
+                let service = match services.get((argument0 as usize).wrapping_sub(1)) {
+                    Some(service) => service,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                let mut buffer = self.shared_buffers[argument0 as usize].borrow_mut();
+                (service.responder)(&mut buffer);
+                self.share_ref
+                    .schedule_upcall(argument0, (argument0, buffer.len() as u32, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            NOTIFY_CLIENT => {
+                if !Self::is_valid_id(argument0) {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                let mut notifications = self.client_notifications.take();
+                notifications.push(argument0);
+                self.client_notifications.set(notifications);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const DISCOVER: u32 = 1;
+const NOTIFY_SERVICE: u32 = 2;
+const NOTIFY_CLIENT: u32 = 3;
+
+const SERVICE_UPCALL: u32 = 0;
+const ALLOW_SEARCH: u32 = 0;
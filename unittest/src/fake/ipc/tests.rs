@@ -0,0 +1,96 @@
+use crate::fake::{self, SyscallDriver};
+use crate::RwAllowBuffer;
+use fake::ipc::*;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let ipc = Ipc::<4>::new();
+    assert!(ipc.command(EXISTS, 1, 2).is_success());
+
+    // No buffer has been shared, so no package name matches.
+    assert_eq!(
+        ipc.command(DISCOVER, 0, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+
+    assert_eq!(ipc.add_service("first", |_| {}), 1);
+    assert_eq!(ipc.add_service("second", |_| {}), 2);
+    assert!(ipc.command(NOTIFY_SERVICE, 2, 0).is_success());
+    assert_eq!(
+        ipc.command(NOTIFY_SERVICE, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        ipc.command(NOTIFY_SERVICE, 3, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(ipc.command(NOTIFY_CLIENT, 4, 0).is_success());
+    assert_eq!(
+        ipc.command(NOTIFY_CLIENT, 5, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(ipc.take_client_notifications(), [4]);
+    assert_eq!(ipc.take_client_notifications(), []);
+
+    assert!(ipc.allow_readwrite(4, RwAllowBuffer::default()).is_ok());
+    assert!(ipc.allow_readwrite(0, RwAllowBuffer::default()).is_err());
+    assert!(ipc.allow_readwrite(5, RwAllowBuffer::default()).is_err());
+}
+
+#[test]
+#[should_panic = "no room for another process"]
+fn too_many_services() {
+    let ipc = Ipc::<1>::new();
+    ipc.add_service("first", |_| {});
+    ipc.add_service("second", |_| {});
+}
+
+// Integration test that verifies Ipc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let ipc = Ipc::<4>::new();
+    kernel.add_driver(&ipc);
+    let service_id = ipc.add_service("org.tockos.service", |buffer| buffer.fill(0xAA));
+
+    share::scope(|allow_ro| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SEARCH>(
+            allow_ro,
+            b"org.tockos.service",
+        )
+        .unwrap();
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, DISCOVER, 0, 0).get_success_u32(),
+            Some(service_id)
+        );
+    });
+
+    let mut buffer = [0; 3];
+    let listener = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope::<(AllowRw<_, DRIVER_NUM, 1>, Subscribe<_, DRIVER_NUM, 1>), _, _>(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, 1>(allow_rw, &mut buffer).unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 1>(subscribe, &listener)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, NOTIFY_SERVICE, service_id, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((service_id, 3)));
+    });
+    assert_eq!(buffer, [0xAA; 3]);
+
+    let listener = Cell::<Option<(u32,)>>::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SERVICE_UPCALL>(
+            subscribe, &listener,
+        )
+        .unwrap();
+        ipc.notify_from_client(3);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((3,)));
+    });
+}
@@ -10,38 +10,80 @@
 //! (e.g. `fake::Console`).
 
 mod adc;
+mod aes;
+mod air_quality;
 mod alarm;
 mod ambient_light;
+mod analog_comparator;
+mod ble;
 mod buttons;
 mod buzzer;
 mod console;
+mod crc;
+mod ctap;
+mod dac;
 mod gpio;
+mod hmac;
+mod humidity;
+mod ieee802154;
+mod ipc;
 mod kernel;
 mod leds;
 mod low_level_debug;
 mod ninedof;
+mod pressure;
 mod proximity;
+mod pwm;
+mod read_only_state;
+mod rtc;
+mod screen;
+mod servo;
+mod seven_segment;
+mod sha;
 mod sound_pressure;
 mod syscall_driver;
 mod syscalls;
 mod temperature;
+mod text_screen;
+mod usb_keyboard;
 
+pub use self::aes::Aes;
+pub use self::crc::Crc;
+pub use self::hmac::Hmac;
 pub use adc::Adc;
+pub use air_quality::AirQuality;
 pub use alarm::Alarm;
 pub use ambient_light::AmbientLight;
+pub use analog_comparator::AnalogComparator;
+pub use ble::{Advertisement, Ble};
 pub use buttons::Buttons;
 pub use buzzer::Buzzer;
 pub use console::Console;
+pub use ctap::Ctap;
+pub use dac::Dac;
 pub use gpio::{Gpio, GpioMode, InterruptEdge, PullMode};
+pub use humidity::Humidity;
+pub use ieee802154::{Ieee802154, RadioConfig};
+pub use ipc::Ipc;
 pub use kernel::Kernel;
 pub use leds::Leds;
 pub use low_level_debug::{LowLevelDebug, Message};
 pub use ninedof::{NineDof, NineDofData};
+pub use pressure::Pressure;
 pub use proximity::Proximity;
+pub use pwm::{Pwm, PwmSignal};
+pub use read_only_state::ReadOnlyState;
+pub use rtc::Rtc;
+pub use screen::Screen;
+pub use servo::Servo;
+pub use seven_segment::SevenSegment;
+pub use sha::Sha;
 pub use sound_pressure::SoundPressure;
 pub use syscall_driver::SyscallDriver;
 pub use syscalls::Syscalls;
 pub use temperature::Temperature;
+pub use text_screen::TextScreen;
+pub use usb_keyboard::UsbKeyboard;
 
 #[cfg(test)]
 mod kernel_tests;
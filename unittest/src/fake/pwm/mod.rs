@@ -0,0 +1,106 @@
+//! Fake implementation of the PWM API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/10000_pwm.md
+//!
+//! Like the real API, `Pwm` controls a set of fake PWM pins. It provides a
+//! function `output` used to retrieve the signal a pin is currently producing.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+/// The signal produced by an active PWM pin.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PwmSignal {
+    pub frequency_hz: u32,
+    /// The duty cycle in hundredths of a percent.
+    pub duty_cycle: u16,
+}
+
+pub struct Pwm<const PINS_COUNT: usize> {
+    max_frequency_hz: Cell<u32>,
+    outputs: [Cell<Option<PwmSignal>>; PINS_COUNT],
+}
+
+impl<const PINS_COUNT: usize> Pwm<PINS_COUNT> {
+    pub fn new() -> std::rc::Rc<Pwm<PINS_COUNT>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const STOPPED: Cell<Option<PwmSignal>> = Cell::new(None);
+        std::rc::Rc::new(Pwm {
+            max_frequency_hz: Cell::new(DEFAULT_MAX_FREQUENCY_HZ),
+            outputs: [STOPPED; PINS_COUNT],
+        })
+    }
+
+    /// Sets the maximum frequency of every pin.
+    pub fn set_max_frequency(&self, frequency_hz: u32) {
+        self.max_frequency_hz.set(frequency_hz);
+    }
+
+    /// Returns the signal `pin` is producing, or `None` if it is stopped.
+    pub fn output(&self, pin: u32) -> Option<PwmSignal> {
+        self.outputs.get(pin as usize).and_then(Cell::get)
+    }
+}
+
+impl<const PINS_COUNT: usize> crate::fake::SyscallDriver for Pwm<PINS_COUNT> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            START => {
+                let pin = argument0 & 0xffff;
+                let duty_cycle = (argument0 >> 16) as u16;
+                let output = match self.outputs.get(pin as usize) {
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                    Some(output) => output,
+                };
+                if duty_cycle > MAX_DUTY_CYCLE || argument1 > self.max_frequency_hz.get() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                output.set(Some(PwmSignal {
+                    frequency_hz: argument1,
+                    duty_cycle,
+                }));
+                crate::command_return::success()
+            }
+            STOP => match self.outputs.get(argument0 as usize) {
+                None => crate::command_return::failure(ErrorCode::Invalid),
+                Some(output) => {
+                    output.set(None);
+                    crate::command_return::success()
+                }
+            },
+            GET_MAX_FREQUENCY => {
+                if argument0 < PINS_COUNT as u32 {
+                    crate::command_return::success_u32(self.max_frequency_hz.get())
+                } else {
+                    crate::command_return::failure(ErrorCode::Invalid)
+                }
+            }
+            COUNT => crate::command_return::success_u32(PINS_COUNT as u32),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const START: u32 = 1;
+const STOP: u32 = 2;
+const GET_MAX_FREQUENCY: u32 = 3;
+const COUNT: u32 = 4;
+
+const DEFAULT_MAX_FREQUENCY_HZ: u32 = 1_000_000;
+const MAX_DUTY_CYCLE: u16 = 10000;
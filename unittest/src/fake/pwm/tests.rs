@@ -0,0 +1,75 @@
+use crate::fake;
+use fake::pwm::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let pwm = Pwm::<2>::new();
+    assert!(pwm.command(EXISTS, 0, 0).is_success());
+    assert_eq!(pwm.command(COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(
+        pwm.command(GET_MAX_FREQUENCY, 1, 0).get_success_u32(),
+        Some(DEFAULT_MAX_FREQUENCY_HZ)
+    );
+    assert_eq!(
+        pwm.command(GET_MAX_FREQUENCY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(pwm.command(START, 2500 << 16 | 1, 1000).is_success());
+    assert_eq!(pwm.output(0), None);
+    assert_eq!(
+        pwm.output(1),
+        Some(PwmSignal {
+            frequency_hz: 1000,
+            duty_cycle: 2500
+        })
+    );
+    assert_eq!(
+        pwm.command(START, 10001 << 16, 1000).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        pwm.command(START, 2, 1000).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    pwm.set_max_frequency(500);
+    assert_eq!(
+        pwm.command(START, 0, 1000).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(pwm.command(STOP, 1, 0).is_success());
+    assert_eq!(pwm.output(1), None);
+    assert_eq!(
+        pwm.command(STOP, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies Pwm works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let pwm = Pwm::<2>::new();
+    kernel.add_driver(&pwm);
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, COUNT, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, START, 10000 << 16, 50).is_success());
+    assert_eq!(
+        pwm.output(0),
+        Some(PwmSignal {
+            frequency_hz: 50,
+            duty_cycle: 10000
+        })
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, STOP, 0, 0).is_success());
+    assert_eq!(pwm.output(0), None);
+}
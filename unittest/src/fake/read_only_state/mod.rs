@@ -0,0 +1,101 @@
+//! Fake implementation of the read-only state API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00009_read_only_state.md
+//!
+//! Like the real API, `ReadOnlyState` writes the process state into the
+//! buffer shared through Userspace Readable Allow. The kernel's values are set
+//! by tests with `set_pending_tasks` and `set_time_ticks`, and are written into
+//! the buffer immediately.
+
+use crate::{DriverInfo, UrAllowBuffer};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::{Cell, RefCell};
+
+pub struct ReadOnlyState {
+    pending_tasks: Cell<u32>,
+    time_ticks: Cell<u64>,
+    buffer: RefCell<UrAllowBuffer>,
+}
+
+impl ReadOnlyState {
+    pub fn new() -> std::rc::Rc<ReadOnlyState> {
+        std::rc::Rc::new(ReadOnlyState {
+            pending_tasks: Cell::new(0),
+            time_ticks: Cell::new(0),
+            buffer: Default::default(),
+        })
+    }
+
+    pub fn set_pending_tasks(&self, pending_tasks: u32) {
+        self.pending_tasks.set(pending_tasks);
+        self.update();
+    }
+
+    pub fn set_time_ticks(&self, time_ticks: u64) {
+        self.time_ticks.set(time_ticks);
+        self.update();
+    }
+
+    // Writes the state into the shared buffer, if there is one.
+    fn update(&self) {
+        let buffer = self.buffer.borrow();
+        if buffer.len() < BUFFER_LEN {
+            return;
+        }
+        let fields = VERSION
+            .to_ne_bytes()
+            .into_iter()
+            .chain(self.pending_tasks.get().to_ne_bytes())
+            .chain(self.time_ticks.get().to_ne_bytes());
+        for (cell, byte) in buffer.iter().zip(fields) {
+            cell.set(byte);
+        }
+    }
+}
+
+impl crate::fake::SyscallDriver for ReadOnlyState {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            GET_VERSION => crate::command_return::success_u32(VERSION),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+
+    fn allow_userspace_readable(
+        &self,
+        buffer_num: u32,
+        buffer: UrAllowBuffer,
+    ) -> Result<UrAllowBuffer, (UrAllowBuffer, ErrorCode)> {
+        if buffer_num != ALLOW_STATE {
+            return Err((buffer, ErrorCode::Invalid));
+        }
+        if !buffer.is_empty() && buffer.len() < BUFFER_LEN {
+            return Err((buffer, ErrorCode::Size));
+        }
+        let old = self.buffer.replace(buffer);
+        self.update();
+        Ok(old)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x9;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const GET_VERSION: u32 = 1;
+
+const ALLOW_STATE: u32 = 0;
+
+const VERSION: u32 = 1;
+const BUFFER_LEN: usize = 16;
@@ -0,0 +1,44 @@
+use crate::fake::{self, SyscallDriver};
+use fake::read_only_state::*;
+use libtock_platform::DefaultConfig;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let read_only_state = ReadOnlyState::new();
+    assert!(read_only_state.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        read_only_state.command(GET_VERSION, 0, 0).get_success_u32(),
+        Some(VERSION)
+    );
+}
+
+// Integration test that verifies ReadOnlyState works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let read_only_state = ReadOnlyState::new();
+    kernel.add_driver(&read_only_state);
+
+    let short: &'static [Cell<u8>] = Box::leak(Box::new([0u8; 8].map(Cell::new)));
+    assert_eq!(
+        fake::Syscalls::allow_userspace_readable::<DefaultConfig, DRIVER_NUM, ALLOW_STATE>(short),
+        Err(ErrorCode::Size)
+    );
+
+    read_only_state.set_pending_tasks(2);
+    let buffer: &'static [Cell<u8>] = Box::leak(Box::new([0u8; 16].map(Cell::new)));
+    assert_eq!(
+        fake::Syscalls::allow_userspace_readable::<DefaultConfig, DRIVER_NUM, ALLOW_STATE>(buffer),
+        Ok(())
+    );
+    let bytes = || buffer.iter().map(Cell::get).collect::<Vec<u8>>();
+    assert_eq!(bytes()[..4], VERSION.to_ne_bytes());
+    assert_eq!(bytes()[4..8], 2u32.to_ne_bytes());
+    assert_eq!(bytes()[8..], 0u64.to_ne_bytes());
+
+    read_only_state.set_time_ticks(0x1_0000_0005);
+    assert_eq!(bytes()[8..], 0x1_0000_0005u64.to_ne_bytes());
+}
@@ -0,0 +1,141 @@
+//! Fake implementation of the date-time (real-time clock) API, documented
+//! here: https://github.com/tock/tock/blob/master/doc/syscalls/90007_date_time.md
+//!
+//! Like the real API, `Rtc` keeps the wall-clock time. The time is stored as a
+//! Unix timestamp, and does not pass unless the test calls `advance`. Reading
+//! or setting the time immediately schedules the corresponding upcall.
+
+use crate::{DriverInfo, DriverShareRef};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::Cell;
+
+pub struct Rtc {
+    unix_timestamp: Cell<u64>,
+    share_ref: DriverShareRef,
+}
+
+impl Rtc {
+    pub fn new(unix_timestamp: u64) -> std::rc::Rc<Rtc> {
+        std::rc::Rc::new(Rtc {
+            unix_timestamp: Cell::new(unix_timestamp),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn unix_timestamp(&self) -> u64 {
+        self.unix_timestamp.get()
+    }
+
+    /// Moves the clock forward by `seconds`.
+    pub fn advance(&self, seconds: u64) {
+        self.unix_timestamp.set(self.unix_timestamp.get() + seconds);
+    }
+}
+
+impl crate::fake::SyscallDriver for Rtc {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            GET_DATE_TIME => {
+                let (date, time) = to_registers(self.unix_timestamp.get());
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (0, date, time))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            SET_DATE_TIME => match from_registers(argument0, argument1) {
+                None => crate::command_return::failure(ErrorCode::Invalid),
+                Some(unix_timestamp) => {
+                    self.unix_timestamp.set(unix_timestamp);
+                    self.share_ref
+                        .schedule_upcall(SUBSCRIBE_DONE, (0, 0, 0))
+                        .expect("Unable to schedule upcall");
+                    crate::command_return::success()
+                }
+            },
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Encodes a Unix timestamp into the kernel's date and time registers. The
+// date is year << 9 | month << 5 | day and the time is
+// weekday << 17 | hour << 12 | minute << 6 | second, with Sunday as weekday 0.
+fn to_registers(unix_timestamp: u64) -> (u32, u32) {
+    let days = unix_timestamp / SECONDS_PER_DAY;
+    let seconds = unix_timestamp % SECONDS_PER_DAY;
+    // Converts days since the epoch to a date, using years that start on
+    // March 1st, 0000.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = (shifted_month + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+    // 1970-01-01 was a Thursday.
+    let weekday = (unix_timestamp / SECONDS_PER_DAY + 4) % 7;
+    let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    let date = (year << 9 | month << 5 | day) as u32;
+    let time = (weekday << 17 | hour << 12 | minute << 6 | second) as u32;
+    (date, time)
+}
+
+// Decodes the kernel's date and time registers into a Unix timestamp. Returns
+// `None` if they do not hold a valid date and time from 1970 onwards.
+fn from_registers(date: u32, time: u32) -> Option<u64> {
+    let (year, month, day) = (date as u64 >> 9, date as u64 >> 5 & 0xf, date as u64 & 0x1f);
+    let (hour, minute, second) = (
+        time as u64 >> 12 & 0x1f,
+        time as u64 >> 6 & 0x3f,
+        time as u64 & 0x3f,
+    );
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_days = match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if year < 1970 || day == 0 || day > month_days || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year / 400;
+    let year_of_era = shifted_year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second)
+}
+
+const DRIVER_NUM: u32 = 0x90007;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const GET_DATE_TIME: u32 = 1;
+const SET_DATE_TIME: u32 = 2;
+
+const SUBSCRIBE_DONE: u32 = 0;
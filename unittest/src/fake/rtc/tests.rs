@@ -0,0 +1,76 @@
+use crate::fake::{self, SyscallDriver};
+use fake::rtc::*;
+use libtock_platform::{share, DefaultConfig, YieldNoWaitReturn};
+
+// 2024-03-14 15:09:26, a Thursday.
+const TIMESTAMP: u64 = 1_710_428_966;
+const DATE: u32 = 2024 << 9 | 3 << 5 | 14;
+const TIME: u32 = 4 << 17 | 15 << 12 | 9 << 6 | 26;
+
+#[test]
+fn registers() {
+    assert_eq!(to_registers(TIMESTAMP), (DATE, TIME));
+    assert_eq!(from_registers(DATE, TIME), Some(TIMESTAMP));
+    assert_eq!(to_registers(0), (1970 << 9 | 1 << 5 | 1, 4 << 17));
+    // 2000-02-29 was a Tuesday.
+    assert_eq!(
+        to_registers(951_782_400),
+        (2000 << 9 | 2 << 5 | 29, 2 << 17)
+    );
+    assert_eq!(
+        from_registers(2000 << 9 | 2 << 5 | 29, 0),
+        Some(951_782_400)
+    );
+    assert_eq!(from_registers(1900 << 9 | 2 << 5 | 29, 0), None);
+    assert_eq!(from_registers(2023 << 9 | 2 << 5 | 29, 0), None);
+    assert_eq!(from_registers(2023 << 9 | 13 << 5 | 1, 0), None);
+    assert_eq!(from_registers(2023 << 9 | 1 << 5 | 1, 24 << 12), None);
+}
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let rtc = Rtc::new(TIMESTAMP);
+    assert!(rtc.command(EXISTS, 0, 0).is_success());
+    assert!(rtc.command(GET_DATE_TIME, 0, 0).is_success());
+    assert_eq!(
+        rtc.command(SET_DATE_TIME, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(rtc.unix_timestamp(), TIMESTAMP);
+    assert!(rtc
+        .command(SET_DATE_TIME, 1970 << 9 | 1 << 5 | 2, 0)
+        .is_success());
+    assert_eq!(rtc.unix_timestamp(), SECONDS_PER_DAY);
+    rtc.advance(61);
+    assert_eq!(rtc.unix_timestamp(), SECONDS_PER_DAY + 61);
+}
+
+// Integration test that verifies Rtc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let rtc = Rtc::new(TIMESTAMP);
+    kernel.add_driver(&rtc);
+
+    let listener = Cell::<Option<(u32, u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+                subscribe, &listener
+            ),
+            Ok(())
+        );
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_DATE_TIME, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((0, DATE, TIME)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_DATE_TIME, DATE + 1, TIME).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(listener.get(), Some((0, 0, 0)));
+        assert_eq!(rtc.unix_timestamp(), TIMESTAMP + SECONDS_PER_DAY);
+    });
+}
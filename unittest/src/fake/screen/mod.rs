@@ -0,0 +1,316 @@
+//! Fake implementation of the screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90001_screen.md
+//!
+//! `Screen` stores the pixels written by the process in a framebuffer, which
+//! tests can compare against an expected image with `snapshot`. Changing the
+//! resolution, pixel format or rotation clears the framebuffer. Pixel formats
+//! smaller than a byte (mono) are not supported.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+
+pub struct Screen {
+    resolutions: Vec<(u32, u32)>,
+    resolution: Cell<(u32, u32)>,
+    pixel_format: Cell<u32>,
+    rotation: Cell<u32>,
+    on: Cell<bool>,
+    brightness: Cell<u16>,
+    inverted: Cell<bool>,
+    // The write frame, as (x, y, width, height).
+    frame: Cell<(u32, u32, u32, u32)>,
+    framebuffer: RefCell<Vec<u8>>,
+    buffer: Cell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Screen {
+    /// Creates a screen with the given resolution, which uses the RGB565
+    /// pixel format.
+    pub fn new(width: u32, height: u32) -> std::rc::Rc<Screen> {
+        let screen = std::rc::Rc::new(Screen {
+            resolutions: vec![(width, height)],
+            resolution: Cell::new((width, height)),
+            pixel_format: Cell::new(PIXEL_FORMAT_RGB565),
+            rotation: Cell::new(0),
+            on: Cell::new(false),
+            brightness: Cell::new(0),
+            inverted: Cell::new(false),
+            frame: Cell::new((0, 0, 0, 0)),
+            framebuffer: Default::default(),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        });
+        screen.clear();
+        screen
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on.get()
+    }
+
+    pub fn brightness(&self) -> u16 {
+        self.brightness.get()
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.inverted.get()
+    }
+
+    /// Returns the current `(width, height)`, which accounts for the rotation.
+    pub fn resolution(&self) -> (u32, u32) {
+        let (width, height) = self.resolution.get();
+        match self.rotation.get() {
+            ROTATED_90 | ROTATED_270 => (height, width),
+            _ => (width, height),
+        }
+    }
+
+    /// Returns a copy of the framebuffer, row by row in the current pixel
+    /// format.
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.framebuffer.borrow().clone()
+    }
+
+    /// Returns the bytes of the pixel at (`x`, `y`).
+    pub fn pixel(&self, x: u32, y: u32) -> Vec<u8> {
+        let bytes_per_pixel = self.bytes_per_pixel();
+        let start = (y * self.resolution().0 + x) as usize * bytes_per_pixel;
+        self.framebuffer.borrow()[start..start + bytes_per_pixel].to_vec()
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        match self.pixel_format.get() {
+            PIXEL_FORMAT_RGB233 => 1,
+            PIXEL_FORMAT_RGB565 => 2,
+            PIXEL_FORMAT_RGB888 => 3,
+            _ => 4,
+        }
+    }
+
+    fn clear(&self) {
+        let (width, height) = self.resolution();
+        let len = (width * height) as usize * self.bytes_per_pixel();
+        self.framebuffer.replace(vec![0; len]);
+    }
+
+    // Calls `write` with each pixel of the write frame, row by row, until it
+    // returns false.
+    fn for_each_frame_pixel<F: FnMut(&mut [u8]) -> bool>(&self, mut write: F) {
+        let (x, y, width, height) = self.frame.get();
+        let screen_width = self.resolution().0;
+        let bytes_per_pixel = self.bytes_per_pixel();
+        let mut framebuffer = self.framebuffer.borrow_mut();
+        for row in y..y + height {
+            for column in x..x + width {
+                let start = (row * screen_width + column) as usize * bytes_per_pixel;
+                if !write(&mut framebuffer[start..start + bytes_per_pixel]) {
+                    return;
+                }
+            }
+        }
+    }
+
+    // Completes an asynchronous command with an upcall.
+    fn complete(&self) -> CommandReturn {
+        self.complete_with(0, 0)
+    }
+
+    // Completes an asynchronous command with an upcall that passes two values,
+    // as the getters do.
+    fn complete_with(&self, value0: u32, value1: u32) -> CommandReturn {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (0, value0, value1))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Screen {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_BUFFER {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SET_POWER => {
+                self.on.set(argument0 != 0);
+                self.complete()
+            }
+            SET_BRIGHTNESS => {
+                self.brightness.set(argument0 as u16);
+                self.complete()
+            }
+            INVERT_ON | INVERT_OFF => {
+                self.inverted.set(command_id == INVERT_ON);
+                self.complete()
+            }
+            SUPPORTED_RESOLUTIONS => {
+                crate::command_return::success_u32(self.resolutions.len() as u32)
+            }
+            SUPPORTED_RESOLUTION => match self.resolutions.get(argument0 as usize) {
+                Some(&(width, height)) => crate::command_return::success_2_u32(width, height),
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            SUPPORTED_PIXEL_FORMATS => {
+                crate::command_return::success_u32(SUPPORTED_PIXEL_FORMATS_LIST.len() as u32)
+            }
+            SUPPORTED_PIXEL_FORMAT => match SUPPORTED_PIXEL_FORMATS_LIST.get(argument0 as usize) {
+                Some(&format) => crate::command_return::success_u32(format),
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            GET_ROTATION => self.complete_with(self.rotation.get(), 0),
+            SET_ROTATION => {
+                if argument0 > ROTATED_270 {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.rotation.set(argument0);
+                self.clear();
+                self.complete()
+            }
+            GET_RESOLUTION => {
+                let (width, height) = self.resolution();
+                self.complete_with(width, height)
+            }
+            SET_RESOLUTION => {
+                if !self.resolutions.contains(&(argument0, argument1)) {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.resolution.set((argument0, argument1));
+                self.clear();
+                self.complete()
+            }
+            GET_PIXEL_FORMAT => self.complete_with(self.pixel_format.get(), 0),
+            SET_PIXEL_FORMAT => {
+                if !SUPPORTED_PIXEL_FORMATS_LIST.contains(&argument0) {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.pixel_format.set(argument0);
+                self.clear();
+                self.complete()
+            }
+            SET_WRITE_FRAME => {
+                let frame = (
+                    argument0 >> 16,
+                    argument0 & 0xFFFF,
+                    argument1 >> 16,
+                    argument1 & 0xFFFF,
+                );
+                let (width, height) = self.resolution();
+                if frame.0 + frame.2 > width || frame.1 + frame.3 > height {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.frame.set(frame);
+                self.complete()
+            }
+            WRITE => {
+                let buffer = self.buffer.take();
+                let result = match buffer.get(..argument0 as usize) {
+                    Some(data) => {
+                        let mut pixels = data.chunks_exact(self.bytes_per_pixel());
+                        self.for_each_frame_pixel(|pixel| match pixels.next() {
+                            Some(data) => {
+                                pixel.copy_from_slice(data);
+                                true
+                            }
+                            None => false,
+                        });
+                        Ok(())
+                    }
+                    None => Err(ErrorCode::Invalid),
+                };
+                self.buffer.set(buffer);
+                match result {
+                    Ok(()) => self.complete(),
+                    Err(error) => crate::command_return::failure(error),
+                }
+            }
+            FILL => {
+                let buffer = self.buffer.take();
+                let result = match buffer.get(..self.bytes_per_pixel()) {
+                    Some(color) => {
+                        self.for_each_frame_pixel(|pixel| {
+                            pixel.copy_from_slice(color);
+                            true
+                        });
+                        Ok(())
+                    }
+                    None => Err(ErrorCode::Invalid),
+                };
+                self.buffer.set(buffer);
+                match result {
+                    Ok(()) => self.complete(),
+                    Err(error) => crate::command_return::failure(error),
+                }
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SET_POWER: u32 = 2;
+const SET_BRIGHTNESS: u32 = 3;
+const INVERT_ON: u32 = 4;
+const INVERT_OFF: u32 = 5;
+const SUPPORTED_RESOLUTIONS: u32 = 11;
+const SUPPORTED_RESOLUTION: u32 = 12;
+const SUPPORTED_PIXEL_FORMATS: u32 = 13;
+const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+const GET_ROTATION: u32 = 21;
+const SET_ROTATION: u32 = 22;
+const GET_RESOLUTION: u32 = 23;
+const SET_RESOLUTION: u32 = 24;
+const GET_PIXEL_FORMAT: u32 = 25;
+const SET_PIXEL_FORMAT: u32 = 26;
+const SET_WRITE_FRAME: u32 = 100;
+const WRITE: u32 = 200;
+const FILL: u32 = 300;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_BUFFER: u32 = 0;
+
+// Pixel formats
+const PIXEL_FORMAT_RGB233: u32 = 1;
+const PIXEL_FORMAT_RGB565: u32 = 2;
+const PIXEL_FORMAT_RGB888: u32 = 3;
+const PIXEL_FORMAT_ARGB8888: u32 = 4;
+const SUPPORTED_PIXEL_FORMATS_LIST: [u32; 4] = [
+    PIXEL_FORMAT_RGB233,
+    PIXEL_FORMAT_RGB565,
+    PIXEL_FORMAT_RGB888,
+    PIXEL_FORMAT_ARGB8888,
+];
+
+// Rotations
+const ROTATED_90: u32 = 1;
+const ROTATED_270: u32 = 3;
@@ -0,0 +1,116 @@
+use crate::fake::{self, SyscallDriver};
+use fake::screen::*;
+use libtock_platform::{share, AllowRo, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let screen = Screen::new(4, 2);
+    assert!(screen.command(EXISTS, 0, 0).is_success());
+
+    assert!(screen.command(SET_POWER, 1, 0).is_success());
+    assert!(screen.is_on());
+    assert!(screen.command(SET_BRIGHTNESS, 300, 0).is_success());
+    assert_eq!(screen.brightness(), 300);
+    assert!(screen.command(INVERT_ON, 0, 0).is_success());
+    assert!(screen.is_inverted());
+    assert!(screen.command(INVERT_OFF, 0, 0).is_success());
+    assert!(!screen.is_inverted());
+
+    assert_eq!(
+        screen
+            .command(SUPPORTED_RESOLUTION, 0, 0)
+            .get_success_2_u32(),
+        Some((4, 2))
+    );
+    assert_eq!(
+        screen.command(SET_RESOLUTION, 8, 8).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(screen.command(SET_ROTATION, 1, 0).is_success());
+    assert_eq!(screen.resolution(), (2, 4));
+    assert_eq!(screen.snapshot().len(), 16);
+
+    assert_eq!(
+        screen.command(SET_PIXEL_FORMAT, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(screen.command(SET_PIXEL_FORMAT, 3, 0).is_success());
+    assert_eq!(screen.snapshot().len(), 24);
+
+    assert_eq!(
+        screen
+            .command(SET_WRITE_FRAME, 1 << 16, 2 << 16 | 1)
+            .get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(screen
+        .command(SET_WRITE_FRAME, 1 << 16 | 3, 1 << 16 | 1)
+        .is_success());
+    // No buffer is shared, so there is no pixel to fill with.
+    assert_eq!(
+        screen.command(FILL, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies Screen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let screen = Screen::new(3, 2);
+    kernel.add_driver(&screen);
+
+    let done = Cell::<Option<(u32, u32, u32)>>::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_BUFFER>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_BUFFER>(
+            allow_ro,
+            &[1, 2, 3, 4, 5, 6],
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        // Write a column of two pixels, ignoring the extra data.
+        let frame = fake::Syscalls::command(DRIVER_NUM, SET_WRITE_FRAME, 1 << 16, 1 << 16 | 2);
+        assert!(frame.is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0, 0)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 6, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, WRITE, 7, 0).get_failure(),
+            Some(ErrorCode::Invalid)
+        );
+
+        let frame = fake::Syscalls::command(DRIVER_NUM, SET_WRITE_FRAME, 2 << 16, 1 << 16 | 2);
+        assert!(frame.is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, FILL, 0, 0).is_success());
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+
+        // The getters return their values through the upcall.
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_RESOLUTION, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 3, 2)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_PIXEL_FORMAT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 2, 0)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_ROTATION, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0, 0)));
+    });
+    assert_eq!(screen.snapshot(), [0, 0, 1, 2, 1, 2, 0, 0, 3, 4, 1, 2]);
+    assert_eq!(screen.pixel(1, 1), [3, 4]);
+}
@@ -0,0 +1,74 @@
+//! Fake implementation of the Servo API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90009_servo.md
+//!
+//! Like the real API, `Servo` controls a set of fake servos. It provides a
+//! function `angle` used to retrieve the angle a servo was last moved to.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct Servo<const SERVOS_COUNT: usize> {
+    angles: [Cell<Option<u32>>; SERVOS_COUNT],
+}
+
+impl<const SERVOS_COUNT: usize> Servo<SERVOS_COUNT> {
+    pub fn new() -> std::rc::Rc<Servo<SERVOS_COUNT>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const UNKNOWN: Cell<Option<u32>> = Cell::new(None);
+        std::rc::Rc::new(Servo {
+            angles: [UNKNOWN; SERVOS_COUNT],
+        })
+    }
+
+    /// Returns the angle `servo` was last moved to, or `None` if it was never
+    /// moved.
+    pub fn angle(&self, servo: u32) -> Option<u32> {
+        self.angles.get(servo as usize).and_then(Cell::get)
+    }
+}
+
+impl<const SERVOS_COUNT: usize> crate::fake::SyscallDriver for Servo<SERVOS_COUNT> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SERVO_COUNT => crate::command_return::success_u32(SERVOS_COUNT as u32),
+            SET_ANGLE => match self.angles.get(argument0 as usize) {
+                None => crate::command_return::failure(ErrorCode::NoDevice),
+                Some(_) if argument1 > MAX_ANGLE => {
+                    crate::command_return::failure(ErrorCode::Invalid)
+                }
+                Some(angle) => {
+                    angle.set(Some(argument1));
+                    crate::command_return::success()
+                }
+            },
+            GET_ANGLE => match self.angle(argument0) {
+                None => crate::command_return::failure(ErrorCode::NoDevice),
+                Some(angle) => crate::command_return::success_u32(angle),
+            },
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90009;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SERVO_COUNT: u32 = 1;
+const SET_ANGLE: u32 = 2;
+const GET_ANGLE: u32 = 3;
+
+const MAX_ANGLE: u32 = 180;
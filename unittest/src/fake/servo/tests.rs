@@ -0,0 +1,50 @@
+use crate::fake;
+use fake::servo::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let servo = Servo::<3>::new();
+    assert!(servo.command(EXISTS, 0, 0).is_success());
+    assert_eq!(servo.command(SERVO_COUNT, 0, 0).get_success_u32(), Some(3));
+
+    assert_eq!(
+        servo.command(GET_ANGLE, 2, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+    assert!(servo.command(SET_ANGLE, 2, 120).is_success());
+    assert_eq!(servo.angle(2), Some(120));
+    assert_eq!(servo.command(GET_ANGLE, 2, 0).get_success_u32(), Some(120));
+
+    assert_eq!(
+        servo.command(SET_ANGLE, 2, 181).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        servo.command(SET_ANGLE, 3, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+    assert_eq!(servo.angle(2), Some(120));
+}
+
+// Integration test that verifies Servo works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let servo = Servo::<3>::new();
+    kernel.add_driver(&servo);
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, SERVO_COUNT, 0, 0).get_success_u32(),
+        Some(3)
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_ANGLE, 0, 30).is_success());
+    assert_eq!(servo.angle(0), Some(30));
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, GET_ANGLE, 0, 0).get_success_u32(),
+        Some(30)
+    );
+}
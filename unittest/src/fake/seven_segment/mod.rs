@@ -0,0 +1,120 @@
+//! Fake implementation of the seven-segment display API.
+//!
+//! Like the real API, `SevenSegment` controls a row of fake digits. It provides
+//! a function `get_segments` used to retrieve the segments lit on a digit, and
+//! `text` which decodes the whole display into a string.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct SevenSegment<const DIGITS_COUNT: usize> {
+    digits: [Cell<u8>; DIGITS_COUNT],
+}
+
+impl<const DIGITS_COUNT: usize> SevenSegment<DIGITS_COUNT> {
+    pub fn new() -> std::rc::Rc<SevenSegment<DIGITS_COUNT>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const BLANK: Cell<u8> = Cell::new(0);
+        std::rc::Rc::new(SevenSegment {
+            digits: [BLANK; DIGITS_COUNT],
+        })
+    }
+
+    /// Returns the segments lit on `digit`, with segments A to G in bits 0 to
+    /// 6 and the decimal point in bit 7.
+    pub fn get_segments(&self, digit: u32) -> Option<u8> {
+        self.digits.get(digit as usize).map(|digit| digit.get())
+    }
+
+    /// Decodes the display, from left to right. Blank digits are shown as
+    /// spaces, digits showing only segment G as `-`, and a lit decimal point
+    /// as a `.` after its digit. Patterns that are not a hexadecimal digit are
+    /// shown as `?`.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for digit in &self.digits {
+            let segments = digit.get();
+            text.push(match segments & !DECIMAL_POINT {
+                0 => ' ',
+                MINUS => '-',
+                pattern => HEX_DIGITS
+                    .iter()
+                    .position(|&digit| digit == pattern)
+                    .and_then(|value| char::from_digit(value as u32, 16))
+                    .unwrap_or('?'),
+            });
+            if segments & DECIMAL_POINT != 0 {
+                text.push('.');
+            }
+        }
+        text
+    }
+}
+
+impl<const DIGITS_COUNT: usize> crate::fake::SyscallDriver for SevenSegment<DIGITS_COUNT> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            DIGITS_COUNT_COMMAND => crate::command_return::success_u32(DIGITS_COUNT as u32),
+            PRINT_DIGIT => match (
+                self.digits.get(argument0 as usize),
+                HEX_DIGITS.get(argument1 as usize),
+            ) {
+                (Some(digit), Some(&segments)) => {
+                    digit.set(segments);
+                    crate::command_return::success()
+                }
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            CLEAR => {
+                self.digits.iter().for_each(|digit| digit.set(0));
+                crate::command_return::success()
+            }
+            PRINT_DOT => match self.digits.get(argument0 as usize) {
+                Some(digit) => {
+                    digit.set(digit.get() | DECIMAL_POINT);
+                    crate::command_return::success()
+                }
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            PRINT_CUSTOM => match (self.digits.get(argument0 as usize), u8::try_from(argument1)) {
+                (Some(digit), Ok(segments)) => {
+                    digit.set(segments);
+                    crate::command_return::success()
+                }
+                _ => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DECIMAL_POINT: u8 = 0x80;
+const MINUS: u8 = 0x40;
+const HEX_DIGITS: [u8; 16] = [
+    0x3f, 0x06, 0x5b, 0x4f, 0x66, 0x6d, 0x7d, 0x07, 0x7f, 0x6f, 0x77, 0x7c, 0x39, 0x5e, 0x79, 0x71,
+];
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90004;
+
+// Command IDs
+const DIGITS_COUNT_COMMAND: u32 = 0;
+const PRINT_DIGIT: u32 = 1;
+const CLEAR: u32 = 2;
+const PRINT_DOT: u32 = 3;
+const PRINT_CUSTOM: u32 = 4;
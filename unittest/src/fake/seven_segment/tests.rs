@@ -0,0 +1,71 @@
+use crate::fake;
+use fake::seven_segment::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let display = SevenSegment::<4>::new();
+    assert_eq!(
+        display
+            .command(DIGITS_COUNT_COMMAND, 0, 0)
+            .get_success_u32(),
+        Some(4)
+    );
+    assert!(display.command(PRINT_CUSTOM, 0, 0x06).is_success());
+    assert_eq!(display.get_segments(0), Some(0x06));
+    assert_eq!(display.get_segments(4), None);
+    assert_eq!(
+        display.command(PRINT_CUSTOM, 4, 0x06).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        display.command(PRINT_CUSTOM, 0, 0x100).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(display.command(PRINT_DIGIT, 1, 0xf).is_success());
+    assert_eq!(display.get_segments(1), Some(0x71));
+    assert_eq!(
+        display.command(PRINT_DIGIT, 1, 16).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(display.command(PRINT_DOT, 1, 0).is_success());
+    assert_eq!(display.get_segments(1), Some(0xf1));
+    assert_eq!(
+        display.command(PRINT_DOT, 4, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(display.command(CLEAR, 0, 0).is_success());
+    assert_eq!(display.get_segments(0), Some(0));
+}
+
+#[test]
+fn text() {
+    use fake::SyscallDriver;
+    let display = SevenSegment::<5>::new();
+    assert_eq!(display.text(), "     ");
+    assert!(display.command(PRINT_CUSTOM, 0, 0x40).is_success());
+    assert!(display.command(PRINT_CUSTOM, 1, 0x06 | 0x80).is_success());
+    assert!(display.command(PRINT_CUSTOM, 2, 0x71).is_success());
+    assert!(display.command(PRINT_CUSTOM, 4, 0x01).is_success());
+    assert_eq!(display.text(), "-1.f ?");
+}
+
+// Integration test that verifies SevenSegment works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let display = SevenSegment::<4>::new();
+    kernel.add_driver(&display);
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, DIGITS_COUNT_COMMAND, 0, 0).get_success_u32(),
+        Some(4)
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, PRINT_CUSTOM, 3, 0x3f).is_success());
+    assert_eq!(display.text(), "   0");
+    assert!(fake::Syscalls::command(DRIVER_NUM, CLEAR, 0, 0).is_success());
+    assert_eq!(display.text(), "    ");
+}
@@ -0,0 +1,155 @@
+//! Fake implementation of the SHA API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40005_sha.md
+//!
+//! Like the real API, `Sha` computes SHA-2 digests, using the `sha2` crate so
+//! tests can compare the results against known test vectors. Every operation
+//! completes immediately, by scheduling its upcall.
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+use libtock_platform::{CommandReturn, ErrorCode};
+use sha2::Digest;
+use std::cell::{Cell, RefCell};
+
+pub struct Sha {
+    algorithm: Cell<Option<u32>>,
+    message: RefCell<Vec<u8>>,
+    data_buffer: RefCell<RoAllowBuffer>,
+    digest_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Sha {
+    pub fn new() -> std::rc::Rc<Sha> {
+        std::rc::Rc::new(Sha {
+            algorithm: Cell::new(None),
+            message: Default::default(),
+            data_buffer: Default::default(),
+            digest_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the data added to the current digest so far.
+    pub fn message(&self) -> Vec<u8> {
+        self.message.borrow().clone()
+    }
+
+    // Writes the digest of the message into the digest buffer, and starts a
+    // new message. If the digest buffer is too small, the message is kept.
+    fn finish(&self, algorithm: u32) -> CommandReturn {
+        let digest_len = match algorithm {
+            ALGORITHM_SHA256 => 32,
+            ALGORITHM_SHA384 => 48,
+            _ => 64,
+        };
+        let mut buffer = self.digest_buffer.borrow_mut();
+        if buffer.len() < digest_len {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        let message = self.message.take();
+        let digest = match algorithm {
+            ALGORITHM_SHA256 => sha2::Sha256::digest(&message).to_vec(),
+            ALGORITHM_SHA384 => sha2::Sha384::digest(&message).to_vec(),
+            _ => sha2::Sha512::digest(&message).to_vec(),
+        };
+        buffer[..digest_len].copy_from_slice(&digest);
+        self.complete()
+    }
+
+    fn complete(&self) -> CommandReturn {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (0, 0, 0))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Sha {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DATA {
+            Ok(self.data_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DIGEST {
+            Ok(self.digest_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        if command_id == EXISTS {
+            return crate::command_return::success();
+        }
+        if command_id == SET_ALGORITHM {
+            if argument0 > ALGORITHM_SHA512 {
+                return crate::command_return::failure(ErrorCode::NoSupport);
+            }
+            self.algorithm.set(Some(argument0));
+            self.message.borrow_mut().clear();
+            return crate::command_return::success();
+        }
+        let algorithm = match self.algorithm.get() {
+            None => return crate::command_return::failure(ErrorCode::Invalid),
+            Some(algorithm) => algorithm,
+        };
+        match command_id {
+            RUN => {
+                let data = self.data_buffer.borrow();
+                self.message.borrow_mut().extend_from_slice(&data);
+                self.finish(algorithm)
+            }
+            UPDATE => {
+                let data = self.data_buffer.borrow();
+                self.message.borrow_mut().extend_from_slice(&data);
+                self.complete()
+            }
+            FINISH => self.finish(algorithm),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const RUN: u32 = 2;
+const UPDATE: u32 = 3;
+const FINISH: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_DATA: u32 = 0;
+const ALLOW_DIGEST: u32 = 0;
+
+const ALGORITHM_SHA256: u32 = 0;
+const ALGORITHM_SHA384: u32 = 1;
+const ALGORITHM_SHA512: u32 = 2;
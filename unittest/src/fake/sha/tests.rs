@@ -0,0 +1,130 @@
+use crate::fake::{self, SyscallDriver};
+use fake::sha::*;
+use libtock_platform::{share, AllowRo, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// SHA-256("abc"), from FIPS 180-2.
+const SHA256_ABC: [u8; 32] = [
+    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+];
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let sha = Sha::new();
+    assert!(sha.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        sha.command(UPDATE, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        sha.command(SET_ALGORITHM, 3, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(sha.command(SET_ALGORITHM, ALGORITHM_SHA256, 0).is_success());
+    // No digest buffer is shared.
+    assert_eq!(
+        sha.command(FINISH, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+}
+
+// Integration test that verifies Sha works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let sha = Sha::new();
+    kernel.add_driver(&sha);
+
+    let done = Cell::<Option<(u32,)>>::new(None);
+    let mut digest = [0; 64];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(allow_ro, b"ab").unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(allow_rw, &mut digest)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(
+            fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, ALGORITHM_SHA256, 0).is_success()
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, UPDATE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0,)));
+        assert_eq!(sha.message(), b"ab");
+    });
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(allow_ro, b"c").unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(allow_rw, &mut digest)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, RUN, 0, 0).is_success());
+        assert_eq!(sha.message(), b"");
+    });
+    assert_eq!(digest[..32], SHA256_ABC);
+    assert_eq!(digest[32..], [0; 32]);
+}
+
+// A digest buffer that is too small fails FINISH without discarding the
+// message, so the digest can be finished after sharing a larger buffer.
+#[test]
+fn finish_too_small() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let sha = Sha::new();
+    kernel.add_driver(&sha);
+
+    let mut small = [0; 16];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(allow_ro, b"abc")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(allow_rw, &mut small)
+            .unwrap();
+        assert!(
+            fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, ALGORITHM_SHA256, 0).is_success()
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, UPDATE, 0, 0).is_success());
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, FINISH, 0, 0).get_failure(),
+            Some(ErrorCode::Size)
+        );
+    });
+    assert_eq!(sha.message(), b"abc");
+
+    let mut digest = [0; 32];
+    share::scope::<AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>, _, _>(|allow_rw| {
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(allow_rw, &mut digest)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, FINISH, 0, 0).is_success());
+    });
+    assert_eq!(digest, SHA256_ABC);
+    assert_eq!(sha.message(), b"");
+}
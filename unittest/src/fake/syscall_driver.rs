@@ -1,4 +1,4 @@
-use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer, UrAllowBuffer};
 use libtock_platform::{CommandReturn, ErrorCode};
 
 /// The `fake::SyscallDriver` trait is implemented by fake versions of Tock's
@@ -54,4 +54,17 @@ pub trait SyscallDriver: 'static {
         let _ = buffer_num; // Silences the unused variable warning.
         Err((buffer, ErrorCode::NoSupport))
     }
+
+    /// Process a Userspace Readable Allow call. Because not all SyscallDriver
+    /// implementations need to support Userspace Readable Allow, a default
+    /// implementation is provided that rejects all Userspace Readable Allow
+    /// calls.
+    fn allow_userspace_readable(
+        &self,
+        buffer_num: u32,
+        buffer: UrAllowBuffer,
+    ) -> Result<UrAllowBuffer, (UrAllowBuffer, ErrorCode)> {
+        let _ = buffer_num; // Silences the unused variable warning.
+        Err((buffer, ErrorCode::NoSupport))
+    }
 }
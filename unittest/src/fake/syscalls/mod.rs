@@ -1,5 +1,6 @@
 mod allow_ro_impl;
 mod allow_rw_impl;
+mod allow_ur_impl;
 mod command_impl;
 mod exit_impl;
 mod raw_syscalls_impl;
@@ -16,6 +17,8 @@ mod allow_ro_impl_tests;
 #[cfg(test)]
 mod allow_rw_impl_tests;
 #[cfg(test)]
+mod allow_ur_impl_tests;
+#[cfg(test)]
 mod command_impl_tests;
 #[cfg(all(not(miri), test))]
 mod exit_impl_tests;
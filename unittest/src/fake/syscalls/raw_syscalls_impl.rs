@@ -48,6 +48,9 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
             syscall_class::COMMAND => super::command_impl::command(r0, r1, r2, r3),
             syscall_class::ALLOW_RW => unsafe { super::allow_rw_impl::allow_rw(r0, r1, r2, r3) },
             syscall_class::ALLOW_RO => unsafe { super::allow_ro_impl::allow_ro(r0, r1, r2, r3) },
+            syscall_class::USERSPACE_READABLE_ALLOW => unsafe {
+                super::allow_ur_impl::allow_userspace_readable(r0, r1, r2, r3)
+            },
             _ => panic!("Unknown syscall4 call. Class: {}", CLASS),
         }
     }
@@ -0,0 +1,182 @@
+//! Fake implementation of the text screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90003_text_screen.md
+//!
+//! `TextScreen` models a character display with a fixed number of rows and
+//! columns. Text written past the end of a row continues on the next row, and
+//! text written past the last row wraps around to the first row.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+
+pub struct TextScreen {
+    columns: u32,
+    rows: u32,
+    grid: RefCell<Vec<u8>>,
+    cursor: Cell<(u32, u32)>,
+    display_on: Cell<bool>,
+    blink: Cell<bool>,
+    cursor_visible: Cell<bool>,
+    buffer: Cell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl TextScreen {
+    pub fn new(columns: u32, rows: u32) -> std::rc::Rc<TextScreen> {
+        std::rc::Rc::new(TextScreen {
+            columns,
+            rows,
+            grid: RefCell::new(vec![b' '; (columns * rows) as usize]),
+            cursor: Cell::new((0, 0)),
+            display_on: Cell::new(false),
+            blink: Cell::new(false),
+            cursor_visible: Cell::new(false),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the contents of each row. Empty cells contain spaces.
+    pub fn rows(&self) -> Vec<String> {
+        self.grid
+            .borrow()
+            .chunks(self.columns as usize)
+            .map(|row| String::from_utf8_lossy(row).into_owned())
+            .collect()
+    }
+
+    /// Returns the cursor position as `(column, row)`.
+    pub fn cursor(&self) -> (u32, u32) {
+        self.cursor.get()
+    }
+
+    pub fn is_display_on(&self) -> bool {
+        self.display_on.get()
+    }
+
+    pub fn is_blinking(&self) -> bool {
+        self.blink.get()
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible.get()
+    }
+
+    fn write(&self, text: &[u8]) {
+        let mut grid = self.grid.borrow_mut();
+        let (mut column, mut row) = self.cursor.get();
+        for &byte in text {
+            grid[(row * self.columns + column) as usize] = byte;
+            column += 1;
+            if column == self.columns {
+                column = 0;
+                row = (row + 1) % self.rows;
+            }
+        }
+        self.cursor.set((column, row));
+    }
+
+    // Completes a command with an upcall.
+    fn complete(&self, data0: u32, data1: u32) -> CommandReturn {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (0, data0, data1))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for TextScreen {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            GET_SIZE => self.complete(self.columns, self.rows),
+            DISPLAY_ON | DISPLAY_OFF => {
+                self.display_on.set(command_id == DISPLAY_ON);
+                self.complete(0, 0)
+            }
+            BLINK_ON | BLINK_OFF => {
+                self.blink.set(command_id == BLINK_ON);
+                self.complete(0, 0)
+            }
+            SHOW_CURSOR | HIDE_CURSOR => {
+                self.cursor_visible.set(command_id == SHOW_CURSOR);
+                self.complete(0, 0)
+            }
+            WRITE => {
+                let buffer = self.buffer.take();
+                let result = buffer
+                    .get(..argument0 as usize)
+                    .map(|text| self.write(text));
+                self.buffer.set(buffer);
+                match result {
+                    Some(()) => self.complete(argument0, 0),
+                    None => crate::command_return::failure(ErrorCode::Invalid),
+                }
+            }
+            CLEAR => {
+                self.grid.borrow_mut().fill(b' ');
+                self.cursor.set((0, 0));
+                self.complete(0, 0)
+            }
+            HOME => {
+                self.cursor.set((0, 0));
+                self.complete(0, 0)
+            }
+            SET_CURSOR => {
+                if argument0 >= self.columns || argument1 >= self.rows {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.cursor.set((argument0, argument1));
+                self.complete(0, 0)
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const GET_SIZE: u32 = 1;
+const DISPLAY_ON: u32 = 2;
+const DISPLAY_OFF: u32 = 3;
+const BLINK_ON: u32 = 4;
+const BLINK_OFF: u32 = 5;
+const SHOW_CURSOR: u32 = 6;
+const HIDE_CURSOR: u32 = 7;
+const WRITE: u32 = 8;
+const CLEAR: u32 = 9;
+const HOME: u32 = 10;
+const SET_CURSOR: u32 = 11;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
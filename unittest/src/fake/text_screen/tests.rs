@@ -0,0 +1,77 @@
+use crate::fake::{self, SyscallDriver};
+use fake::text_screen::*;
+use libtock_platform::{share, AllowRo, DefaultConfig, Subscribe, YieldNoWaitReturn};
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let screen = TextScreen::new(4, 2);
+    assert!(screen.command(EXISTS, 0, 0).is_success());
+    assert!(screen.command(DISPLAY_ON, 0, 0).is_success());
+    assert!(screen.is_display_on());
+    assert!(screen.command(BLINK_ON, 0, 0).is_success());
+    assert!(screen.is_blinking());
+    assert!(screen.command(SHOW_CURSOR, 0, 0).is_success());
+    assert!(screen.is_cursor_visible());
+    assert!(screen.command(HIDE_CURSOR, 0, 0).is_success());
+    assert!(!screen.is_cursor_visible());
+
+    assert_eq!(
+        screen.command(SET_CURSOR, 4, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(screen.command(SET_CURSOR, 3, 1).is_success());
+    assert_eq!(screen.cursor(), (3, 1));
+    assert!(screen.command(HOME, 0, 0).is_success());
+    assert_eq!(screen.cursor(), (0, 0));
+    // No buffer is shared.
+    assert_eq!(
+        screen.command(WRITE, 1, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies TextScreen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let screen = TextScreen::new(4, 2);
+    kernel.add_driver(&screen);
+
+    let called = Cell::<Option<(u32, u32, u32)>>::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"abcdefghij")
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &called,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_SIZE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.get(), Some((0, 4, 2)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_CURSOR, 2, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 5, 0).is_success());
+        assert_eq!(screen.rows(), ["  ab", "cde "]);
+        assert_eq!(screen.cursor(), (3, 1));
+        // Writing past the last row wraps around to the first row.
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 3, 0).is_success());
+        assert_eq!(screen.rows(), ["bcab", "cdea"]);
+        assert_eq!(screen.cursor(), (2, 0));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, CLEAR, 0, 0).is_success());
+        assert_eq!(screen.rows(), ["    ", "    "]);
+        assert_eq!(screen.cursor(), (0, 0));
+    });
+}
@@ -0,0 +1,108 @@
+//! Fake implementation of the USB HID keyboard API.
+//!
+//! Like the real API, `UsbKeyboard` sends the report in the send buffer to
+//! the host. The reports are captured instead, and can be retrieved with
+//! `take_reports`. Every report is sent immediately, by scheduling the upcall,
+//! unless `set_send_error` makes sends fail.
+
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::cell::{Cell, RefCell};
+
+pub struct UsbKeyboard {
+    reports: RefCell<Vec<[u8; REPORT_LEN]>>,
+    send_error: Cell<Option<ErrorCode>>,
+    send_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl UsbKeyboard {
+    pub fn new() -> std::rc::Rc<UsbKeyboard> {
+        std::rc::Rc::new(UsbKeyboard {
+            reports: Default::default(),
+            send_error: Cell::new(None),
+            send_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the reports sent since the last call, in the order they were
+    /// sent.
+    pub fn take_reports(&self) -> Vec<[u8; REPORT_LEN]> {
+        self.reports.take()
+    }
+
+    /// Makes later sends report `error` in the upcall instead of sending the
+    /// report, or sends reports again if `error` is `None`.
+    pub fn set_send_error(&self, error: Option<ErrorCode>) {
+        self.send_error.set(error);
+    }
+}
+
+impl crate::fake::SyscallDriver for UsbKeyboard {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SEND {
+            Ok(self.send_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_id {
+            EXISTS => crate::command_return::success(),
+            SEND => {
+                let buffer = self.send_buffer.borrow();
+                let report = match buffer.get(..REPORT_LEN) {
+                    None => return crate::command_return::failure(ErrorCode::Size),
+                    Some(report) => report.try_into().unwrap(),
+                };
+                let status = match self.send_error.get() {
+                    Some(error) => error as u32,
+                    None => {
+                        self.reports.borrow_mut().push(report);
+                        SENT
+                    }
+                };
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (status, 0, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90005;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const SEND: u32 = 1;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_SEND: u32 = 1;
+
+// The first argument of the DONE upcall when a report was sent.
+const SENT: u32 = 1;
+
+const REPORT_LEN: usize = 8;
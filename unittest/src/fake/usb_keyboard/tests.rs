@@ -0,0 +1,57 @@
+use crate::fake::{self, SyscallDriver};
+use fake::usb_keyboard::*;
+use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, YieldNoWaitReturn};
+use std::cell::Cell;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    let keyboard = UsbKeyboard::new();
+    assert!(keyboard.command(EXISTS, 0, 0).is_success());
+    // No send buffer is shared.
+    assert_eq!(
+        keyboard.command(SEND, 0, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(keyboard.take_reports(), Vec::<[u8; REPORT_LEN]>::new());
+}
+
+// Integration test that verifies UsbKeyboard works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let keyboard = UsbKeyboard::new();
+    kernel.add_driver(&keyboard);
+
+    let done = Cell::<Option<(u32,)>>::new(None);
+    let mut report = [2, 0, 4, 0, 0, 0, 0, 0];
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_SEND>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_SEND>(allow_rw, &mut report)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((SENT,)));
+
+        keyboard.set_send_error(Some(ErrorCode::Busy));
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::Busy as u32,)));
+    });
+    assert_eq!(keyboard.take_reports(), [[2, 0, 4, 0, 0, 0, 0, 0]]);
+    assert_eq!(keyboard.take_reports(), Vec::<[u8; REPORT_LEN]>::new());
+}
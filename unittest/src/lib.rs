@@ -15,7 +15,7 @@ mod share_data;
 mod syscall_log;
 pub mod upcall;
 
-pub use allow_db::{RoAllowBuffer, RwAllowBuffer};
+pub use allow_db::{RoAllowBuffer, RwAllowBuffer, UrAllowBuffer};
 pub use driver_info::DriverInfo;
 #[cfg(not(miri))]
 pub use exit_test::{exit_test, ExitCall};
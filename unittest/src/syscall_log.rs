@@ -43,6 +43,14 @@ pub enum SyscallLogEntry {
         buffer_num: u32,
         len: usize,
     },
+    // -------------------------------------------------------------------------
+    // Userspace Readable Allow
+    // -------------------------------------------------------------------------
+    AllowUserspaceReadable {
+        driver_num: u32,
+        buffer_num: u32,
+        len: usize,
+    },
     // TODO: Add Memop.
     // TODO: Add Exit.
 }